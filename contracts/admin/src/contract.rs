@@ -1,7 +1,7 @@
 use shade_protocol::{
     admin::{
-        errors::unauthorized_super, AdminAuthStatus, AdminsResponse, ConfigResponse, ExecuteMsg,
-        InstantiateMsg, PermissionsResponse, QueryMsg,
+        errors::unauthorized_super, AdminAuthStatus, AuditAction, ConfigResponse, ExecuteMsg,
+        InstantiateMsg, PausedPermissionsResponse, PermissionsResponse, QueryMsg,
     },
     c_std::{
         shd_entry_point, to_binary, Addr, Deps, DepsMut, Env, MessageInfo, QueryResponse, Response,
@@ -12,11 +12,15 @@ use shade_protocol::{
 
 use crate::{
     execute::{
-        try_self_destruct, try_toggle_status, try_transfer_super, try_update_registry,
+        try_accept_super, try_cancel_transfer_super, try_self_destruct,
+        try_toggle_permission_status, try_toggle_status, try_transfer_super, try_update_registry,
         try_update_registry_bulk,
     },
-    query::query_validate_permission,
-    shared::{ADMINS, PERMISSIONS, STATUS, SUPER},
+    query::{query_admins, query_audit_log, query_permission_holders, query_validate_permission},
+    shared::{
+        active_permissions, record_audit, ADMINS, PAUSED_PERMISSIONS, PENDING_SUPER, PERMISSIONS,
+        STATUS, SUPER,
+    },
 };
 
 pub const RESPONSE_BLOCK_SIZE: usize = 256;
@@ -44,13 +48,20 @@ pub fn instantiate(
 #[cfg_attr(not(feature = "library"), shd_entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> StdResult<Response> {
-    // Only the super user can execute anything on this contract.
-    is_super(deps.storage, &info.sender)?;
-    // Super user is assumed to have been verified by this point.
+    // Only the super user can execute on this contract, except for the proposed super user
+    // accepting the transfer, which is checked in try_accept_super.
+    if !matches!(msg, ExecuteMsg::AcceptSuper {}) {
+        is_super(deps.storage, &info.sender)?;
+    }
+    // Failed messages revert the whole transaction so the entries can be written first.
+    for action in audit_actions(&msg) {
+        record_audit(deps.storage, &env, &info.sender, action)?;
+    }
+    // Sender is assumed to have been verified by this point.
     pad_handle_result(
         match msg {
             ExecuteMsg::UpdateRegistry { action } => {
@@ -58,13 +69,49 @@ pub fn execute(
             }
             ExecuteMsg::UpdateRegistryBulk { actions } => try_update_registry_bulk(deps, actions),
             ExecuteMsg::TransferSuper { new_super } => try_transfer_super(deps, new_super),
+            ExecuteMsg::AcceptSuper {} => try_accept_super(deps, info),
+            ExecuteMsg::CancelTransferSuper {} => try_cancel_transfer_super(deps),
             ExecuteMsg::SelfDestruct {} => try_self_destruct(deps),
             ExecuteMsg::ToggleStatus { new_status } => try_toggle_status(deps, new_status),
+            ExecuteMsg::TogglePermissionStatus {
+                permissions,
+                paused,
+            } => try_toggle_permission_status(deps, permissions, paused),
         },
         RESPONSE_BLOCK_SIZE,
     )
 }
 
+fn audit_actions(msg: &ExecuteMsg) -> Vec<AuditAction> {
+    match msg {
+        ExecuteMsg::UpdateRegistry { action } => vec![AuditAction::Registry {
+            action: action.clone(),
+        }],
+        ExecuteMsg::UpdateRegistryBulk { actions } => actions
+            .iter()
+            .map(|action| AuditAction::Registry {
+                action: action.clone(),
+            })
+            .collect(),
+        ExecuteMsg::TransferSuper { new_super } => vec![AuditAction::TransferSuper {
+            new_super: new_super.clone(),
+        }],
+        ExecuteMsg::AcceptSuper {} => vec![AuditAction::AcceptSuper {}],
+        ExecuteMsg::CancelTransferSuper {} => vec![AuditAction::CancelTransferSuper {}],
+        ExecuteMsg::SelfDestruct {} => vec![AuditAction::SelfDestruct {}],
+        ExecuteMsg::ToggleStatus { new_status } => vec![AuditAction::ToggleStatus {
+            new_status: new_status.clone(),
+        }],
+        ExecuteMsg::TogglePermissionStatus {
+            permissions,
+            paused,
+        } => vec![AuditAction::TogglePermissionStatus {
+            permissions: permissions.clone(),
+            paused: *paused,
+        }],
+    }
+}
+
 fn is_super(storage: &dyn Storage, address: &Addr) -> StdResult<()> {
     let super_admin = SUPER.load(storage)?;
     if super_admin == *address {
@@ -75,23 +122,23 @@ fn is_super(storage: &dyn Storage, address: &Addr) -> StdResult<()> {
 }
 
 #[cfg_attr(not(feature = "library"), shd_entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<QueryResponse> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<QueryResponse> {
     Ok(match msg {
         QueryMsg::GetConfig {} => to_binary(&ConfigResponse {
             super_admin: SUPER.load(deps.storage)?,
+            pending_super_admin: PENDING_SUPER.may_load(deps.storage)?,
             status: STATUS.load(deps.storage)?,
         }),
         QueryMsg::ValidateAdminPermission { permission, user } => {
-            to_binary(&query_validate_permission(deps, permission, user)?)
+            to_binary(&query_validate_permission(
+                deps,
+                permission,
+                user,
+                env.block.time.seconds(),
+            )?)
         }
-        QueryMsg::GetAdmins {} => {
-            STATUS
-                .load(deps.storage)?
-                .not_shutdown()?
-                .not_under_maintenance()?;
-            to_binary(&AdminsResponse {
-                admins: ADMINS.load(deps.storage)?,
-            })
+        QueryMsg::GetAdmins { page, page_size } => {
+            to_binary(&query_admins(deps, page.unwrap_or(0), page_size)?)
         }
         QueryMsg::GetPermissions { user } => {
             STATUS
@@ -100,8 +147,24 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<QueryResponse> {
                 .not_under_maintenance()?;
             let validated_user = deps.api.addr_validate(user.as_str())?;
             to_binary(&PermissionsResponse {
-                permissions: PERMISSIONS.load(deps.storage, &validated_user)?,
+                permissions: active_permissions(
+                    deps.storage,
+                    &validated_user,
+                    PERMISSIONS.load(deps.storage, &validated_user)?,
+                    env.block.time.seconds(),
+                )?,
             })
         }
+        QueryMsg::GetPermissionHolders { permission } => to_binary(&query_permission_holders(
+            deps,
+            permission,
+            env.block.time.seconds(),
+        )?),
+        QueryMsg::GetAuditLog { start, end } => to_binary(&query_audit_log(deps, start, end)?),
+        QueryMsg::GetPausedPermissions {} => to_binary(&PausedPermissionsResponse {
+            permissions: PAUSED_PERMISSIONS
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        }),
     }?)
 }
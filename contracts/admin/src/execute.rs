@@ -1,7 +1,10 @@
-use crate::shared::{validate_permissions, ADMINS, PERMISSIONS, STATUS, SUPER};
-use shade_protocol::admin::errors::{no_permission, unregistered_admin};
+use crate::shared::{
+    validate_permissions, ADMINS, PAUSED_PERMISSIONS, PENDING_SUPER, PERMISSIONS,
+    PERMISSION_EXPIRATIONS, STATUS, SUPER,
+};
+use shade_protocol::admin::errors::{no_pending_super, no_permission, unregistered_admin};
 use shade_protocol::admin::{AdminAuthStatus, RegistryAction};
-use shade_protocol::c_std::{Addr, Api, DepsMut, Response, StdResult, Storage};
+use shade_protocol::c_std::{Addr, Api, DepsMut, MessageInfo, Response, StdResult, Storage};
 
 /// Performs one registry update. Cannot be run during a shutdown.
 pub fn try_update_registry(
@@ -30,28 +33,49 @@ pub fn try_update_registry_bulk(
     Ok(Response::default())
 }
 
+/// Proposes the new super, the transfer only happens once they accept it.
 pub fn try_transfer_super(deps: DepsMut, new_super: String) -> StdResult<Response> {
     let valid_super = deps.api.addr_validate(new_super.as_str())?;
     // If you're trying to transfer the super permissions to someone who hasn't been registered as an admin,
     // it won't work. This is a safeguard.
-    let mut admins = ADMINS.load(deps.storage)?;
+    let admins = ADMINS.load(deps.storage)?;
     if !admins.contains(&valid_super) {
         return Err(unregistered_admin(valid_super.as_str()));
-    } else {
-        // Update the super and remove them from the admin list.
-        SUPER.save(deps.storage, &valid_super)?;
-        delete_admin(deps.storage, &mut admins, deps.api, new_super)?;
-        ADMINS.save(deps.storage, &admins)?;
     }
+    PENDING_SUPER.save(deps.storage, &valid_super)?;
+    Ok(Response::default())
+}
+
+pub fn try_accept_super(deps: DepsMut, info: MessageInfo) -> StdResult<Response> {
+    match PENDING_SUPER.may_load(deps.storage)? {
+        Some(pending) if pending == info.sender => {}
+        _ => return Err(no_pending_super(info.sender.as_str())),
+    }
+    // They could have been removed as an admin after being proposed.
+    let mut admins = ADMINS.load(deps.storage)?;
+    if !admins.contains(&info.sender) {
+        return Err(unregistered_admin(info.sender.as_str()));
+    }
+    // Update the super and remove them from the admin list.
+    SUPER.save(deps.storage, &info.sender)?;
+    PENDING_SUPER.remove(deps.storage);
+    delete_admin(deps.storage, &mut admins, deps.api, info.sender.to_string())?;
+    ADMINS.save(deps.storage, &admins)?;
+    Ok(Response::default())
+}
+
+pub fn try_cancel_transfer_super(deps: DepsMut) -> StdResult<Response> {
+    PENDING_SUPER.remove(deps.storage);
     Ok(Response::default())
 }
 
 pub fn try_self_destruct(deps: DepsMut) -> StdResult<Response> {
     // Clear permissions
     let admins = ADMINS.load(deps.storage)?;
-    admins
-        .iter()
-        .for_each(|admin| PERMISSIONS.remove(deps.storage, admin));
+    for admin in admins.iter() {
+        clear_expirations(deps.storage, admin)?;
+        PERMISSIONS.remove(deps.storage, admin);
+    }
     // Clear admins
     ADMINS.save(deps.storage, &vec![])?;
     // Disable contract
@@ -64,6 +88,27 @@ pub fn try_toggle_status(deps: DepsMut, new_status: AdminAuthStatus) -> StdResul
     Ok(Response::default())
 }
 
+pub fn try_toggle_permission_status(
+    deps: DepsMut,
+    mut permissions: Vec<String>,
+    paused: bool,
+) -> StdResult<Response> {
+    validate_permissions(permissions.as_slice())?;
+    let mut paused_permissions = PAUSED_PERMISSIONS
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    if paused {
+        permissions.sort();
+        permissions.dedup();
+        permissions.retain(|c| !paused_permissions.contains(c));
+        paused_permissions.append(&mut permissions);
+    } else {
+        paused_permissions.retain(|c| !permissions.contains(c));
+    }
+    PAUSED_PERMISSIONS.save(deps.storage, &paused_permissions)?;
+    Ok(Response::default())
+}
+
 fn resolve_registry_action(
     store: &mut dyn Storage,
     admins: &mut Vec<Addr>,
@@ -72,9 +117,11 @@ fn resolve_registry_action(
 ) -> StdResult<()> {
     match action {
         RegistryAction::RegisterAdmin { user } => register_admin(store, admins, api, user),
-        RegistryAction::GrantAccess { permissions, user } => {
-            grant_access(store, api, admins, permissions, user)
-        }
+        RegistryAction::GrantAccess {
+            permissions,
+            user,
+            expiration,
+        } => grant_access(store, api, admins, permissions, user, expiration),
         RegistryAction::RevokeAccess { permissions, user } => {
             revoke_access(store, api, admins, permissions, user)
         }
@@ -109,6 +156,7 @@ fn delete_admin(
         // Delete admin from list.
         admins.retain(|x| x.ne(&user_addr));
         // Delete their permissions.
+        clear_expirations(store, &user_addr)?;
         PERMISSIONS.remove(store, &user_addr);
     };
     Ok(())
@@ -120,10 +168,20 @@ fn grant_access(
     admins: &[Addr],
     mut permissions: Vec<String>,
     user: String,
+    expiration: Option<u64>,
 ) -> StdResult<()> {
     let user = api.addr_validate(user.as_str())?;
     validate_permissions(permissions.as_slice())?;
     verify_registered(admins, &user)?;
+    // Granting again replaces the previous expiration.
+    for permission in permissions.iter() {
+        match expiration {
+            Some(expiration) => {
+                PERMISSION_EXPIRATIONS.save(store, (&user, permission.as_str()), &expiration)?
+            }
+            None => PERMISSION_EXPIRATIONS.remove(store, (&user, permission.as_str())),
+        }
+    }
     PERMISSIONS.update(store, &user, |old_perms| -> StdResult<_> {
         match old_perms {
             Some(mut old_perms) => {
@@ -156,6 +214,18 @@ fn revoke_access(
             None => Err(no_permission(user.as_str())),
         }
     })?;
+    for permission in permissions.iter() {
+        PERMISSION_EXPIRATIONS.remove(store, (&user, permission.as_str()));
+    }
+    Ok(())
+}
+
+fn clear_expirations(store: &mut dyn Storage, user: &Addr) -> StdResult<()> {
+    if let Some(permissions) = PERMISSIONS.may_load(store, user)? {
+        for permission in permissions.iter() {
+            PERMISSION_EXPIRATIONS.remove(store, (user, permission.as_str()));
+        }
+    }
     Ok(())
 }
 
@@ -1,20 +1,38 @@
-use crate::shared::{is_valid_permission, PERMISSIONS, STATUS, SUPER};
+use crate::shared::{
+    is_expired, is_valid_permission, ADMINS, AUDIT_LOG, AUDIT_LOG_COUNT, PAUSED_PERMISSIONS,
+    PERMISSIONS, STATUS, SUPER,
+};
 use shade_protocol::{
-    admin::{errors::unregistered_admin, ValidateAdminPermissionResponse},
+    admin::{
+        errors::{permission_under_maintenance, unregistered_admin},
+        AdminsResponse, AuditLogResponse, PermissionHoldersResponse,
+        ValidateAdminPermissionResponse,
+    },
     c_std::{Deps, StdResult},
 };
 
-/// Checks if the user has the requested permission. Permissions are case sensitive.
+/// Max number of audit log entries returned per query.
+pub const AUDIT_LOG_PAGE_LIMIT: u64 = 100;
+
+/// Checks if the user has the requested permission and that it hasn't lapsed. Permissions are case sensitive.
 pub fn query_validate_permission(
     deps: Deps,
     permission: String,
     user: String,
+    now: u64,
 ) -> StdResult<ValidateAdminPermissionResponse> {
     STATUS
         .load(deps.storage)?
         .not_shutdown()?
         .not_under_maintenance()?;
     is_valid_permission(permission.as_str())?;
+    if PAUSED_PERMISSIONS
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .contains(&permission)
+    {
+        return Err(permission_under_maintenance(permission.as_str()));
+    }
     let valid_user = deps.api.addr_validate(user.as_str())?;
     let super_admin = SUPER.load(deps.storage)?;
 
@@ -28,7 +46,8 @@ pub fn query_validate_permission(
         match permissions {
             Some(permissions) => {
                 if permissions.iter().any(|perm| permission.eq(perm)) {
-                    has_permission = true;
+                    has_permission =
+                        !is_expired(deps.storage, &valid_user, permission.as_str(), now)?;
                 } else {
                     has_permission = false;
                 }
@@ -38,3 +57,57 @@ pub fn query_validate_permission(
     }
     Ok(ValidateAdminPermissionResponse { has_permission })
 }
+
+pub fn query_admins(deps: Deps, page: u32, page_size: Option<u32>) -> StdResult<AdminsResponse> {
+    STATUS
+        .load(deps.storage)?
+        .not_shutdown()?
+        .not_under_maintenance()?;
+    let admins = ADMINS.load(deps.storage)?;
+    let total = admins.len() as u32;
+    let admins = match page_size {
+        None => admins,
+        Some(page_size) => admins
+            .into_iter()
+            .skip(page.saturating_mul(page_size) as usize)
+            .take(page_size as usize)
+            .collect(),
+    };
+    Ok(AdminsResponse { admins, total })
+}
+
+/// Lists the registered admins that currently hold the permission.
+pub fn query_permission_holders(
+    deps: Deps,
+    permission: String,
+    now: u64,
+) -> StdResult<PermissionHoldersResponse> {
+    STATUS
+        .load(deps.storage)?
+        .not_shutdown()?
+        .not_under_maintenance()?;
+    is_valid_permission(permission.as_str())?;
+
+    let mut holders = vec![];
+    for admin in ADMINS.load(deps.storage)? {
+        let permissions = PERMISSIONS.may_load(deps.storage, &admin)?.unwrap_or_default();
+        if permissions.contains(&permission)
+            && !is_expired(deps.storage, &admin, permission.as_str(), now)?
+        {
+            holders.push(admin);
+        }
+    }
+    Ok(PermissionHoldersResponse { holders })
+}
+
+/// Returns the entries with ids in [start, end). Available even when shutdown.
+pub fn query_audit_log(deps: Deps, start: u64, end: u64) -> StdResult<AuditLogResponse> {
+    let total = AUDIT_LOG_COUNT.may_load(deps.storage)?.unwrap_or_default();
+    let end = end.min(total).min(start.saturating_add(AUDIT_LOG_PAGE_LIMIT));
+
+    let mut entries = vec![];
+    for id in start..end {
+        entries.push(AUDIT_LOG.load(deps.storage, id)?);
+    }
+    Ok(AuditLogResponse { entries, total })
+}
@@ -1,8 +1,8 @@
 use shade_protocol::c_std::Addr;
 use shade_protocol::utils::storage::plus::{Item, Map};
 use shade_protocol::{
-    admin::{errors::invalid_permission_format, AdminAuthStatus},
-    c_std::StdResult,
+    admin::{errors::invalid_permission_format, AdminAuthStatus, AuditAction, AuditLogEntry},
+    c_std::{Env, StdResult, Storage},
 };
 
 /// Maps user to permissions for which they have user.
@@ -11,9 +11,63 @@ pub const PERMISSIONS: Map<&Addr, Vec<String>> = Map::new("permissions");
 pub const ADMINS: Item<Vec<Addr>> = Item::new("admins");
 /// Super user.
 pub const SUPER: Item<Addr> = Item::new("super");
+/// User proposed as the next super user.
+pub const PENDING_SUPER: Item<Addr> = Item::new("pending_super");
+/// Time in seconds when a user's permission lapses, permissions without one never do.
+pub const PERMISSION_EXPIRATIONS: Map<(&Addr, &str), u64> = Map::new("permission_expirations");
+/// Append only log of every change made to the contract.
+pub const AUDIT_LOG: Map<u64, AuditLogEntry> = Map::new("audit_log");
+/// Number of audit log entries.
+pub const AUDIT_LOG_COUNT: Item<u64> = Item::new("audit_log_count");
+/// Permissions that can't be validated while the rest of the contract stays active.
+pub const PAUSED_PERMISSIONS: Item<Vec<String>> = Item::new("paused_permissions");
 /// Whether or not this contract can be consumed.
 pub const STATUS: Item<AdminAuthStatus> = Item::new("is_active");
 
+pub fn record_audit(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    action: AuditAction,
+) -> StdResult<()> {
+    let id = AUDIT_LOG_COUNT.may_load(storage)?.unwrap_or_default();
+    AUDIT_LOG.save(storage, id, &AuditLogEntry {
+        id,
+        time: env.block.time.seconds(),
+        sender: sender.clone(),
+        action,
+    })?;
+    AUDIT_LOG_COUNT.save(storage, &(id + 1))
+}
+
+/// Filters out the permissions that have lapsed.
+pub fn active_permissions(
+    storage: &dyn Storage,
+    user: &Addr,
+    permissions: Vec<String>,
+    now: u64,
+) -> StdResult<Vec<String>> {
+    let mut active = vec![];
+    for permission in permissions {
+        if !is_expired(storage, user, permission.as_str(), now)? {
+            active.push(permission);
+        }
+    }
+    Ok(active)
+}
+
+pub fn is_expired(
+    storage: &dyn Storage,
+    user: &Addr,
+    permission: &str,
+    now: u64,
+) -> StdResult<bool> {
+    Ok(match PERMISSION_EXPIRATIONS.may_load(storage, (user, permission))? {
+        None => false,
+        Some(expiration) => now >= expiration,
+    })
+}
+
 pub fn validate_permissions(permissions: &[String]) -> StdResult<()> {
     for permission in permissions {
         is_valid_permission(permission.as_str())?;
@@ -3,8 +3,9 @@ use rstest::*;
 use shade_multi_test::multi::admin::Admin;
 use shade_protocol::{
     admin::{
-        AdminAuthStatus, AdminsResponse, ConfigResponse, ExecuteMsg, InstantiateMsg,
-        PermissionsResponse, QueryMsg, RegistryAction, ValidateAdminPermissionResponse,
+        AdminAuthStatus, AdminsResponse, AuditAction, AuditLogResponse, ConfigResponse, ExecuteMsg,
+        InstantiateMsg, PausedPermissionsResponse, PermissionHoldersResponse, PermissionsResponse,
+        QueryMsg, RegistryAction, ValidateAdminPermissionResponse,
     },
     c_std::Addr,
     multi_test::App,
@@ -76,6 +77,9 @@ fn test_status(#[case] status: AdminAuthStatus, #[case] expect_success: Vec<bool
     }
     .test_exec(&contract, &mut chain, Addr::unchecked("admin"), &[]);
     assert_eq!(&result.is_ok(), expect_success.get(2).unwrap());
+    let result =
+        ExecuteMsg::AcceptSuper {}.test_exec(&contract, &mut chain, Addr::unchecked("super"), &[]);
+    assert_eq!(&result.is_ok(), expect_success.get(2).unwrap());
 
     //register 'admin' as admin without being the super user
     let action = RegistryAction::RegisterAdmin {
@@ -103,6 +107,9 @@ fn test_status(#[case] status: AdminAuthStatus, #[case] expect_success: Vec<bool
     }
     .test_exec(&contract, &mut chain, Addr::unchecked("super"), &[]);
     assert_eq!(&result.is_ok(), expect_success.get(5).unwrap());
+    let result =
+        ExecuteMsg::AcceptSuper {}.test_exec(&contract, &mut chain, Addr::unchecked("admin"), &[]);
+    assert_eq!(&result.is_ok(), expect_success.get(5).unwrap());
 
     //self destruct
     let result =
@@ -139,9 +146,12 @@ fn test_admin(
     assert_eq!(config.status, AdminAuthStatus::Active);
 
     //read admins
-    let response: AdminsResponse = QueryMsg::GetAdmins {}
-        .test_query(&admin_contract, &chain)
-        .unwrap();
+    let response: AdminsResponse = QueryMsg::GetAdmins {
+        page: None,
+        page_size: None,
+    }
+    .test_query(&admin_contract, &chain)
+    .unwrap();
     assert!(response.admins.is_empty());
 
     //add admins
@@ -156,9 +166,12 @@ fn test_admin(
     }
 
     //read admins
-    let response: AdminsResponse = QueryMsg::GetAdmins {}
-        .test_query(&admin_contract, &chain)
-        .unwrap();
+    let response: AdminsResponse = QueryMsg::GetAdmins {
+        page: None,
+        page_size: None,
+    }
+    .test_query(&admin_contract, &chain)
+    .unwrap();
     let admin_list = response.admins;
     let admin_list_str: Vec<String> = admin_list.into_iter().map(|x| x.to_string()).collect();
     for admin in admins_to_add.iter() {
@@ -177,9 +190,12 @@ fn test_admin(
     }
 
     //read admins
-    let response: AdminsResponse = QueryMsg::GetAdmins {}
-        .test_query(&admin_contract, &chain)
-        .unwrap();
+    let response: AdminsResponse = QueryMsg::GetAdmins {
+        page: None,
+        page_size: None,
+    }
+    .test_query(&admin_contract, &chain)
+    .unwrap();
     let admin_list = response.admins;
     let admin_list_str: Vec<String> = admin_list.into_iter().map(|x| x.to_string()).collect();
     for (i, admin) in admins_to_add.iter().enumerate() {
@@ -202,9 +218,12 @@ fn test_admin(
         .unwrap();
 
     //read admins
-    let response: AdminsResponse = QueryMsg::GetAdmins {}
-        .test_query(&admin_contract, &chain)
-        .unwrap();
+    let response: AdminsResponse = QueryMsg::GetAdmins {
+        page: None,
+        page_size: None,
+    }
+    .test_query(&admin_contract, &chain)
+    .unwrap();
     let admin_list = response.admins;
     let admin_list_str: Vec<String> = admin_list.into_iter().map(|x| x.to_string()).collect();
     for admin in &admins_to_add {
@@ -250,6 +269,7 @@ fn test_permissions(
             RegistryAction::GrantAccess {
                 permissions: permission.1.iter().map(|&i| i.to_string()).collect(),
                 user: permission.0.to_string(),
+                expiration: None,
             },
         ])
     }
@@ -349,3 +369,392 @@ fn test_permissions(
         }
     }
 }
+
+#[test]
+fn test_transfer_super() {
+    let mut chain = App::default();
+
+    let admin = InstantiateMsg { super_admin: None }
+        .test_init(
+            Admin::default(),
+            &mut chain,
+            Addr::unchecked("admin"),
+            "admin_contract",
+            &[],
+        )
+        .unwrap();
+
+    ExecuteMsg::UpdateRegistryBulk {
+        actions: vec![
+            RegistryAction::RegisterAdmin {
+                user: "new_super".to_string(),
+            },
+            RegistryAction::RegisterAdmin {
+                user: "other".to_string(),
+            },
+        ],
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    // Nothing to accept yet
+    assert!(ExecuteMsg::AcceptSuper {}
+        .test_exec(&admin, &mut chain, Addr::unchecked("new_super"), &[])
+        .is_err());
+
+    ExecuteMsg::TransferSuper {
+        new_super: "new_super".to_string(),
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let config: ConfigResponse = QueryMsg::GetConfig {}.test_query(&admin, &chain).unwrap();
+    assert_eq!(config.super_admin.as_str(), "admin");
+    assert_eq!(
+        config.pending_super_admin,
+        Some(Addr::unchecked("new_super"))
+    );
+
+    // Only the proposed user can accept
+    assert!(ExecuteMsg::AcceptSuper {}
+        .test_exec(&admin, &mut chain, Addr::unchecked("other"), &[])
+        .is_err());
+
+    // Cancelled proposals can't be accepted
+    ExecuteMsg::CancelTransferSuper {}
+        .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+        .unwrap();
+    assert!(ExecuteMsg::AcceptSuper {}
+        .test_exec(&admin, &mut chain, Addr::unchecked("new_super"), &[])
+        .is_err());
+
+    ExecuteMsg::TransferSuper {
+        new_super: "new_super".to_string(),
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+    ExecuteMsg::AcceptSuper {}
+        .test_exec(&admin, &mut chain, Addr::unchecked("new_super"), &[])
+        .unwrap();
+
+    let config: ConfigResponse = QueryMsg::GetConfig {}.test_query(&admin, &chain).unwrap();
+    assert_eq!(config.super_admin.as_str(), "new_super");
+    assert_eq!(config.pending_super_admin, None);
+
+    let response: AdminsResponse = QueryMsg::GetAdmins {
+        page: None,
+        page_size: None,
+    }
+    .test_query(&admin, &chain)
+    .unwrap();
+    assert_eq!(response.admins, vec![Addr::unchecked("other")]);
+
+    // Old super lost its powers
+    assert!(ExecuteMsg::CancelTransferSuper {}
+        .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+        .is_err());
+}
+
+#[test]
+fn test_permission_expiration() {
+    let mut chain = App::default();
+
+    let admin = InstantiateMsg { super_admin: None }
+        .test_init(
+            Admin::default(),
+            &mut chain,
+            Addr::unchecked("admin"),
+            "admin_contract",
+            &[],
+        )
+        .unwrap();
+
+    let expiration = chain.block_info().time.seconds() + 100;
+
+    ExecuteMsg::UpdateRegistryBulk {
+        actions: vec![
+            RegistryAction::RegisterAdmin {
+                user: "operator".to_string(),
+            },
+            RegistryAction::GrantAccess {
+                permissions: vec!["TEMPORARY_ACCESS".to_string()],
+                user: "operator".to_string(),
+                expiration: Some(expiration),
+            },
+            RegistryAction::GrantAccess {
+                permissions: vec!["PERMANENT_ACCESS".to_string()],
+                user: "operator".to_string(),
+                expiration: None,
+            },
+        ],
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let has_permission = |chain: &App, permission: &str| -> bool {
+        let res: ValidateAdminPermissionResponse = QueryMsg::ValidateAdminPermission {
+            permission: permission.to_string(),
+            user: "operator".to_string(),
+        }
+        .test_query(&admin, chain)
+        .unwrap();
+        res.has_permission
+    };
+
+    assert!(has_permission(&chain, "TEMPORARY_ACCESS"));
+    assert!(has_permission(&chain, "PERMANENT_ACCESS"));
+
+    chain.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    assert!(!has_permission(&chain, "TEMPORARY_ACCESS"));
+    assert!(has_permission(&chain, "PERMANENT_ACCESS"));
+
+    let stored_permissions: PermissionsResponse = QueryMsg::GetPermissions {
+        user: "operator".to_string(),
+    }
+    .test_query(&admin, &chain)
+    .unwrap();
+    assert_eq!(
+        stored_permissions.permissions,
+        vec!["PERMANENT_ACCESS".to_string()]
+    );
+
+    // Granting again without an expiration makes it permanent
+    ExecuteMsg::UpdateRegistry {
+        action: RegistryAction::GrantAccess {
+            permissions: vec!["TEMPORARY_ACCESS".to_string()],
+            user: "operator".to_string(),
+            expiration: None,
+        },
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    assert!(has_permission(&chain, "TEMPORARY_ACCESS"));
+}
+
+#[test]
+fn test_admins_pagination_and_holders() {
+    let mut chain = App::default();
+
+    let admin = InstantiateMsg { super_admin: None }
+        .test_init(
+            Admin::default(),
+            &mut chain,
+            Addr::unchecked("admin"),
+            "admin_contract",
+            &[],
+        )
+        .unwrap();
+
+    let mut actions = vec![];
+    for user in ["one", "two", "three"] {
+        actions.push(RegistryAction::RegisterAdmin {
+            user: user.to_string(),
+        });
+    }
+    for user in ["one", "three"] {
+        actions.push(RegistryAction::GrantAccess {
+            permissions: vec!["SOME_TARGET".to_string()],
+            user: user.to_string(),
+            expiration: None,
+        });
+    }
+    ExecuteMsg::UpdateRegistryBulk { actions }
+        .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+        .unwrap();
+
+    let response: AdminsResponse = QueryMsg::GetAdmins {
+        page: Some(1),
+        page_size: Some(2),
+    }
+    .test_query(&admin, &chain)
+    .unwrap();
+    assert_eq!(response.admins, vec![Addr::unchecked("three")]);
+    assert_eq!(response.total, 3);
+
+    // Pages past the end are empty instead of overflowing
+    let response: AdminsResponse = QueryMsg::GetAdmins {
+        page: Some(u32::MAX),
+        page_size: Some(u32::MAX),
+    }
+    .test_query(&admin, &chain)
+    .unwrap();
+    assert!(response.admins.is_empty());
+    assert_eq!(response.total, 3);
+
+    let response: PermissionHoldersResponse = QueryMsg::GetPermissionHolders {
+        permission: "SOME_TARGET".to_string(),
+    }
+    .test_query(&admin, &chain)
+    .unwrap();
+    assert_eq!(response.holders, vec![
+        Addr::unchecked("one"),
+        Addr::unchecked("three")
+    ]);
+}
+
+#[test]
+fn test_audit_log() {
+    let mut chain = App::default();
+
+    let admin = InstantiateMsg { super_admin: None }
+        .test_init(
+            Admin::default(),
+            &mut chain,
+            Addr::unchecked("admin"),
+            "admin_contract",
+            &[],
+        )
+        .unwrap();
+
+    let actions = vec![
+        RegistryAction::RegisterAdmin {
+            user: "user".to_string(),
+        },
+        RegistryAction::GrantAccess {
+            permissions: vec!["SOME_TARGET".to_string()],
+            user: "user".to_string(),
+            expiration: None,
+        },
+    ];
+    ExecuteMsg::UpdateRegistryBulk {
+        actions: actions.clone(),
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    // Failed messages are not recorded
+    assert!(ExecuteMsg::ToggleStatus {
+        new_status: AdminAuthStatus::Maintenance,
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("user"), &[])
+    .is_err());
+
+    ExecuteMsg::ToggleStatus {
+        new_status: AdminAuthStatus::Maintenance,
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let response: AuditLogResponse = QueryMsg::GetAuditLog { start: 0, end: 10 }
+        .test_query(&admin, &chain)
+        .unwrap();
+    assert_eq!(response.total, 3);
+    assert_eq!(response.entries.len(), 3);
+    for (i, entry) in response.entries.iter().enumerate() {
+        assert_eq!(entry.id, i as u64);
+        assert_eq!(entry.sender, Addr::unchecked("admin"));
+        assert_eq!(entry.time, chain.block_info().time.seconds());
+    }
+    assert_eq!(response.entries[0].action, AuditAction::Registry {
+        action: actions[0].clone()
+    });
+    assert_eq!(response.entries[1].action, AuditAction::Registry {
+        action: actions[1].clone()
+    });
+    assert_eq!(response.entries[2].action, AuditAction::ToggleStatus {
+        new_status: AdminAuthStatus::Maintenance
+    });
+
+    let response: AuditLogResponse = QueryMsg::GetAuditLog { start: 2, end: 3 }
+        .test_query(&admin, &chain)
+        .unwrap();
+    assert_eq!(response.entries.len(), 1);
+    assert_eq!(response.entries[0].id, 2);
+}
+
+#[test]
+fn test_permission_maintenance() {
+    let mut chain = App::default();
+
+    let admin = InstantiateMsg { super_admin: None }
+        .test_init(
+            Admin::default(),
+            &mut chain,
+            Addr::unchecked("admin"),
+            "admin_contract",
+            &[],
+        )
+        .unwrap();
+
+    ExecuteMsg::UpdateRegistryBulk {
+        actions: vec![
+            RegistryAction::RegisterAdmin {
+                user: "user".to_string(),
+            },
+            RegistryAction::GrantAccess {
+                permissions: vec![
+                    "SHADE_TREASURY_ADMIN".to_string(),
+                    "SHADE_STAKING_ADMIN".to_string(),
+                ],
+                user: "user".to_string(),
+                expiration: None,
+            },
+        ],
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    // Only the super admin can pause
+    assert!(ExecuteMsg::TogglePermissionStatus {
+        permissions: vec!["SHADE_TREASURY_ADMIN".to_string()],
+        paused: true,
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("user"), &[])
+    .is_err());
+
+    // Duplicates are only paused once
+    ExecuteMsg::TogglePermissionStatus {
+        permissions: vec![
+            "SHADE_TREASURY_ADMIN".to_string(),
+            "SHADE_TREASURY_ADMIN".to_string(),
+        ],
+        paused: true,
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let response: PausedPermissionsResponse = QueryMsg::GetPausedPermissions {}
+        .test_query(&admin, &chain)
+        .unwrap();
+    assert_eq!(response.permissions, vec!["SHADE_TREASURY_ADMIN".to_string()]);
+
+    // Paused for everyone, including the super admin
+    for user in ["user", "admin"] {
+        assert!(QueryMsg::ValidateAdminPermission {
+            permission: "SHADE_TREASURY_ADMIN".to_string(),
+            user: user.to_string(),
+        }
+        .test_query::<ValidateAdminPermissionResponse>(&admin, &chain)
+        .is_err());
+    }
+
+    let res: ValidateAdminPermissionResponse = QueryMsg::ValidateAdminPermission {
+        permission: "SHADE_STAKING_ADMIN".to_string(),
+        user: "user".to_string(),
+    }
+    .test_query(&admin, &chain)
+    .unwrap();
+    assert!(res.has_permission);
+
+    ExecuteMsg::TogglePermissionStatus {
+        permissions: vec!["SHADE_TREASURY_ADMIN".to_string()],
+        paused: false,
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let response: PausedPermissionsResponse = QueryMsg::GetPausedPermissions {}
+        .test_query(&admin, &chain)
+        .unwrap();
+    assert!(response.permissions.is_empty());
+
+    let res: ValidateAdminPermissionResponse = QueryMsg::ValidateAdminPermission {
+        permission: "SHADE_TREASURY_ADMIN".to_string(),
+        user: "user".to_string(),
+    }
+    .test_query(&admin, &chain)
+    .unwrap();
+    assert!(res.has_permission);
+}
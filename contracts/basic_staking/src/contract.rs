@@ -1,5 +1,14 @@
 use shade_protocol::{
-    basic_staking::{Auth, AuthPermit, Config, ExecuteMsg, InstantiateMsg, QueryAnswer, QueryMsg},
+    basic_staking::{
+        Auth,
+        AuthPermit,
+        Config,
+        ExecuteMsg,
+        InstantiateMsg,
+        OperatorPermission,
+        QueryAnswer,
+        QueryMsg,
+    },
     c_std::{
         shd_entry_point, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response,
         StdError, StdResult, Uint128,
@@ -31,6 +40,8 @@ pub fn instantiate(
             },
             unbond_period: msg.unbond_period,
             max_user_pools: msg.max_user_pools,
+            lock_tiers: vec![],
+            instant_unbond: None,
         },
     )?;
 
@@ -65,6 +76,9 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
                 airdrop,
                 unbond_period,
                 max_user_pools,
+                lock_tiers,
+                instant_unbond,
+                disable_instant_unbond,
                 padding,
             } => execute::update_config(
                 deps,
@@ -75,6 +89,9 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
                 airdrop,
                 unbond_period,
                 max_user_pools,
+                lock_tiers,
+                instant_unbond,
+                disable_instant_unbond.unwrap_or(false),
             ),
             ExecuteMsg::RegisterRewards { token, padding } => {
                 let api = deps.api;
@@ -95,16 +112,24 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
                 msg,
                 ..
             } => execute::receive(deps, env, info, sender, from, amount, msg),
-            ExecuteMsg::Claim { padding } => execute::claim(deps, env, info),
+            ExecuteMsg::Claim { padding } => execute::claim(deps, env, info.sender),
             ExecuteMsg::Unbond {
                 amount,
                 compound,
+                instant,
                 padding,
-            } => execute::unbond(deps, env, info, amount, compound.unwrap_or(false)),
+            } => execute::unbond(
+                deps,
+                env,
+                info,
+                amount,
+                compound.unwrap_or(false),
+                instant.unwrap_or(false),
+            ),
             ExecuteMsg::Withdraw { ids, padding } => {
                 execute::withdraw(deps, env, info.clone(), ids)
             }
-            ExecuteMsg::Compound { padding } => execute::compound(deps, env, info),
+            ExecuteMsg::Compound { padding } => execute::compound(deps, env, info.sender),
             ExecuteMsg::EndRewardPool { id, force, padding } => {
                 execute::end_reward_pool(deps, env, info, id, force.unwrap_or(false))
             }
@@ -124,6 +149,52 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
                     compound.unwrap_or(false),
                 )
             }
+            ExecuteMsg::SetOperator {
+                operator,
+                permissions,
+                expiration,
+                padding,
+            } => {
+                let api = deps.api;
+                execute::set_operator(
+                    deps,
+                    env,
+                    info,
+                    api.addr_validate(&operator)?,
+                    permissions,
+                    expiration,
+                )
+            }
+            ExecuteMsg::RevokeOperator { operator, padding } => {
+                let api = deps.api;
+                execute::revoke_operator(deps, env, info, api.addr_validate(&operator)?)
+            }
+            ExecuteMsg::ClaimFor { user, padding } => {
+                let user = deps.api.addr_validate(&user)?;
+                execute::validate_operator(
+                    deps.storage,
+                    &env,
+                    user.clone(),
+                    info.sender,
+                    OperatorPermission::Claim,
+                )?;
+                execute::claim(deps, env, user)
+            }
+            ExecuteMsg::CompoundFor { user, padding } => {
+                let user = deps.api.addr_validate(&user)?;
+                execute::validate_operator(
+                    deps.storage,
+                    &env,
+                    user.clone(),
+                    info.sender,
+                    OperatorPermission::Compound,
+                )?;
+                execute::compound(deps, env, user)
+            }
+            ExecuteMsg::ExpireLocks { user, padding } => {
+                let user = deps.api.addr_validate(&user)?;
+                execute::expire_user_locks(deps, env, user)
+            }
         },
         RESPONSE_BLOCK_SIZE,
     )
@@ -156,8 +227,14 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::StakeToken {} => to_binary(&query::stake_token(deps)?),
         QueryMsg::StakingInfo {} => to_binary(&query::staking_info(deps)?),
         QueryMsg::TotalStaked {} => to_binary(&query::total_staked(deps)?),
-        QueryMsg::RewardTokens {} => to_binary(&query::reward_tokens(deps)?),
+        QueryMsg::TotalStakedAt { time } => to_binary(&query::total_staked_at(deps, env, time)?),
+        QueryMsg::RewardTokens { start_after, limit } => {
+            to_binary(&query::reward_tokens(deps, start_after, limit)?)
+        }
         QueryMsg::RewardPools {} => to_binary(&query::reward_pools(deps)?),
+        QueryMsg::AllRewardPools { start_after, limit } => {
+            to_binary(&query::all_reward_pools(deps, env, start_after, limit)?)
+        }
         QueryMsg::Balance {
             auth,
             unbonding_ids,
@@ -188,6 +265,15 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 authenticate(deps, auth, config.query_auth)?,
             )?)
         }
+        QueryMsg::StakedAt { auth, time } => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&query::user_staked_at(
+                deps,
+                env,
+                authenticate(deps, auth, config.query_auth)?,
+                time,
+            )?)
+        }
         QueryMsg::Rewards { auth } => {
             let config = CONFIG.load(deps.storage)?;
             to_binary(&query::user_rewards(
@@ -205,8 +291,46 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 ids.unwrap_or(USER_UNBONDING_IDS.load(deps.storage, user)?),
             )?)
         }
+        QueryMsg::Claimed {
+            auth,
+            start_after,
+            limit,
+        } => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&query::user_claimed(
+                deps,
+                authenticate(deps, auth, config.query_auth)?,
+                start_after,
+                limit,
+            )?)
+        }
         QueryMsg::TransferWhitelist {} => to_binary(&QueryAnswer::TransferWhitelist {
             whitelist: TRANSFER_WL.load(deps.storage)?,
         }),
+        QueryMsg::Operators { auth } => {
+            let config = CONFIG.load(deps.storage)?;
+            let user = authenticate(deps, auth, config.query_auth)?;
+            to_binary(&QueryAnswer::Operators {
+                operators: USER_OPERATORS
+                    .may_load(deps.storage, user)?
+                    .unwrap_or(vec![]),
+            })
+        }
+        QueryMsg::Locks { auth } => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&query::user_locks(
+                deps,
+                authenticate(deps, auth, config.query_auth)?,
+            )?)
+        }
+        QueryMsg::Weight { auth } => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&query::user_weight(
+                deps,
+                env,
+                authenticate(deps, auth, config.query_auth)?,
+            )?)
+        }
+        QueryMsg::TotalWeight {} => to_binary(&query::total_weight(deps)?),
     }
 }
@@ -1,9 +1,21 @@
 use shade_protocol::{
     admin::helpers::{admin_is_valid, validate_admin, AdminPermissions},
-    basic_staking::{Action, ExecuteAnswer, RewardPoolInternal, Unbonding},
+    basic_staking::{
+        Action,
+        Checkpoint,
+        ExecuteAnswer,
+        InstantUnbond,
+        Lock,
+        LockTier,
+        Operator,
+        OperatorPermission,
+        PenaltyDestination,
+        RewardPoolInternal,
+        Unbonding,
+    },
     c_std::{
         from_binary, to_binary, Addr, Binary, DepsMut, Env, MessageInfo, Response, StdError,
-        StdResult, Storage, Uint128,
+        Decimal, StdResult, Storage, Uint128,
     },
     contract_interfaces::airdrop::ExecuteMsg::CompleteTask,
     snip20::helpers::{register_receive, send_msg, set_viewing_key_msg},
@@ -26,6 +38,9 @@ pub fn update_config(
     airdrop: Option<RawContract>,
     unbond_period: Option<Uint128>,
     max_user_pools: Option<Uint128>,
+    lock_tiers: Option<Vec<LockTier>>,
+    instant_unbond: Option<InstantUnbond>,
+    disable_instant_unbond: bool,
 ) -> StdResult<Response> {
     let mut config = CONFIG.load(deps.storage)?;
 
@@ -56,6 +71,54 @@ pub fn update_config(
         config.max_user_pools = max_user_pools;
     }
 
+    if let Some(lock_tiers) = lock_tiers {
+        for tier in lock_tiers.iter() {
+            if tier.duration.is_zero() {
+                return Err(StdError::generic_err("Lock duration must be non-zero"));
+            }
+            if tier.multiplier < Decimal::one() {
+                return Err(StdError::generic_err("Lock multiplier must be at least 1"));
+            }
+            if lock_tiers
+                .iter()
+                .filter(|t| t.duration == tier.duration)
+                .count()
+                > 1
+            {
+                return Err(StdError::generic_err(format!(
+                    "Duplicate lock duration {}",
+                    tier.duration
+                )));
+            }
+        }
+        config.lock_tiers = lock_tiers;
+    }
+
+    if let Some(mut instant_unbond) = instant_unbond {
+        if instant_unbond.penalty > Decimal::one() {
+            return Err(StdError::generic_err("Penalty cannot exceed 100%"));
+        }
+        match &instant_unbond.destination {
+            PenaltyDestination::Stakers { duration } => {
+                if duration.is_zero() {
+                    return Err(StdError::generic_err(
+                        "Penalty reward duration must be non-zero",
+                    ));
+                }
+            }
+            PenaltyDestination::Treasury { address } => {
+                instant_unbond.destination = PenaltyDestination::Treasury {
+                    address: deps.api.addr_validate(address)?.to_string(),
+                };
+            }
+        }
+        config.instant_unbond = Some(instant_unbond);
+    }
+
+    if disable_instant_unbond {
+        config.instant_unbond = None;
+    }
+
     CONFIG.save(deps.storage, &config)?;
 
     Ok(
@@ -114,6 +177,7 @@ pub fn receive(
             Action::Stake {
                 compound,
                 airdrop_task,
+                lock,
             } => {
                 let stake_token = STAKE_TOKEN.load(deps.storage)?;
                 if info.sender != stake_token.address {
@@ -127,8 +191,7 @@ pub fn receive(
 
                 let total_staked = TOTAL_STAKED.load(deps.storage)?;
 
-                let mut reward_pools =
-                    update_rewards(env.clone(), &REWARD_POOLS.load(deps.storage)?, total_staked);
+                let mut reward_pools = REWARD_POOLS.load(deps.storage)?;
 
                 let mut response = Response::new();
 
@@ -137,17 +200,16 @@ pub fn receive(
                 let user_staked = USER_STAKED
                     .may_load(deps.storage, from.clone())?
                     .unwrap_or(Uint128::zero());
+                let weight = user_weight(deps.storage, from.clone())?;
 
-                if !user_staked.is_zero() {
+                // New stakers start earning from now
+                let claimed =
+                    settle_rewards(deps.storage, vec![from.clone()], &mut reward_pools, now)?
+                        .remove(0);
+
+                if !weight.is_zero() {
                     // Claim Rewards
-                    for reward_pool in reward_pools.iter_mut() {
-                        let reward_claimed = reward_pool_claim(
-                            deps.storage,
-                            from.clone(),
-                            user_staked,
-                            &reward_pool,
-                        )?;
-                        reward_pool.claimed += reward_claimed;
+                    for (reward_pool, reward_claimed) in reward_pools.iter().zip(claimed) {
                         if compound && reward_pool.token == stake_token {
                             // Compound stake_token rewards
                             compound_amount += reward_claimed;
@@ -168,15 +230,6 @@ pub fn receive(
                                 );
                         }
                     }
-                } else {
-                    for reward_pool in reward_pools.iter() {
-                        // make sure user rewards start now
-                        USER_REWARD_PER_TOKEN_PAID.save(
-                            deps.storage,
-                            user_pool_key(from.clone(), reward_pool.id),
-                            &reward_pool.reward_per_token,
-                        )?;
-                    }
                 }
 
                 // Send airdrop message
@@ -199,17 +252,35 @@ pub fn receive(
                     response = response.add_attribute("compounded", compound_amount);
                 }
 
-                USER_STAKED.save(
+                let locked = match lock {
+                    Some(duration) => {
+                        let config = CONFIG.load(deps.storage)?;
+                        match config.lock_tiers.iter().find(|t| t.duration == duration) {
+                            Some(tier) => Some(add_lock(deps.storage, from.clone(), amount, tier, now)?),
+                            None => {
+                                return Err(StdError::generic_err(format!(
+                                    "Invalid lock duration {}",
+                                    duration
+                                )));
+                            }
+                        }
+                    }
+                    None => None,
+                };
+
+                save_user_staked(
                     deps.storage,
                     from.clone(),
-                    &(user_staked + amount + compound_amount),
+                    user_staked + amount + compound_amount,
+                    now,
                 )?;
-                TOTAL_STAKED.save(deps.storage, &(total_staked + amount + compound_amount))?;
+                save_total_staked(deps.storage, total_staked + amount + compound_amount, now)?;
 
                 REWARD_POOLS.save(deps.storage, &reward_pools.clone())?;
 
                 Ok(response.set_data(to_binary(&ExecuteAnswer::Stake {
                     staked: user_staked + amount,
+                    locked,
                     status: ResponseStatus::Success,
                 })?))
             }
@@ -293,6 +364,213 @@ pub fn receive(
     }
 }
 
+/* Reward weight of all stakers
+ * staked amounts + lock boosts
+ */
+pub fn total_weight(storage: &dyn Storage) -> StdResult<Uint128> {
+    Ok(TOTAL_STAKED.load(storage)?
+        + TOTAL_BOOST
+            .may_load(storage)?
+            .unwrap_or(Uint128::zero()))
+}
+
+pub fn user_weight(storage: &dyn Storage, user: Addr) -> StdResult<Uint128> {
+    Ok(USER_STAKED
+        .may_load(storage, user.clone())?
+        .unwrap_or(Uint128::zero())
+        + USER_BOOST.may_load(storage, user)?.unwrap_or(Uint128::zero()))
+}
+
+pub fn lock_boost(lock: &Lock) -> Uint128 {
+    lock.amount * lock.multiplier - lock.amount
+}
+
+pub fn locked_amount(storage: &dyn Storage, user: Addr) -> StdResult<Uint128> {
+    Ok(USER_LOCKS
+        .may_load(storage, user)?
+        .unwrap_or(vec![])
+        .iter()
+        .map(|lock| lock.amount)
+        .sum())
+}
+
+pub fn add_lock(
+    storage: &mut dyn Storage,
+    user: Addr,
+    amount: Uint128,
+    tier: &LockTier,
+    now: Uint128,
+) -> StdResult<Lock> {
+    let mut locks = USER_LOCKS
+        .may_load(storage, user.clone())?
+        .unwrap_or(vec![]);
+
+    let lock = Lock {
+        id: locks
+            .iter()
+            .map(|lock| lock.id)
+            .max()
+            .unwrap_or(Uint128::zero())
+            + Uint128::one(),
+        amount,
+        multiplier: tier.multiplier,
+        start: now,
+        end: now + tier.duration,
+    };
+    let boost = lock_boost(&lock);
+
+    locks.push(lock.clone());
+    USER_LOCKS.save(storage, user.clone(), &locks)?;
+
+    let user_boost = USER_BOOST
+        .may_load(storage, user.clone())?
+        .unwrap_or(Uint128::zero());
+    USER_BOOST.save(storage, user, &(user_boost + boost))?;
+    let total_boost = TOTAL_BOOST.may_load(storage)?.unwrap_or(Uint128::zero());
+    TOTAL_BOOST.save(storage, &(total_boost + boost))?;
+
+    Ok(lock)
+}
+
+/* Removes locks ended by 'now' & their boost, returns the boost removed
+ * Rewards must be claimed with the previous weight up to 'now' before calling,
+ * see 'settle_rewards'
+ */
+pub fn expire_locks(storage: &mut dyn Storage, user: Addr, now: Uint128) -> StdResult<Uint128> {
+    let locks = USER_LOCKS
+        .may_load(storage, user.clone())?
+        .unwrap_or(vec![]);
+
+    let (expired, active): (Vec<Lock>, Vec<Lock>) =
+        locks.into_iter().partition(|lock| lock.end <= now);
+
+    if expired.is_empty() {
+        return Ok(Uint128::zero());
+    }
+
+    let expired_boost: Uint128 = expired.iter().map(lock_boost).sum();
+
+    USER_LOCKS.save(storage, user.clone(), &active)?;
+
+    let user_boost = USER_BOOST
+        .may_load(storage, user.clone())?
+        .unwrap_or(Uint128::zero());
+    USER_BOOST.save(storage, user, &(user_boost - expired_boost))?;
+    let total_boost = TOTAL_BOOST.may_load(storage)?.unwrap_or(Uint128::zero());
+    TOTAL_BOOST.save(storage, &(total_boost - expired_boost))?;
+
+    Ok(expired_boost)
+}
+
+/* Saves USER_STAKED & checkpoints it for historical queries
+ * Multiple updates at the same time overwrite the latest checkpoint
+ * Stake from before checkpointing is seeded as the balance since time 0
+ */
+pub fn save_user_staked(
+    storage: &mut dyn Storage,
+    user: Addr,
+    amount: Uint128,
+    now: Uint128,
+) -> StdResult<()> {
+    let mut count = USER_CHECKPOINT_COUNT
+        .may_load(storage, user.clone())?
+        .unwrap_or(0);
+
+    if count == 0 {
+        let prior = USER_STAKED
+            .may_load(storage, user.clone())?
+            .unwrap_or(Uint128::zero());
+        if !prior.is_zero() {
+            USER_CHECKPOINTS.save(storage, user_checkpoint_key(user.clone(), 0), &Checkpoint {
+                time: Uint128::zero(),
+                amount: prior,
+            })?;
+            count = 1;
+            USER_CHECKPOINT_COUNT.save(storage, user.clone(), &count)?;
+        }
+    }
+
+    USER_STAKED.save(storage, user.clone(), &amount)?;
+
+    let index = match count.checked_sub(1) {
+        Some(last)
+            if USER_CHECKPOINTS
+                .load(storage, user_checkpoint_key(user.clone(), last))?
+                .time
+                == now =>
+        {
+            last
+        }
+        _ => {
+            USER_CHECKPOINT_COUNT.save(storage, user.clone(), &(count + 1))?;
+            count
+        }
+    };
+
+    USER_CHECKPOINTS.save(
+        storage,
+        user_checkpoint_key(user, index),
+        &Checkpoint { time: now, amount },
+    )
+}
+
+/* Saves TOTAL_STAKED & checkpoints it for historical queries
+ * Stake from before checkpointing is seeded as the total since time 0
+ */
+pub fn save_total_staked(storage: &mut dyn Storage, amount: Uint128, now: Uint128) -> StdResult<()> {
+    let mut count = TOTAL_CHECKPOINT_COUNT.may_load(storage)?.unwrap_or(0);
+
+    if count == 0 {
+        let prior = TOTAL_STAKED.load(storage)?;
+        if !prior.is_zero() {
+            TOTAL_CHECKPOINTS.save(storage, 0, &Checkpoint {
+                time: Uint128::zero(),
+                amount: prior,
+            })?;
+            count = 1;
+            TOTAL_CHECKPOINT_COUNT.save(storage, &count)?;
+        }
+    }
+
+    TOTAL_STAKED.save(storage, &amount)?;
+
+    let index = match count.checked_sub(1) {
+        Some(last) if TOTAL_CHECKPOINTS.load(storage, last)?.time == now => last,
+        _ => {
+            TOTAL_CHECKPOINT_COUNT.save(storage, &(count + 1))?;
+            count
+        }
+    };
+
+    TOTAL_CHECKPOINTS.save(storage, index, &Checkpoint { time: now, amount })
+}
+
+/* Binary search for the last checkpoint at or before 'time'
+ * Returns zero if there is none
+ */
+pub fn checkpoint_lookup<F>(count: u64, time: Uint128, load: F) -> StdResult<Uint128>
+where
+    F: Fn(u64) -> StdResult<Checkpoint>,
+{
+    let mut low = 0;
+    let mut high = count;
+
+    while low < high {
+        let mid = (low + high) / 2;
+        if load(mid)?.time <= time {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    if low == 0 {
+        return Ok(Uint128::zero());
+    }
+
+    Ok(load(low - 1)?.amount)
+}
+
 pub fn reward_per_token(total_staked: Uint128, now: u64, pool: &RewardPoolInternal) -> Uint128 {
     if total_staked.is_zero() {
         return Uint128::zero();
@@ -341,6 +619,60 @@ pub fn update_rewards(
         .collect()
 }
 
+/* Claims rewards for each user, advancing reward_pools to now.
+ * Each expired lock is settled at its end: rewards up to then are earned
+ * with the boost, after which it's removed from the user's & total weight.
+ * Returns the amount claimed from each pool, per user
+ */
+pub fn settle_rewards(
+    storage: &mut dyn Storage,
+    users: Vec<Addr>,
+    reward_pools: &mut Vec<RewardPoolInternal>,
+    now: Uint128,
+) -> StdResult<Vec<Vec<Uint128>>> {
+    let mut ends = vec![];
+    for user in users.iter() {
+        for lock in USER_LOCKS
+            .may_load(storage, user.clone())?
+            .unwrap_or(vec![])
+        {
+            if lock.end <= now {
+                ends.push(lock.end);
+            }
+        }
+    }
+    ends.sort();
+    ends.dedup();
+    ends.push(now);
+
+    let mut claimed = vec![vec![Uint128::zero(); reward_pools.len()]; users.len()];
+
+    for end in ends {
+        let total = total_weight(storage)?;
+        for reward_pool in reward_pools.iter_mut() {
+            // pools already updated past 'end' can't be rewound
+            if reward_pool.last_update < end {
+                *reward_pool = updated_reward_pool(reward_pool, total, end.u128() as u64);
+            }
+        }
+
+        for (user, user_claimed) in users.iter().zip(claimed.iter_mut()) {
+            let weight = user_weight(storage, user.clone())?;
+            for (reward_pool, pool_claimed) in
+                reward_pools.iter_mut().zip(user_claimed.iter_mut())
+            {
+                let reward_claimed =
+                    reward_pool_claim(storage, user.clone(), weight, reward_pool)?;
+                reward_pool.claimed += reward_claimed;
+                *pool_claimed += reward_claimed;
+            }
+            expire_locks(storage, user.clone(), end)?;
+        }
+    }
+
+    Ok(claimed)
+}
+
 /* returns the earned rewards
  * Reward must be sent buy calling code
  */
@@ -366,11 +698,19 @@ pub fn reward_pool_claim(
         &reward_pool.reward_per_token,
     )?;
 
+    if !user_reward.is_zero() {
+        let key = (user, reward_pool.id.u128());
+        let user_claimed = USER_POOL_CLAIMED
+            .may_load(storage, key.clone())?
+            .unwrap_or(Uint128::zero());
+        USER_POOL_CLAIMED.save(storage, key, &(user_claimed + user_reward))?;
+    }
+
     Ok(user_reward)
 }
 
-pub fn claim(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
-    let user_staked = USER_STAKED.load(deps.storage, info.sender.clone())?;
+pub fn claim(deps: DepsMut, env: Env, user: Addr) -> StdResult<Response> {
+    let user_staked = USER_STAKED.load(deps.storage, user.clone())?;
 
     if user_staked.is_zero() {
         return Ok(Response::new().set_data(to_binary(&ExecuteAnswer::Claim {
@@ -378,24 +718,18 @@ pub fn claim(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response>
         })?));
     }
 
-    let total_staked = TOTAL_STAKED.load(deps.storage)?;
-
-    let now = env.block.time.seconds();
+    let now = Uint128::new(env.block.time.seconds() as u128);
 
-    let mut reward_pools =
-        update_rewards(env.clone(), &REWARD_POOLS.load(deps.storage)?, total_staked);
+    let mut reward_pools = REWARD_POOLS.load(deps.storage)?;
+    let claimed =
+        settle_rewards(deps.storage, vec![user.clone()], &mut reward_pools, now)?.remove(0);
 
     let mut response = Response::new();
 
-    for reward_pool in reward_pools.iter_mut() {
-        let reward_claimed =
-            reward_pool_claim(deps.storage, info.sender.clone(), user_staked, &reward_pool)?;
-
-        reward_pool.claimed += reward_claimed;
-
+    for (reward_pool, reward_claimed) in reward_pools.iter().zip(claimed) {
         response = response
             .add_message(send_msg(
-                info.sender.clone(),
+                user.clone(),
                 reward_claimed,
                 None,
                 None,
@@ -413,12 +747,38 @@ pub fn claim(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response>
     })?))
 }
 
+/* Permissionless, so a boost can't outlive its lock
+ * just because its owner doesn't interact
+ */
+pub fn expire_user_locks(deps: DepsMut, env: Env, user: Addr) -> StdResult<Response> {
+    let now = Uint128::new(env.block.time.seconds() as u128);
+
+    let expired: Vec<Lock> = USER_LOCKS
+        .may_load(deps.storage, user.clone())?
+        .unwrap_or(vec![])
+        .into_iter()
+        .filter(|lock| lock.end <= now)
+        .collect();
+
+    if expired.is_empty() {
+        return Err(StdError::generic_err(format!("No expired locks for {}", user)));
+    }
+
+    let response = claim(deps, env, user)?;
+
+    Ok(response.set_data(to_binary(&ExecuteAnswer::ExpireLocks {
+        expired,
+        status: ResponseStatus::Success,
+    })?))
+}
+
 pub fn unbond(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     amount: Uint128,
     compound: bool,
+    instant: bool,
 ) -> StdResult<Response> {
     let config = CONFIG.load(deps.storage)?;
 
@@ -426,6 +786,14 @@ pub fn unbond(
         return Err(StdError::generic_err("Must unbond non-zero amount"));
     }
 
+    let instant_unbond = match (instant, config.instant_unbond.clone()) {
+        (true, Some(instant_unbond)) => Some(instant_unbond),
+        (true, None) => {
+            return Err(StdError::generic_err("Instant unbond is not enabled"));
+        }
+        (false, _) => None,
+    };
+
     if let Some(mut user_staked) = USER_STAKED.may_load(deps.storage, info.sender.clone())? {
         // if not compounding, check staked >= unbond amount
         if !compound && user_staked < amount {
@@ -439,8 +807,14 @@ pub fn unbond(
 
         let mut total_staked = TOTAL_STAKED.load(deps.storage)?;
 
-        let mut reward_pools =
-            update_rewards(env.clone(), &REWARD_POOLS.load(deps.storage)?, total_staked);
+        let mut reward_pools = REWARD_POOLS.load(deps.storage)?;
+        let claimed = settle_rewards(
+            deps.storage,
+            vec![info.sender.clone()],
+            &mut reward_pools,
+            Uint128::new(now as u128),
+        )?
+        .remove(0);
 
         let stake_token = STAKE_TOKEN.load(deps.storage)?;
         let mut compound_amount = Uint128::zero();
@@ -448,11 +822,7 @@ pub fn unbond(
         let mut response = Response::new();
 
         // Claim/Compound rewards
-        for reward_pool in reward_pools.iter_mut() {
-            let reward_claimed =
-                reward_pool_claim(deps.storage, info.sender.clone(), user_staked, &reward_pool)?;
-            reward_pool.claimed += reward_claimed;
-
+        for (reward_pool, reward_claimed) in reward_pools.iter().zip(claimed) {
             if compound && reward_pool.token == stake_token {
                 // Compound stake_token rewards
                 compound_amount += reward_claimed;
@@ -479,6 +849,16 @@ pub fn unbond(
                 user_staked + compound_amount,
             )));
         }
+
+        // Locked stake can't be unbonded until the lock expires
+        let locked = locked_amount(deps.storage, info.sender.clone())?;
+        if user_staked + compound_amount - locked < amount {
+            return Err(StdError::generic_err(format!(
+                "Cannot unbond {}, {} is locked",
+                amount, locked,
+            )));
+        }
+
         if compound_amount > Uint128::zero() {
             response = response.add_attribute("compounded", compound_amount);
         }
@@ -486,8 +866,61 @@ pub fn unbond(
         user_staked = (user_staked + compound_amount) - amount;
         total_staked = (total_staked + compound_amount) - amount;
 
-        TOTAL_STAKED.save(deps.storage, &total_staked)?;
-        USER_STAKED.save(deps.storage, info.sender.clone(), &user_staked)?;
+        save_total_staked(deps.storage, total_staked, Uint128::new(now as u128))?;
+        save_user_staked(
+            deps.storage,
+            info.sender.clone(),
+            user_staked,
+            Uint128::new(now as u128),
+        )?;
+
+        // Skip the unbond period, withdrawing immediately minus the penalty
+        if let Some(instant_unbond) = instant_unbond {
+            let penalty = amount * instant_unbond.penalty;
+
+            if !penalty.is_zero() {
+                match instant_unbond.destination {
+                    PenaltyDestination::Stakers { duration } => add_penalty_rewards(
+                        deps.storage,
+                        &mut reward_pools,
+                        penalty,
+                        duration,
+                        Uint128::new(now as u128),
+                        &stake_token,
+                        env.contract.address.clone(),
+                    )?,
+                    PenaltyDestination::Treasury { address } => {
+                        response = response.add_message(send_msg(
+                            deps.api.addr_validate(&address)?,
+                            penalty,
+                            None,
+                            None,
+                            None,
+                            &stake_token,
+                        )?);
+                    }
+                }
+            }
+            REWARD_POOLS.save(deps.storage, &reward_pools)?;
+
+            return Ok(response
+                .add_message(send_msg(
+                    info.sender,
+                    amount - penalty,
+                    None,
+                    None,
+                    None,
+                    &stake_token,
+                )?)
+                .add_attribute("penalty", penalty)
+                .set_data(to_binary(&ExecuteAnswer::Unbond {
+                    id: Uint128::zero(),
+                    unbonded: amount,
+                    penalty,
+                    status: ResponseStatus::Success,
+                })?));
+        }
+
         REWARD_POOLS.save(deps.storage, &reward_pools)?;
 
         let mut user_unbonding_ids = USER_UNBONDING_IDS
@@ -512,6 +945,7 @@ pub fn unbond(
         Ok(response.set_data(to_binary(&ExecuteAnswer::Unbond {
             id: next_id,
             unbonded: amount,
+            penalty: Uint128::zero(),
             status: ResponseStatus::Success,
         })?))
     } else {
@@ -519,6 +953,74 @@ pub fn unbond(
     }
 }
 
+/* Adds an instant unbond penalty to the penalty pool, emitting what it has left
+ * plus the penalty over 'duration' from now. Carried forward while nobody is
+ * staked or the amount is too small to emit
+ */
+fn add_penalty_rewards(
+    storage: &mut dyn Storage,
+    reward_pools: &mut Vec<RewardPoolInternal>,
+    penalty: Uint128,
+    duration: Uint128,
+    now: Uint128,
+    stake_token: &Contract,
+    creator: Addr,
+) -> StdResult<()> {
+    let amount = penalty + PENALTY_CARRY.may_load(storage)?.unwrap_or(Uint128::zero());
+
+    let pool_i = match PENALTY_POOL_ID.may_load(storage)? {
+        Some(id) => reward_pools.iter().position(|p| p.id == id),
+        None => None,
+    };
+
+    // Rewards the penalty pool has yet to emit
+    let remaining = match pool_i {
+        Some(i) if reward_pools[i].end > now => {
+            reward_pools[i].rate * (reward_pools[i].end - now) / Uint128::new(10u128.pow(18))
+        }
+        _ => Uint128::zero(),
+    };
+    let rate = (remaining + amount) * Uint128::new(10u128.pow(18)) / duration;
+
+    if total_weight(storage)?.is_zero() || rate.is_zero() {
+        PENALTY_CARRY.save(storage, &amount)?;
+        return Ok(());
+    }
+    PENALTY_CARRY.remove(storage);
+
+    match pool_i {
+        Some(i) => {
+            // Pools are settled up to now, so the new rate only applies from here
+            let pool = &mut reward_pools[i];
+            pool.amount += amount;
+            pool.end = now + duration;
+            pool.rate = rate;
+            pool.last_update = now;
+        }
+        None => {
+            let id = MAX_POOL_ID.load(storage)? + Uint128::one();
+            MAX_POOL_ID.save(storage, &id)?;
+            PENALTY_POOL_ID.save(storage, &id)?;
+
+            reward_pools.push(RewardPoolInternal {
+                id,
+                amount,
+                start: now,
+                end: now + duration,
+                token: stake_token.clone(),
+                rate,
+                reward_per_token: Uint128::zero(),
+                claimed: Uint128::zero(),
+                last_update: now,
+                creator,
+                official: true,
+            });
+        }
+    }
+
+    Ok(())
+}
+
 pub fn withdraw(
     deps: DepsMut,
     env: Env,
@@ -598,29 +1100,28 @@ pub fn withdraw(
         })?))
 }
 
-pub fn compound(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+pub fn compound(deps: DepsMut, env: Env, user: Addr) -> StdResult<Response> {
     let mut response = Response::new();
 
     let user_staked = USER_STAKED
-        .may_load(deps.storage, info.sender.clone())?
+        .may_load(deps.storage, user.clone())?
         .unwrap_or(Uint128::zero());
 
     if user_staked.is_zero() {
         return Err(StdError::generic_err("User has no stake"));
     }
 
+    let now = Uint128::new(env.block.time.seconds() as u128);
+
     let total_staked = TOTAL_STAKED.load(deps.storage)?;
-    let mut reward_pools =
-        update_rewards(env.clone(), &REWARD_POOLS.load(deps.storage)?, total_staked);
+    let mut reward_pools = REWARD_POOLS.load(deps.storage)?;
+    let claimed =
+        settle_rewards(deps.storage, vec![user.clone()], &mut reward_pools, now)?.remove(0);
     let stake_token = STAKE_TOKEN.load(deps.storage)?;
 
     let mut compound_amount = Uint128::zero();
 
-    for reward_pool in reward_pools.iter_mut() {
-        let reward_claimed =
-            reward_pool_claim(deps.storage, info.sender.clone(), user_staked, &reward_pool)?;
-        reward_pool.claimed += reward_claimed;
-
+    for (reward_pool, reward_claimed) in reward_pools.iter().zip(claimed) {
         if reward_pool.token == stake_token {
             // Compound stake_token rewards
             compound_amount += reward_claimed;
@@ -628,7 +1129,7 @@ pub fn compound(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Respons
             // Claim non-stake_token rewards
             response = response
                 .add_message(send_msg(
-                    info.sender.clone(),
+                    user.clone(),
                     reward_claimed,
                     None,
                     None,
@@ -644,12 +1145,8 @@ pub fn compound(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Respons
         response = response.add_attribute("compounded", compound_amount);
     }
 
-    USER_STAKED.save(
-        deps.storage,
-        info.sender.clone(),
-        &(user_staked + compound_amount),
-    )?;
-    TOTAL_STAKED.save(deps.storage, &(total_staked + compound_amount))?;
+    save_user_staked(deps.storage, user.clone(), user_staked + compound_amount, now)?;
+    save_total_staked(deps.storage, total_staked + compound_amount, now)?;
 
     Ok(response.set_data(to_binary(&ExecuteAnswer::Compound {
         compounded: compound_amount,
@@ -673,9 +1170,11 @@ pub fn end_reward_pool(
         &config.admin_auth,
     )?;
 
-    let total_staked = TOTAL_STAKED.load(deps.storage)?;
-    let mut reward_pools =
-        update_rewards(env.clone(), &REWARD_POOLS.load(deps.storage)?, total_staked);
+    let mut reward_pools = update_rewards(
+        env.clone(),
+        &REWARD_POOLS.load(deps.storage)?,
+        total_weight(deps.storage)?,
+    );
 
     // Amount of rewards pulled from contract
     let mut extract_amount = Uint128::zero();
@@ -725,6 +1224,16 @@ pub fn end_reward_pool(
 
     REWARD_POOLS.save(deps.storage, &reward_pools)?;
 
+    let extracted = REWARD_POOL_EXTRACTED
+        .may_load(deps.storage, id.u128())?
+        .unwrap_or(Uint128::zero());
+    REWARD_POOL_EXTRACTED.save(deps.storage, id.u128(), &(extracted + extract_amount))?;
+
+    // Keep deleted pools for historical queries
+    if deleted {
+        ENDED_REWARD_POOLS.save(deps.storage, id.u128(), &reward_pool)?;
+    }
+
     Ok(Response::new()
         .add_message(send_msg(
             info.sender,
@@ -825,11 +1334,9 @@ pub fn transfer_stake(
         )));
     }
 
-    // Claim/Compound for sending user
-    let total_staked = TOTAL_STAKED.load(deps.storage)?;
+    let mut reward_pools = REWARD_POOLS.load(deps.storage)?;
 
-    let mut reward_pools =
-        update_rewards(env.clone(), &REWARD_POOLS.load(deps.storage)?, total_staked);
+    let now = Uint128::new(env.block.time.seconds() as u128);
 
     let stake_token = STAKE_TOKEN.load(deps.storage)?;
 
@@ -843,20 +1350,20 @@ pub fn transfer_stake(
         return Err(StdError::generic_err("Cannot transfer with 0 staked"));
     }
 
+    // Settled together so both users' expired locks end in order
+    let mut claimed = settle_rewards(
+        deps.storage,
+        vec![info.sender.clone(), recipient.clone()],
+        &mut reward_pools,
+        now,
+    )?;
+    let recipient_claimed = claimed.remove(1);
+    let sender_claimed = claimed.remove(0);
+
     let mut sender_compound_amount = Uint128::zero();
 
     // Claim/Compound rewards for Sender
-    for reward_pool in reward_pools.iter_mut() {
-        println!("reward pool claim sender");
-        let reward_claimed = reward_pool_claim(
-            deps.storage,
-            info.sender.clone(),
-            sender_staked,
-            &reward_pool,
-        )?;
-        println!("POST reward pool claim sender");
-        reward_pool.claimed += reward_claimed;
-
+    for (reward_pool, reward_claimed) in reward_pools.iter().zip(sender_claimed) {
         if compound && reward_pool.token == stake_token {
             // Compound stake_token rewards
             sender_compound_amount += reward_claimed;
@@ -883,6 +1390,15 @@ pub fn transfer_stake(
         )));
     }
 
+    // Locked stake can't be transferred until the lock expires
+    let sender_locked = locked_amount(deps.storage, info.sender.clone())?;
+    if sender_staked + sender_compound_amount - sender_locked < amount {
+        return Err(StdError::generic_err(format!(
+            "Cannot transfer {}, {} is locked",
+            amount, sender_locked
+        )));
+    }
+
     println!("sender compound amount {}", sender_compound_amount);
 
     if sender_compound_amount > Uint128::zero() {
@@ -890,10 +1406,11 @@ pub fn transfer_stake(
     }
 
     // Adjust sender staked
-    USER_STAKED.save(
+    save_user_staked(
         deps.storage,
         info.sender,
-        &(sender_staked + sender_compound_amount - amount),
+        sender_staked + sender_compound_amount - amount,
+        now,
     )?;
 
     // Claim for receiving user
@@ -902,16 +1419,7 @@ pub fn transfer_stake(
         .unwrap_or(Uint128::zero());
 
     // Claim rewards for Receiver (no compound)
-    for reward_pool in reward_pools.iter_mut() {
-        println!("reward pool claim recipient");
-        let reward_claimed = reward_pool_claim(
-            deps.storage,
-            recipient.clone(),
-            recipient_staked,
-            &reward_pool,
-        )?;
-        reward_pool.claimed += reward_claimed;
-
+    for (reward_pool, reward_claimed) in reward_pools.iter().zip(recipient_claimed) {
         // Claim if not compound or not stake token rewards
         println!(
             "SENDING RECIPIETN REWARD {} {}",
@@ -929,10 +1437,108 @@ pub fn transfer_stake(
     }
 
     // Adjust recipient staked
-    USER_STAKED.save(deps.storage, recipient, &(recipient_staked + amount))?;
+    save_user_staked(
+        deps.storage,
+        recipient.clone(),
+        recipient_staked + amount,
+        now,
+    )?;
+
+    REWARD_POOLS.save(deps.storage, &reward_pools)?;
 
     Ok(response.set_data(to_binary(&ExecuteAnswer::TransferStake {
         transferred: amount,
         status: ResponseStatus::Success,
     })?))
 }
+
+pub fn set_operator(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    operator: Addr,
+    permissions: Vec<OperatorPermission>,
+    expiration: Option<Uint128>,
+) -> StdResult<Response> {
+    if operator == info.sender {
+        return Err(StdError::generic_err("Cannot set self as operator"));
+    }
+
+    let mut operators = USER_OPERATORS
+        .may_load(deps.storage, info.sender.clone())?
+        .unwrap_or(vec![]);
+
+    operators.retain(|o| o.address != operator);
+    operators.push(Operator {
+        address: operator,
+        permissions,
+        expiration,
+    });
+
+    USER_OPERATORS.save(deps.storage, info.sender, &operators)?;
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::SetOperator {
+            status: ResponseStatus::Success,
+        })?),
+    )
+}
+
+pub fn revoke_operator(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    operator: Addr,
+) -> StdResult<Response> {
+    let mut operators = USER_OPERATORS
+        .may_load(deps.storage, info.sender.clone())?
+        .unwrap_or(vec![]);
+
+    match operators.iter().position(|o| o.address == operator) {
+        Some(i) => {
+            operators.remove(i);
+        }
+        None => {
+            return Err(StdError::generic_err("Operator not found"));
+        }
+    }
+
+    USER_OPERATORS.save(deps.storage, info.sender, &operators)?;
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::RevokeOperator {
+            status: ResponseStatus::Success,
+        })?),
+    )
+}
+
+/* Errors unless 'operator' is authorized to perform 'permission' for 'user'
+ */
+pub fn validate_operator(
+    storage: &dyn Storage,
+    env: &Env,
+    user: Addr,
+    operator: Addr,
+    permission: OperatorPermission,
+) -> StdResult<()> {
+    let now = Uint128::new(env.block.time.seconds() as u128);
+
+    let authorized = USER_OPERATORS
+        .may_load(storage, user.clone())?
+        .unwrap_or(vec![])
+        .into_iter()
+        .any(|o| {
+            o.address == operator
+                && o.permissions.contains(&permission)
+                && o.expiration.map_or(true, |expiration| now < expiration)
+        });
+
+    if !authorized {
+        return Err(StdError::generic_err(format!(
+            "{} is not an operator for {}",
+            operator, user
+        )));
+    }
+
+    Ok(())
+}
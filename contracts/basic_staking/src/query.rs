@@ -1,13 +1,31 @@
 use shade_protocol::{
-    basic_staking::{QueryAnswer, Reward, RewardPool, RewardPoolInternal, StakingInfo},
-    c_std::{Addr, Deps, Env, StdError, StdResult, Uint128},
+    basic_staking::{
+        Lock,
+        PoolClaimed,
+        QueryAnswer,
+        Reward,
+        RewardPool,
+        RewardPoolInternal,
+        RewardPoolRecord,
+        StakingInfo,
+    },
+    c_std::{Addr, Deps, Env, Order, StdError, StdResult, Storage, Uint128},
+    secret_storage_plus::Bound,
+    utils::asset::Contract,
 };
 
 use crate::{
-    execute::{reward_per_token, rewards_earned},
+    execute::{self, checkpoint_lookup, lock_boost, rewards_earned, updated_reward_pool},
     storage::*,
 };
 
+pub const DEFAULT_PAGE_LIMIT: u32 = 10;
+pub const MAX_PAGE_LIMIT: u32 = 30;
+
+fn page_limit(limit: Option<u32>) -> usize {
+    limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize
+}
+
 pub fn config(deps: Deps) -> StdResult<QueryAnswer> {
     Ok(QueryAnswer::Config {
         config: CONFIG.load(deps.storage)?,
@@ -60,12 +78,52 @@ pub fn total_staked(deps: Deps) -> StdResult<QueryAnswer> {
     })
 }
 
-pub fn reward_tokens(deps: Deps) -> StdResult<QueryAnswer> {
+/* Only past times can be queried, stake can still change within the current block */
+pub fn total_staked_at(deps: Deps, env: Env, time: Uint128) -> StdResult<QueryAnswer> {
+    if time >= Uint128::new(env.block.time.seconds() as u128) {
+        return Err(StdError::generic_err("Can only query past stake"));
+    }
+
+    let count = TOTAL_CHECKPOINT_COUNT.may_load(deps.storage)?.unwrap_or(0);
+
+    Ok(QueryAnswer::TotalStakedAt {
+        amount: match count {
+            // Unchanged since before checkpointing
+            0 => TOTAL_STAKED.load(deps.storage)?,
+            _ => checkpoint_lookup(count, time, |i| TOTAL_CHECKPOINTS.load(deps.storage, i))?,
+        },
+    })
+}
+
+pub fn reward_tokens(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<QueryAnswer> {
+    let tokens = REWARD_TOKENS
+        .load(deps.storage)?
+        .into_iter()
+        .map(|contract| contract.address)
+        .collect::<Vec<Addr>>();
+
+    let start = match start_after {
+        Some(start_after) => match tokens.iter().position(|t| *t == start_after) {
+            Some(i) => i + 1,
+            None => {
+                return Err(StdError::generic_err(format!(
+                    "Unknown reward token {}",
+                    start_after
+                )));
+            }
+        },
+        None => 0,
+    };
+
     Ok(QueryAnswer::RewardTokens {
-        tokens: REWARD_TOKENS
-            .load(deps.storage)?
-            .iter()
-            .map(|contract| contract.address.clone())
+        tokens: tokens
+            .into_iter()
+            .skip(start)
+            .take(page_limit(limit))
             .collect(),
     })
 }
@@ -99,6 +157,130 @@ pub fn reward_pools(deps: Deps) -> StdResult<QueryAnswer> {
     })
 }
 
+fn reward_pool_record(
+    storage: &dyn Storage,
+    pool: RewardPoolInternal,
+    ended: bool,
+) -> StdResult<RewardPoolRecord> {
+    Ok(RewardPoolRecord {
+        extracted: REWARD_POOL_EXTRACTED
+            .may_load(storage, pool.id.u128())?
+            .unwrap_or(Uint128::zero()),
+        id: pool.id,
+        amount: pool.amount,
+        start: pool.start,
+        end: pool.end,
+        token: pool.token,
+        rate: pool.rate,
+        official: pool.official,
+        creator: pool.creator,
+        claimed: pool.claimed,
+        ended,
+    })
+}
+
+pub fn all_reward_pools(
+    deps: Deps,
+    env: Env,
+    start_after: Option<Uint128>,
+    limit: Option<u32>,
+) -> StdResult<QueryAnswer> {
+    let limit = page_limit(limit);
+    let now = Uint128::new(env.block.time.seconds() as u128);
+
+    let mut active_pools = REWARD_POOLS
+        .load(deps.storage)?
+        .into_iter()
+        .filter(|pool| start_after.map_or(true, |start_after| pool.id > start_after))
+        .collect::<Vec<RewardPoolInternal>>();
+    active_pools.sort_by(|a, b| a.id.cmp(&b.id));
+    active_pools.truncate(limit);
+
+    let mut pools = vec![];
+    for pool in active_pools {
+        let ended = pool.end <= now;
+        pools.push(reward_pool_record(deps.storage, pool, ended)?);
+    }
+    for item in ENDED_REWARD_POOLS
+        .range(
+            deps.storage,
+            start_after.map(|id| Bound::exclusive(id.u128())),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+    {
+        let (_, pool) = item?;
+        pools.push(reward_pool_record(deps.storage, pool, true)?);
+    }
+    pools.sort_by(|a, b| a.id.cmp(&b.id));
+    pools.truncate(limit);
+
+    Ok(QueryAnswer::AllRewardPools { pools })
+}
+
+/* Rewards the user would claim now, mirroring 'execute::settle_rewards'
+ * so expired locks only earn with their boost until they ended
+ */
+fn pending_rewards(
+    storage: &dyn Storage,
+    env: &Env,
+    user: Addr,
+) -> StdResult<Vec<(Contract, Uint128)>> {
+    let now = Uint128::new(env.block.time.seconds() as u128);
+
+    let mut reward_pools = REWARD_POOLS.load(storage)?;
+    let mut paid = reward_pools
+        .iter()
+        .map(|pool| {
+            Ok(USER_REWARD_PER_TOKEN_PAID
+                .may_load(storage, user_pool_key(user.clone(), pool.id))?
+                .unwrap_or(Uint128::zero()))
+        })
+        .collect::<StdResult<Vec<Uint128>>>()?;
+    let mut earned = vec![Uint128::zero(); reward_pools.len()];
+
+    let mut expired: Vec<Lock> = USER_LOCKS
+        .may_load(storage, user.clone())?
+        .unwrap_or(vec![])
+        .into_iter()
+        .filter(|lock| lock.end <= now)
+        .collect();
+    expired.sort_by_key(|lock| lock.end);
+
+    let mut total_weight = execute::total_weight(storage)?;
+    let mut weight = execute::user_weight(storage, user)?;
+
+    // each expired lock's end, then now
+    let mut segments: Vec<(Uint128, Uint128)> = expired
+        .iter()
+        .map(|lock| (lock.end, lock_boost(lock)))
+        .collect();
+    segments.push((now, Uint128::zero()));
+
+    for (end, boost) in segments {
+        for ((reward_pool, paid), earned) in reward_pools
+            .iter_mut()
+            .zip(paid.iter_mut())
+            .zip(earned.iter_mut())
+        {
+            if reward_pool.last_update < end {
+                *reward_pool = updated_reward_pool(reward_pool, total_weight, end.u128() as u64);
+            }
+            *earned += rewards_earned(weight, reward_pool.reward_per_token, *paid);
+            *paid = reward_pool.reward_per_token;
+        }
+        weight -= boost;
+        total_weight -= boost;
+    }
+
+    Ok(reward_pools
+        .into_iter()
+        .map(|pool| pool.token)
+        .zip(earned)
+        .collect())
+}
+
 pub fn user_balance(
     deps: Deps,
     env: Env,
@@ -131,22 +313,9 @@ pub fn user_balance(
                 unbondings,
             });
         }
-        let reward_pools = REWARD_POOLS.load(deps.storage)?;
-        let total_staked = TOTAL_STAKED.load(deps.storage)?;
-        let now = env.block.time.seconds();
-
-        for reward_pool in reward_pools {
-            let user_reward_per_token_paid = USER_REWARD_PER_TOKEN_PAID
-                .may_load(deps.storage, user_pool_key(user.clone(), reward_pool.id))?
-                .unwrap_or(Uint128::zero());
-            let reward_per_token = reward_per_token(total_staked, now, &reward_pool);
-            let rewards_earned =
-                rewards_earned(user_staked, reward_per_token, user_reward_per_token_paid);
-            if !rewards_earned.is_zero() {
-                rewards.push(Reward {
-                    token: reward_pool.token,
-                    amount: rewards_earned,
-                });
+        for (token, amount) in pending_rewards(deps.storage, &env, user)? {
+            if !amount.is_zero() {
+                rewards.push(Reward { token, amount });
             }
         }
 
@@ -172,6 +341,29 @@ pub fn user_staked(deps: Deps, user: Addr) -> StdResult<QueryAnswer> {
     })
 }
 
+/* Only past times can be queried, stake can still change within the current block */
+pub fn user_staked_at(deps: Deps, env: Env, user: Addr, time: Uint128) -> StdResult<QueryAnswer> {
+    if time >= Uint128::new(env.block.time.seconds() as u128) {
+        return Err(StdError::generic_err("Can only query past stake"));
+    }
+
+    let count = USER_CHECKPOINT_COUNT
+        .may_load(deps.storage, user.clone())?
+        .unwrap_or(0);
+
+    Ok(QueryAnswer::StakedAt {
+        amount: match count {
+            // Unchanged since before checkpointing
+            0 => USER_STAKED
+                .may_load(deps.storage, user)?
+                .unwrap_or(Uint128::zero()),
+            _ => checkpoint_lookup(count, time, |i| {
+                USER_CHECKPOINTS.load(deps.storage, user_checkpoint_key(user.clone(), i))
+            })?,
+        },
+    })
+}
+
 pub fn user_rewards(deps: Deps, env: Env, user: Addr) -> StdResult<QueryAnswer> {
     let mut rewards = vec![];
 
@@ -179,19 +371,8 @@ pub fn user_rewards(deps: Deps, env: Env, user: Addr) -> StdResult<QueryAnswer>
         if user_staked.is_zero() {
             return Ok(QueryAnswer::Rewards { rewards });
         }
-        let reward_pools = REWARD_POOLS.load(deps.storage)?;
-        let total_staked = TOTAL_STAKED.load(deps.storage)?;
-        let now = env.block.time.seconds();
-
-        for reward_pool in reward_pools {
-            let user_reward_per_token_paid = USER_REWARD_PER_TOKEN_PAID
-                .may_load(deps.storage, user_pool_key(user.clone(), reward_pool.id))?
-                .unwrap_or(Uint128::zero());
-            let reward_per_token = reward_per_token(total_staked, now, &reward_pool);
-            rewards.push(Reward {
-                token: reward_pool.token,
-                amount: rewards_earned(user_staked, reward_per_token, user_reward_per_token_paid),
-            });
+        for (token, amount) in pending_rewards(deps.storage, &env, user)? {
+            rewards.push(Reward { token, amount });
         }
     }
 
@@ -213,3 +394,69 @@ pub fn user_unbondings(deps: Deps, user: Addr, ids: Vec<Uint128>) -> StdResult<Q
 
     Ok(QueryAnswer::Unbonding { unbondings })
 }
+
+pub fn user_locks(deps: Deps, user: Addr) -> StdResult<QueryAnswer> {
+    Ok(QueryAnswer::Locks {
+        locks: USER_LOCKS.may_load(deps.storage, user)?.unwrap_or(vec![]),
+    })
+}
+
+/* Expired locks are left out even before they're removed */
+pub fn user_weight(deps: Deps, env: Env, user: Addr) -> StdResult<QueryAnswer> {
+    let now = Uint128::new(env.block.time.seconds() as u128);
+    let staked = USER_STAKED
+        .may_load(deps.storage, user.clone())?
+        .unwrap_or(Uint128::zero());
+    let active: Vec<Lock> = USER_LOCKS
+        .may_load(deps.storage, user)?
+        .unwrap_or(vec![])
+        .into_iter()
+        .filter(|lock| lock.end > now)
+        .collect();
+
+    Ok(QueryAnswer::Weight {
+        staked,
+        locked: active.iter().map(|lock| lock.amount).sum(),
+        weight: staked + active.iter().map(lock_boost).sum::<Uint128>(),
+    })
+}
+
+pub fn total_weight(deps: Deps) -> StdResult<QueryAnswer> {
+    Ok(QueryAnswer::TotalWeight {
+        amount: execute::total_weight(deps.storage)?,
+    })
+}
+
+pub fn user_claimed(
+    deps: Deps,
+    user: Addr,
+    start_after: Option<Uint128>,
+    limit: Option<u32>,
+) -> StdResult<QueryAnswer> {
+    let active_pools = REWARD_POOLS.load(deps.storage)?;
+
+    let mut claimed = vec![];
+    for item in USER_POOL_CLAIMED
+        .prefix(user)
+        .range(
+            deps.storage,
+            start_after.map(|id| Bound::exclusive(id.u128())),
+            None,
+            Order::Ascending,
+        )
+        .take(page_limit(limit))
+    {
+        let (id, amount) = item?;
+        let token = match active_pools.iter().find(|pool| pool.id.u128() == id) {
+            Some(pool) => pool.token.clone(),
+            None => ENDED_REWARD_POOLS.load(deps.storage, id)?.token,
+        };
+        claimed.push(PoolClaimed {
+            pool_id: Uint128::new(id),
+            token,
+            amount,
+        });
+    }
+
+    Ok(QueryAnswer::Claimed { claimed })
+}
@@ -13,13 +13,29 @@ pub const VIEWING_KEY: Item<String> = Item::new("viewing_key");
 // Whitelist for transferring stake
 pub const TRANSFER_WL: Item<Vec<Addr>> = Item::new("transfer_whitelist");
 
+// Addresses allowed to claim/compound on a user's behalf
+pub const USER_OPERATORS: Map<Addr, Vec<basic_staking::Operator>> = Map::new("user_operators");
+
 pub const TOTAL_STAKED: Item<Uint128> = Item::new("total_stake");
 
 pub const REWARD_TOKENS: Item<Vec<Contract>> = Item::new("reward_tokens");
 pub const REWARD_POOLS: Item<Vec<basic_staking::RewardPoolInternal>> = Item::new("reward_pools");
+// Pools removed from REWARD_POOLS by 'EndRewardPool', keyed by pool id
+pub const ENDED_REWARD_POOLS: Map<u128, basic_staking::RewardPoolInternal> =
+    Map::new("ended_reward_pools");
+pub const REWARD_POOL_EXTRACTED: Map<u128, Uint128> = Map::new("reward_pool_extracted");
+// Pool that instant unbond penalties are added to, replaced when it's removed
+pub const PENALTY_POOL_ID: Item<Uint128> = Item::new("penalty_pool_id");
+// Penalties held back while nobody is staked or too small to emit
+pub const PENALTY_CARRY: Item<Uint128> = Item::new("penalty_carry");
 
 pub const USER_STAKED: Map<Addr, Uint128> = Map::new("user_stake");
 
+// Extra reward weight from locked stake, on top of the staked amount
+pub const TOTAL_BOOST: Item<Uint128> = Item::new("total_boost");
+pub const USER_BOOST: Map<Addr, Uint128> = Map::new("user_boost");
+pub const USER_LOCKS: Map<Addr, Vec<basic_staking::Lock>> = Map::new("user_locks");
+
 pub fn user_unbonding_key(user: Addr, unbond_id: Uint128) -> String {
     format!("{}-{}", user, unbond_id)
 }
@@ -32,3 +48,15 @@ pub fn user_pool_key(user: Addr, pool_id: Uint128) -> String {
 }
 
 pub const USER_REWARD_PER_TOKEN_PAID: Map<String, Uint128> = Map::new("user_reward_per_token_paid");
+// Lifetime rewards claimed, keyed by (user, pool id)
+pub const USER_POOL_CLAIMED: Map<(Addr, u128), Uint128> = Map::new("user_pool_claimed");
+
+pub fn user_checkpoint_key(user: Addr, index: u64) -> String {
+    format!("{}-{}", user, index)
+}
+
+// Staked amount history, written whenever USER_STAKED/TOTAL_STAKED change
+pub const USER_CHECKPOINT_COUNT: Map<Addr, u64> = Map::new("user_checkpoint_count");
+pub const USER_CHECKPOINTS: Map<String, basic_staking::Checkpoint> = Map::new("user_checkpoints");
+pub const TOTAL_CHECKPOINT_COUNT: Item<u64> = Item::new("total_checkpoint_count");
+pub const TOTAL_CHECKPOINTS: Map<u64, basic_staking::Checkpoint> = Map::new("total_checkpoints");
@@ -195,10 +195,13 @@ fn bad_stake_token(
             to_binary(&basic_staking::Action::Stake {
                 compound: None,
                 airdrop_task: None,
+                lock: None,
             })
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&reward_token, &mut app, staking_user.clone(), &[]))
@@ -251,6 +254,8 @@ fn bad_stake_token(
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&reward_token, &mut app, reward_user.clone(), &[])
@@ -264,10 +269,13 @@ fn bad_stake_token(
             to_binary(&basic_staking::Action::Stake {
                 compound: None,
                 airdrop_task: None,
+                lock: None,
             })
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&reward_token, &mut app, staking_user.clone(), &[]))
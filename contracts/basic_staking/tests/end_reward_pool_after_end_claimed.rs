@@ -166,10 +166,13 @@ fn end_reward_pool_after_end() {
             to_binary(&basic_staking::Action::Stake {
                 compound: None,
                 airdrop_task: None,
+                lock: None,
             })
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, staking_user.clone(), &[])
@@ -211,6 +214,8 @@ fn end_reward_pool_after_end() {
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, reward_user.clone(), &[])
@@ -268,6 +273,67 @@ fn end_reward_pool_after_end() {
         }
     };
 
+    // Ended pool is kept in history
+    match (basic_staking::QueryMsg::AllRewardPools {
+        start_after: None,
+        limit: None,
+    })
+    .test_query(&basic_staking, &app)
+    .unwrap()
+    {
+        basic_staking::QueryAnswer::AllRewardPools { pools } => {
+            assert_eq!(pools.len(), 1, "Ended pool in history");
+            assert_eq!(pools[0].id, pool_id, "History pool id");
+            assert!(pools[0].ended, "History pool ended");
+            assert_eq!(pools[0].claimed, reward_amount, "History pool claimed");
+            assert_eq!(
+                pools[0].extracted,
+                Uint128::zero(),
+                "History pool extracted"
+            );
+        }
+        _ => {
+            panic!("All reward pools query failed");
+        }
+    };
+
+    match (basic_staking::QueryMsg::AllRewardPools {
+        start_after: Some(pool_id),
+        limit: None,
+    })
+    .test_query(&basic_staking, &app)
+    .unwrap()
+    {
+        basic_staking::QueryAnswer::AllRewardPools { pools } => {
+            assert!(pools.is_empty(), "No pools after the last id");
+        }
+        _ => {
+            panic!("All reward pools query failed");
+        }
+    };
+
+    // Staker claimed the full pool
+    match (basic_staking::QueryMsg::Claimed {
+        auth: basic_staking::Auth::ViewingKey {
+            key: viewing_key.clone(),
+            address: staking_user.clone().into(),
+        },
+        start_after: None,
+        limit: None,
+    })
+    .test_query(&basic_staking, &app)
+    .unwrap()
+    {
+        basic_staking::QueryAnswer::Claimed { claimed } => {
+            assert_eq!(claimed.len(), 1, "Claimed from 1 pool");
+            assert_eq!(claimed[0].pool_id, pool_id, "Claimed pool id");
+            assert_eq!(claimed[0].amount, reward_amount, "Claimed amount");
+        }
+        _ => {
+            panic!("Claimed query failed");
+        }
+    };
+
     // Admin user that cancelled should receive un-emitted funds
     match (snip20::QueryMsg::Balance {
         key: viewing_key.clone(),
@@ -194,10 +194,13 @@ fn end_reward_pool_after_end() {
             to_binary(&basic_staking::Action::Stake {
                 compound: None,
                 airdrop_task: None,
+                lock: None,
             })
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, staking_user.clone(), &[])
@@ -239,6 +242,8 @@ fn end_reward_pool_after_end() {
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, reward_user.clone(), &[])
@@ -334,6 +339,8 @@ fn end_reward_pool_after_end() {
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&second_token, &mut app, reward_user.clone(), &[])
@@ -166,10 +166,13 @@ fn end_reward_pool_before_end_claimed() {
             to_binary(&basic_staking::Action::Stake {
                 compound: None,
                 airdrop_task: None,
+                lock: None,
             })
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, staking_user.clone(), &[])
@@ -211,6 +214,8 @@ fn end_reward_pool_before_end_claimed() {
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, reward_user.clone(), &[])
@@ -0,0 +1,308 @@
+use shade_protocol::c_std::{to_binary, Addr, BlockInfo, Decimal, Timestamp, Uint128};
+
+use shade_protocol::{
+    contract_interfaces::{basic_staking, query_auth, snip20},
+    multi_test::App,
+    utils::{ExecuteCallback, InstantiateCallback, MultiTestable, Query},
+};
+
+use shade_multi_test::multi::{
+    admin::init_admin_auth,
+    basic_staking::BasicStaking,
+    query_auth::QueryAuth,
+    snip20::Snip20,
+};
+
+// Boost expires halfway through the reward pool, before anyone interacts
+#[test]
+fn expired_lock_claim() {
+    let mut app = App::default();
+
+    // init block time for predictable behavior
+    app.set_block(BlockInfo {
+        height: 1,
+        time: Timestamp::from_seconds(0),
+        chain_id: "chain_id".to_string(),
+    });
+
+    let viewing_key = "unguessable".to_string();
+    let admin_user = Addr::unchecked("admin");
+    let reward_user = Addr::unchecked("reward_user");
+    let locked_user = Addr::unchecked("locked_user");
+    let unlocked_user = Addr::unchecked("unlocked_user");
+    let anyone = Addr::unchecked("anyone");
+
+    let stake_amount = Uint128::new(100);
+    let reward_amount = Uint128::new(300);
+    let reward_end = Uint128::new(100);
+    let lock_duration = Uint128::new(50);
+
+    let token = snip20::InstantiateMsg {
+        name: "stake_token".into(),
+        admin: Some(admin_user.to_string().clone()),
+        symbol: "STKN".into(),
+        decimals: 6,
+        initial_balances: Some(vec![
+            snip20::InitialBalance {
+                amount: stake_amount,
+                address: locked_user.to_string(),
+            },
+            snip20::InitialBalance {
+                amount: stake_amount,
+                address: unlocked_user.to_string(),
+            },
+            snip20::InitialBalance {
+                amount: reward_amount,
+                address: reward_user.to_string(),
+            },
+        ]),
+        query_auth: None,
+        prng_seed: to_binary("").ok().unwrap(),
+        config: Some(snip20::InitConfig {
+            public_total_supply: Some(true),
+            enable_deposit: Some(false),
+            enable_redeem: Some(false),
+            enable_mint: Some(false),
+            enable_burn: Some(false),
+            enable_transfer: Some(true),
+        }),
+    }
+    .test_init(
+        Snip20::default(),
+        &mut app,
+        admin_user.clone(),
+        "stake_token",
+        &[],
+    )
+    .unwrap();
+
+    let admin_contract = init_admin_auth(&mut app, &admin_user);
+
+    let query_contract = query_auth::InstantiateMsg {
+        admin_auth: admin_contract.clone().into(),
+        prng_seed: to_binary("").ok().unwrap(),
+    }
+    .test_init(
+        QueryAuth::default(),
+        &mut app,
+        admin_user.clone(),
+        "query_auth",
+        &[],
+    )
+    .unwrap();
+
+    for user in vec![locked_user.clone(), unlocked_user.clone()] {
+        snip20::ExecuteMsg::SetViewingKey {
+            key: viewing_key.clone(),
+            padding: None,
+        }
+        .test_exec(&token, &mut app, user.clone(), &[])
+        .unwrap();
+
+        query_auth::ExecuteMsg::SetViewingKey {
+            key: viewing_key.clone(),
+            padding: None,
+        }
+        .test_exec(&query_contract, &mut app, user, &[])
+        .unwrap();
+    }
+
+    let basic_staking = basic_staking::InstantiateMsg {
+        admin_auth: admin_contract.into(),
+        query_auth: query_contract.into(),
+        airdrop: None,
+        stake_token: token.clone().into(),
+        unbond_period: Uint128::zero(),
+        max_user_pools: Uint128::one(),
+        viewing_key: viewing_key.clone(),
+    }
+    .test_init(
+        BasicStaking::default(),
+        &mut app,
+        admin_user.clone(),
+        "basic_staking",
+        &[],
+    )
+    .unwrap();
+
+    basic_staking::ExecuteMsg::UpdateConfig {
+        admin_auth: None,
+        query_auth: None,
+        airdrop: None,
+        unbond_period: None,
+        max_user_pools: None,
+        lock_tiers: Some(vec![basic_staking::LockTier {
+            duration: lock_duration,
+            multiplier: Decimal::percent(200),
+        }]),
+        instant_unbond: None,
+        disable_instant_unbond: None,
+        padding: None,
+    }
+    .test_exec(&basic_staking, &mut app, admin_user.clone(), &[])
+    .unwrap();
+
+    for (user, lock) in vec![
+        (locked_user.clone(), Some(lock_duration)),
+        (unlocked_user.clone(), None),
+    ] {
+        snip20::ExecuteMsg::Send {
+            recipient: basic_staking.address.to_string().clone(),
+            recipient_code_hash: None,
+            amount: stake_amount,
+            msg: Some(
+                to_binary(&basic_staking::Action::Stake {
+                    compound: None,
+                    airdrop_task: None,
+                    lock,
+                })
+                .unwrap(),
+            ),
+            memo: None,
+            decoys: None,
+            entropy: None,
+            padding: None,
+        }
+        .test_exec(&token, &mut app, user, &[])
+        .unwrap();
+    }
+
+    // Init Rewards
+    snip20::ExecuteMsg::Send {
+        recipient: basic_staking.address.to_string().clone(),
+        recipient_code_hash: None,
+        amount: reward_amount,
+        msg: Some(
+            to_binary(&basic_staking::Action::Rewards {
+                start: Uint128::zero(),
+                end: reward_end,
+            })
+            .unwrap(),
+        ),
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None,
+    }
+    .test_exec(&token, &mut app, reward_user.clone(), &[])
+    .unwrap();
+
+    // Nothing to expire yet
+    assert!(
+        basic_staking::ExecuteMsg::ExpireLocks {
+            user: locked_user.to_string(),
+            padding: None,
+        }
+        .test_exec(&basic_staking, &mut app, anyone.clone(), &[])
+        .is_err(),
+        "Expired active lock"
+    );
+
+    // Move past lock expiry to the end of rewards
+    app.set_block(BlockInfo {
+        height: 2,
+        time: Timestamp::from_seconds(reward_end.u128() as u64),
+        chain_id: "chain_id".to_string(),
+    });
+
+    // Expired boost no longer counts, though it hasn't been removed
+    match (basic_staking::QueryMsg::Weight {
+        auth: basic_staking::Auth::ViewingKey {
+            key: viewing_key.clone(),
+            address: locked_user.clone().into(),
+        },
+    })
+    .test_query(&basic_staking, &app)
+    .unwrap()
+    {
+        basic_staking::QueryAnswer::Weight {
+            staked,
+            locked,
+            weight,
+        } => {
+            assert_eq!(staked, stake_amount, "Weight staked");
+            assert_eq!(locked, Uint128::zero(), "Weight locked");
+            assert_eq!(weight, stake_amount, "Unboosted weight");
+        }
+        _ => {
+            panic!("Weight query failed");
+        }
+    };
+
+    // 0-50: 150 split 2:1, 50-100: 150 split 1:1
+    let locked_reward = Uint128::new(175);
+    let unlocked_reward = Uint128::new(125);
+
+    match (basic_staking::QueryMsg::Rewards {
+        auth: basic_staking::Auth::ViewingKey {
+            key: viewing_key.clone(),
+            address: locked_user.clone().into(),
+        },
+    })
+    .test_query(&basic_staking, &app)
+    .unwrap()
+    {
+        basic_staking::QueryAnswer::Rewards { rewards } => {
+            assert_eq!(rewards.len(), 1, "Rewards length");
+            let amount = rewards[0].amount;
+            assert!(
+                amount >= locked_reward - Uint128::one() && amount <= locked_reward,
+                "Pending rewards within error of 1 unit token {} != {}",
+                amount,
+                locked_reward
+            );
+        }
+        _ => {
+            panic!("Staking rewards query failed");
+        }
+    };
+
+    // Anyone can settle the expired lock, rewards go to the locked user
+    basic_staking::ExecuteMsg::ExpireLocks {
+        user: locked_user.to_string(),
+        padding: None,
+    }
+    .test_exec(&basic_staking, &mut app, anyone.clone(), &[])
+    .unwrap();
+
+    basic_staking::ExecuteMsg::Claim { padding: None }
+        .test_exec(&basic_staking, &mut app, unlocked_user.clone(), &[])
+        .unwrap();
+
+    for (user, expected) in vec![
+        (locked_user.clone(), locked_reward),
+        (unlocked_user.clone(), unlocked_reward),
+    ] {
+        match (snip20::QueryMsg::Balance {
+            key: viewing_key.clone(),
+            address: user.clone().into(),
+        })
+        .test_query(&token, &app)
+        .unwrap()
+        {
+            snip20::QueryAnswer::Balance { amount } => {
+                assert!(
+                    amount >= expected - Uint128::one() && amount <= expected,
+                    "Rewards claimed within error of 1 unit token {} != {}",
+                    amount,
+                    expected,
+                );
+            }
+            _ => {
+                panic!("Snip20 balance query failed");
+            }
+        };
+    }
+
+    match (basic_staking::QueryMsg::TotalWeight {})
+        .test_query(&basic_staking, &app)
+        .unwrap()
+    {
+        basic_staking::QueryAnswer::TotalWeight { amount } => {
+            assert_eq!(amount, stake_amount * Uint128::new(2), "Total weight");
+        }
+        _ => {
+            panic!("Total weight query failed");
+        }
+    };
+}
@@ -0,0 +1,387 @@
+use shade_protocol::c_std::{to_binary, Addr, BlockInfo, Decimal, Timestamp, Uint128};
+
+use shade_protocol::{
+    contract_interfaces::{basic_staking, query_auth, snip20},
+    multi_test::App,
+    utils::{ExecuteCallback, InstantiateCallback, MultiTestable, Query},
+};
+
+use shade_multi_test::multi::{
+    admin::{init_admin_auth, Admin},
+    basic_staking::BasicStaking,
+    query_auth::QueryAuth,
+    snip20::Snip20,
+};
+
+fn instant_unbond(destination_stakers: bool) {
+    let mut app = App::default();
+
+    // init block time for predictable behavior
+    app.set_block(BlockInfo {
+        height: 1,
+        time: Timestamp::from_seconds(0),
+        chain_id: "chain_id".to_string(),
+    });
+
+    let viewing_key = "unguessable".to_string();
+    let admin_user = Addr::unchecked("admin");
+    let instant_user = Addr::unchecked("instant_user");
+    let remaining_user = Addr::unchecked("remaining_user");
+    let treasury = Addr::unchecked("treasury");
+
+    let stake_amount = Uint128::new(100);
+    let penalty = Uint128::new(10);
+    let penalty_duration = Uint128::new(100);
+
+    let token = snip20::InstantiateMsg {
+        name: "stake_token".into(),
+        admin: Some(admin_user.to_string().clone()),
+        symbol: "STKN".into(),
+        decimals: 6,
+        initial_balances: Some(vec![
+            snip20::InitialBalance {
+                amount: stake_amount,
+                address: instant_user.to_string(),
+            },
+            snip20::InitialBalance {
+                amount: stake_amount,
+                address: remaining_user.to_string(),
+            },
+        ]),
+        query_auth: None,
+        prng_seed: to_binary("").ok().unwrap(),
+        config: Some(snip20::InitConfig {
+            public_total_supply: Some(true),
+            enable_deposit: Some(false),
+            enable_redeem: Some(false),
+            enable_mint: Some(false),
+            enable_burn: Some(false),
+            enable_transfer: Some(true),
+        }),
+    }
+    .test_init(
+        Snip20::default(),
+        &mut app,
+        admin_user.clone(),
+        "stake_token",
+        &[],
+    )
+    .unwrap();
+
+    let admin_contract = init_admin_auth(&mut app, &admin_user);
+
+    let query_contract = query_auth::InstantiateMsg {
+        admin_auth: admin_contract.clone().into(),
+        prng_seed: to_binary("").ok().unwrap(),
+    }
+    .test_init(
+        QueryAuth::default(),
+        &mut app,
+        admin_user.clone(),
+        "query_auth",
+        &[],
+    )
+    .unwrap();
+
+    for user in vec![instant_user.clone(), remaining_user.clone(), treasury.clone()] {
+        snip20::ExecuteMsg::SetViewingKey {
+            key: viewing_key.clone(),
+            padding: None,
+        }
+        .test_exec(&token, &mut app, user.clone(), &[])
+        .unwrap();
+
+        query_auth::ExecuteMsg::SetViewingKey {
+            key: viewing_key.clone(),
+            padding: None,
+        }
+        .test_exec(&query_contract, &mut app, user, &[])
+        .unwrap();
+    }
+
+    let basic_staking = basic_staking::InstantiateMsg {
+        admin_auth: admin_contract.into(),
+        query_auth: query_contract.into(),
+        airdrop: None,
+        stake_token: token.clone().into(),
+        unbond_period: Uint128::new(100),
+        max_user_pools: Uint128::one(),
+        viewing_key: viewing_key.clone(),
+    }
+    .test_init(
+        BasicStaking::default(),
+        &mut app,
+        admin_user.clone(),
+        "basic_staking",
+        &[],
+    )
+    .unwrap();
+
+    // Instant unbond is disabled by default
+    basic_staking::ExecuteMsg::Unbond {
+        amount: stake_amount,
+        compound: None,
+        instant: Some(true),
+        padding: None,
+    }
+    .test_exec(&basic_staking, &mut app, instant_user.clone(), &[])
+    .unwrap_err();
+
+    let destination = if destination_stakers {
+        basic_staking::PenaltyDestination::Stakers {
+            duration: penalty_duration,
+        }
+    } else {
+        basic_staking::PenaltyDestination::Treasury {
+            address: treasury.to_string(),
+        }
+    };
+
+    // Treasury address must be valid
+    basic_staking::ExecuteMsg::UpdateConfig {
+        admin_auth: None,
+        query_auth: None,
+        airdrop: None,
+        unbond_period: None,
+        max_user_pools: None,
+        lock_tiers: None,
+        instant_unbond: Some(basic_staking::InstantUnbond {
+            penalty: Decimal::percent(10),
+            destination: basic_staking::PenaltyDestination::Treasury {
+                address: "".to_string(),
+            },
+        }),
+        disable_instant_unbond: None,
+        padding: None,
+    }
+    .test_exec(&basic_staking, &mut app, admin_user.clone(), &[])
+    .unwrap_err();
+
+    basic_staking::ExecuteMsg::UpdateConfig {
+        admin_auth: None,
+        query_auth: None,
+        airdrop: None,
+        unbond_period: None,
+        max_user_pools: None,
+        lock_tiers: None,
+        instant_unbond: Some(basic_staking::InstantUnbond {
+            penalty: Decimal::percent(10),
+            destination,
+        }),
+        disable_instant_unbond: None,
+        padding: None,
+    }
+    .test_exec(&basic_staking, &mut app, admin_user.clone(), &[])
+    .unwrap();
+
+    for user in vec![instant_user.clone(), remaining_user.clone()] {
+        snip20::ExecuteMsg::Send {
+            recipient: basic_staking.address.to_string().clone(),
+            recipient_code_hash: None,
+            amount: stake_amount,
+            msg: Some(
+                to_binary(&basic_staking::Action::Stake {
+                    compound: None,
+                    airdrop_task: None,
+                    lock: None,
+                })
+                .unwrap(),
+            ),
+            memo: None,
+            decoys: None,
+            entropy: None,
+            padding: None,
+        }
+        .test_exec(&token, &mut app, user, &[])
+        .unwrap();
+    }
+
+    basic_staking::ExecuteMsg::Unbond {
+        amount: stake_amount,
+        compound: None,
+        instant: Some(true),
+        padding: None,
+    }
+    .test_exec(&basic_staking, &mut app, instant_user.clone(), &[])
+    .unwrap();
+
+    // Funds received immediately minus penalty, nothing left unbonding
+    match (snip20::QueryMsg::Balance {
+        key: viewing_key.clone(),
+        address: instant_user.clone().into(),
+    })
+    .test_query(&token, &app)
+    .unwrap()
+    {
+        snip20::QueryAnswer::Balance { amount } => {
+            assert_eq!(amount, stake_amount - penalty, "Instant unbond balance");
+        }
+        _ => {
+            panic!("Snip20 balance query failed");
+        }
+    };
+
+    match (basic_staking::QueryMsg::Unbonding {
+        ids: None,
+        auth: basic_staking::Auth::ViewingKey {
+            key: viewing_key.clone(),
+            address: instant_user.clone().into(),
+        },
+    })
+    .test_query(&basic_staking, &app)
+    .unwrap()
+    {
+        basic_staking::QueryAnswer::Unbonding { unbondings } => {
+            assert!(unbondings.is_empty(), "No pending unbondings");
+        }
+        _ => {
+            panic!("Staking unbonding query failed");
+        }
+    };
+
+    match (basic_staking::QueryMsg::TotalStaked {})
+        .test_query(&basic_staking, &app)
+        .unwrap()
+    {
+        basic_staking::QueryAnswer::TotalStaked { amount } => {
+            assert_eq!(amount, stake_amount, "Total staked after instant unbond");
+        }
+        _ => {
+            panic!("Total staked query failed");
+        }
+    };
+
+    if destination_stakers {
+        // Penalty emitted to remaining stakers
+        app.set_block(BlockInfo {
+            height: 2,
+            time: Timestamp::from_seconds(penalty_duration.u128() as u64),
+            chain_id: "chain_id".to_string(),
+        });
+
+        match (basic_staking::QueryMsg::Rewards {
+            auth: basic_staking::Auth::ViewingKey {
+                key: viewing_key.clone(),
+                address: remaining_user.clone().into(),
+            },
+        })
+        .test_query(&basic_staking, &app)
+        .unwrap()
+        {
+            basic_staking::QueryAnswer::Rewards { rewards } => {
+                assert_eq!(rewards.len(), 1, "Penalty reward pool");
+                let amount = rewards[0].amount;
+                assert!(
+                    amount >= penalty - Uint128::one() && amount <= penalty,
+                    "Penalty rewards within error of 1 unit token {} != {}",
+                    amount,
+                    penalty
+                );
+            }
+            _ => {
+                panic!("Staking rewards query failed");
+            }
+        };
+
+        let penalty_pools = |app: &App| -> Vec<basic_staking::RewardPool> {
+            match (basic_staking::QueryMsg::RewardPools {})
+                .test_query(&basic_staking, app)
+                .unwrap()
+            {
+                basic_staking::QueryAnswer::RewardPools { rewards } => rewards,
+                _ => panic!("Reward pools query failed"),
+            }
+        };
+
+        let unbond_instantly = |app: &mut App, user: &Addr, amount: Uint128| {
+            basic_staking::ExecuteMsg::Unbond {
+                amount,
+                compound: None,
+                instant: Some(true),
+                padding: None,
+            }
+            .test_exec(&basic_staking, app, user.clone(), &[])
+            .unwrap();
+        };
+
+        // Later penalties restart the same pool
+        unbond_instantly(&mut app, &remaining_user, stake_amount / Uint128::new(2));
+        let pools = penalty_pools(&app);
+        assert_eq!(pools.len(), 1, "Single penalty pool");
+        assert_eq!(
+            pools[0].amount,
+            penalty + penalty / Uint128::new(2),
+            "Penalty pool topped up"
+        );
+        assert_eq!(
+            pools[0].end,
+            penalty_duration * Uint128::new(2),
+            "Penalty pool extended"
+        );
+
+        // Nobody left to emit to, carried forward
+        unbond_instantly(&mut app, &remaining_user, stake_amount / Uint128::new(2));
+        let pools = penalty_pools(&app);
+        assert_eq!(pools.len(), 1, "Single penalty pool");
+        assert_eq!(
+            pools[0].amount,
+            penalty + penalty / Uint128::new(2),
+            "Penalty carried"
+        );
+
+        snip20::ExecuteMsg::Send {
+            recipient: basic_staking.address.to_string().clone(),
+            recipient_code_hash: None,
+            amount: stake_amount - penalty,
+            msg: Some(
+                to_binary(&basic_staking::Action::Stake {
+                    compound: None,
+                    airdrop_task: None,
+                    lock: None,
+                })
+                .unwrap(),
+            ),
+            memo: None,
+            decoys: None,
+            entropy: None,
+            padding: None,
+        }
+        .test_exec(&token, &mut app, instant_user.clone(), &[])
+        .unwrap();
+
+        // Picks up the carried penalty with the new one
+        unbond_instantly(&mut app, &instant_user, penalty);
+        let pools = penalty_pools(&app);
+        assert_eq!(pools.len(), 1, "Single penalty pool");
+        assert_eq!(
+            pools[0].amount,
+            penalty * Uint128::new(2) + Uint128::one(),
+            "Carried penalty added"
+        );
+    } else {
+        match (snip20::QueryMsg::Balance {
+            key: viewing_key.clone(),
+            address: treasury.clone().into(),
+        })
+        .test_query(&token, &app)
+        .unwrap()
+        {
+            snip20::QueryAnswer::Balance { amount } => {
+                assert_eq!(amount, penalty, "Treasury received penalty");
+            }
+            _ => {
+                panic!("Snip20 balance query failed");
+            }
+        };
+    }
+}
+
+#[test]
+fn instant_unbond_to_treasury() {
+    instant_unbond(false);
+}
+
+#[test]
+fn instant_unbond_to_stakers() {
+    instant_unbond(true);
+}
@@ -168,10 +168,13 @@ fn multi_staker_single_pool(
                 to_binary(&basic_staking::Action::Stake {
                     compound: None,
                     airdrop_task: None,
+                    lock: None,
                 })
                 .unwrap(),
             ),
             memo: None,
+            decoys: None,
+            entropy: None,
             padding: None,
         }
         .test_exec(&token, &mut app, user.clone(), &[])
@@ -209,6 +212,8 @@ fn multi_staker_single_pool(
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, reward_user.clone(), &[])
@@ -92,6 +92,9 @@ fn non_admin_access() {
         airdrop: None,
         unbond_period: None,
         max_user_pools: None,
+        lock_tiers: None,
+        instant_unbond: None,
+        disable_instant_unbond: None,
         padding: None,
     }
     .test_exec(&basic_staking, &mut app, non_admin_user.clone(), &[]))
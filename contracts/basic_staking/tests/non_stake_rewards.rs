@@ -189,10 +189,13 @@ fn non_stake_rewards(
             to_binary(&basic_staking::Action::Stake {
                 compound: None,
                 airdrop_task: None,
+                lock: None,
             })
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&stake_token, &mut app, staking_user.clone(), &[])
@@ -242,6 +245,8 @@ fn non_stake_rewards(
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&reward_token, &mut app, reward_user.clone(), &[])
@@ -424,6 +429,7 @@ fn non_stake_rewards(
     basic_staking::ExecuteMsg::Unbond {
         amount: stake_amount,
         compound: None,
+        instant: None,
         padding: None,
     }
     .test_exec(&basic_staking, &mut app, staking_user.clone(), &[])
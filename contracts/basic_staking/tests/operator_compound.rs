@@ -0,0 +1,256 @@
+use shade_protocol::c_std::{to_binary, Addr, BlockInfo, Timestamp, Uint128};
+
+use shade_protocol::{
+    contract_interfaces::{basic_staking, query_auth, snip20},
+    multi_test::App,
+    utils::{ExecuteCallback, InstantiateCallback, MultiTestable, Query},
+};
+
+use shade_multi_test::multi::{
+    admin::{init_admin_auth, Admin},
+    basic_staking::BasicStaking,
+    query_auth::QueryAuth,
+    snip20::Snip20,
+};
+
+#[test]
+fn operator_compound() {
+    let mut app = App::default();
+
+    // init block time for predictable behavior
+    app.set_block(BlockInfo {
+        height: 1,
+        time: Timestamp::from_seconds(0),
+        chain_id: "chain_id".to_string(),
+    });
+
+    let viewing_key = "unguessable".to_string();
+    let admin_user = Addr::unchecked("admin");
+    let reward_user = Addr::unchecked("reward_user");
+    let staking_user = Addr::unchecked("staking_user");
+    let operator = Addr::unchecked("operator");
+
+    let stake_amount = Uint128::new(100);
+    let reward_amount = Uint128::new(100);
+    let reward_end = Uint128::new(100);
+
+    let token = snip20::InstantiateMsg {
+        name: "stake_token".into(),
+        admin: Some(admin_user.to_string().clone()),
+        symbol: "STKN".into(),
+        decimals: 6,
+        initial_balances: Some(vec![
+            snip20::InitialBalance {
+                amount: stake_amount,
+                address: staking_user.to_string(),
+            },
+            snip20::InitialBalance {
+                amount: reward_amount,
+                address: reward_user.to_string(),
+            },
+        ]),
+        query_auth: None,
+        prng_seed: to_binary("").ok().unwrap(),
+        config: Some(snip20::InitConfig {
+            public_total_supply: Some(true),
+            enable_deposit: Some(false),
+            enable_redeem: Some(false),
+            enable_mint: Some(false),
+            enable_burn: Some(false),
+            enable_transfer: Some(true),
+        }),
+    }
+    .test_init(
+        Snip20::default(),
+        &mut app,
+        admin_user.clone(),
+        "stake_token",
+        &[],
+    )
+    .unwrap();
+
+    let admin_contract = init_admin_auth(&mut app, &admin_user);
+
+    let query_contract = query_auth::InstantiateMsg {
+        admin_auth: admin_contract.clone().into(),
+        prng_seed: to_binary("").ok().unwrap(),
+    }
+    .test_init(
+        QueryAuth::default(),
+        &mut app,
+        admin_user.clone(),
+        "query_auth",
+        &[],
+    )
+    .unwrap();
+
+    query_auth::ExecuteMsg::SetViewingKey {
+        key: viewing_key.clone(),
+        padding: None,
+    }
+    .test_exec(&query_contract, &mut app, staking_user.clone(), &[])
+    .unwrap();
+
+    let basic_staking = basic_staking::InstantiateMsg {
+        admin_auth: admin_contract.into(),
+        query_auth: query_contract.into(),
+        airdrop: None,
+        stake_token: token.clone().into(),
+        unbond_period: Uint128::zero(),
+        max_user_pools: Uint128::one(),
+        viewing_key: viewing_key.clone(),
+    }
+    .test_init(
+        BasicStaking::default(),
+        &mut app,
+        admin_user.clone(),
+        "basic_staking",
+        &[],
+    )
+    .unwrap();
+
+    // Stake funds
+    snip20::ExecuteMsg::Send {
+        recipient: basic_staking.address.to_string().clone(),
+        recipient_code_hash: None,
+        amount: stake_amount,
+        msg: Some(
+            to_binary(&basic_staking::Action::Stake {
+                compound: None,
+                airdrop_task: None,
+                lock: None,
+            })
+            .unwrap(),
+        ),
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None,
+    }
+    .test_exec(&token, &mut app, staking_user.clone(), &[])
+    .unwrap();
+
+    // Init Rewards
+    snip20::ExecuteMsg::Send {
+        recipient: basic_staking.address.to_string().clone(),
+        recipient_code_hash: None,
+        amount: reward_amount,
+        msg: Some(
+            to_binary(&basic_staking::Action::Rewards {
+                start: Uint128::zero(),
+                end: reward_end,
+            })
+            .unwrap(),
+        ),
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None,
+    }
+    .test_exec(&token, &mut app, reward_user.clone(), &[])
+    .unwrap();
+
+    // Unauthorized operator can't compound
+    assert!(
+        basic_staking::ExecuteMsg::CompoundFor {
+            user: staking_user.to_string(),
+            padding: None,
+        }
+        .test_exec(&basic_staking, &mut app, operator.clone(), &[])
+        .is_err(),
+        "Compounded without authorization"
+    );
+
+    basic_staking::ExecuteMsg::SetOperator {
+        operator: operator.to_string(),
+        permissions: vec![basic_staking::OperatorPermission::Compound],
+        expiration: None,
+        padding: None,
+    }
+    .test_exec(&basic_staking, &mut app, staking_user.clone(), &[])
+    .unwrap();
+
+    match (basic_staking::QueryMsg::Operators {
+        auth: basic_staking::Auth::ViewingKey {
+            key: viewing_key.clone(),
+            address: staking_user.clone().into(),
+        },
+    })
+    .test_query(&basic_staking, &app)
+    .unwrap()
+    {
+        basic_staking::QueryAnswer::Operators { operators } => {
+            assert_eq!(operators.len(), 1, "1 operator");
+            assert_eq!(operators[0].address, operator, "Operator address");
+        }
+        _ => {
+            panic!("Operators query failed");
+        }
+    };
+
+    // Move to end of rewards
+    app.set_block(BlockInfo {
+        height: 2,
+        time: Timestamp::from_seconds(reward_end.u128() as u64),
+        chain_id: "chain_id".to_string(),
+    });
+
+    // Claim is out of the operator's scope
+    assert!(
+        basic_staking::ExecuteMsg::ClaimFor {
+            user: staking_user.to_string(),
+            padding: None,
+        }
+        .test_exec(&basic_staking, &mut app, operator.clone(), &[])
+        .is_err(),
+        "Claimed without claim permission"
+    );
+
+    basic_staking::ExecuteMsg::CompoundFor {
+        user: staking_user.to_string(),
+        padding: None,
+    }
+    .test_exec(&basic_staking, &mut app, operator.clone(), &[])
+    .unwrap();
+
+    // Rewards were compounded into the user's stake
+    match (basic_staking::QueryMsg::Staked {
+        auth: basic_staking::Auth::ViewingKey {
+            key: viewing_key.clone(),
+            address: staking_user.clone().into(),
+        },
+    })
+    .test_query(&basic_staking, &app)
+    .unwrap()
+    {
+        basic_staking::QueryAnswer::Staked { amount } => {
+            let expected = stake_amount + reward_amount;
+            assert!(
+                amount >= expected - Uint128::one() && amount <= expected,
+                "Compounded stake within error of 1 unit token {} != {}",
+                amount,
+                expected
+            );
+        }
+        _ => {
+            panic!("Staking balance query failed");
+        }
+    };
+
+    basic_staking::ExecuteMsg::RevokeOperator {
+        operator: operator.to_string(),
+        padding: None,
+    }
+    .test_exec(&basic_staking, &mut app, staking_user.clone(), &[])
+    .unwrap();
+
+    assert!(
+        basic_staking::ExecuteMsg::CompoundFor {
+            user: staking_user.to_string(),
+            padding: None,
+        }
+        .test_exec(&basic_staking, &mut app, operator.clone(), &[])
+        .is_err(),
+        "Compounded after revoke"
+    );
+}
@@ -160,10 +160,13 @@ fn single_staker_single_pool(
             to_binary(&basic_staking::Action::Stake {
                 compound: None,
                 airdrop_task: None,
+                lock: None,
             })
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, staking_user.clone(), &[])
@@ -219,9 +222,12 @@ fn single_staker_single_pool(
     };
 
     // Stake token should be registered
-    match (basic_staking::QueryMsg::RewardTokens {})
-        .test_query(&basic_staking, &app)
-        .unwrap()
+    match (basic_staking::QueryMsg::RewardTokens {
+        start_after: None,
+        limit: None,
+    })
+    .test_query(&basic_staking, &app)
+    .unwrap()
     {
         basic_staking::QueryAnswer::RewardTokens { tokens } => {
             assert_eq!(tokens.len(), 1, "Only stake token registered");
@@ -245,6 +251,8 @@ fn single_staker_single_pool(
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, reward_user.clone(), &[])
@@ -427,6 +435,7 @@ fn single_staker_single_pool(
     basic_staking::ExecuteMsg::Unbond {
         amount: stake_amount,
         compound: None,
+        instant: None,
         padding: None,
     }
     .test_exec(&basic_staking, &mut app, staking_user.clone(), &[])
@@ -157,10 +157,13 @@ fn single_staker_compounding(
             to_binary(&basic_staking::Action::Stake {
                 compound: None,
                 airdrop_task: None,
+                lock: None,
             })
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, staking_user.clone(), &[])
@@ -197,6 +200,8 @@ fn single_staker_compounding(
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, reward_user.clone(), &[])
@@ -314,10 +319,13 @@ fn single_staker_compounding(
             to_binary(&basic_staking::Action::Stake {
                 compound: Some(true),
                 airdrop_task: None,
+                lock: None,
             })
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, staking_user.clone(), &[])
@@ -447,6 +455,7 @@ fn single_staker_compounding(
     basic_staking::ExecuteMsg::Unbond {
         amount: stake_amount + current_rewards + mid_rewards,
         compound: None,
+        instant: None,
         padding: None,
     }
     .test_exec(&basic_staking, &mut app, staking_user.clone(), &[])
@@ -141,6 +141,8 @@ fn stake_0_total_bug(
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, reward_user.clone(), &[])
@@ -188,10 +190,13 @@ fn stake_0_total_bug(
             to_binary(&basic_staking::Action::Stake {
                 compound: None,
                 airdrop_task: None,
+                lock: None,
             })
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, staking_user.clone(), &[])
@@ -344,6 +349,7 @@ fn stake_0_total_bug(
     basic_staking::ExecuteMsg::Unbond {
         amount: stake_amount,
         compound: None,
+        instant: None,
         padding: None,
     }
     .test_exec(&basic_staking, &mut app, staking_user.clone(), &[])
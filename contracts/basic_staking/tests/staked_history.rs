@@ -0,0 +1,213 @@
+use shade_protocol::c_std::{to_binary, Addr, BlockInfo, Timestamp, Uint128};
+
+use shade_protocol::{
+    contract_interfaces::{basic_staking, query_auth, snip20},
+    multi_test::App,
+    utils::{ExecuteCallback, InstantiateCallback, MultiTestable, Query},
+};
+
+use shade_multi_test::multi::{
+    admin::{init_admin_auth, Admin},
+    basic_staking::BasicStaking,
+    query_auth::QueryAuth,
+    snip20::Snip20,
+};
+
+#[test]
+fn staked_history() {
+    let mut app = App::default();
+
+    // init block time for predictable behavior
+    app.set_block(BlockInfo {
+        height: 1,
+        time: Timestamp::from_seconds(0),
+        chain_id: "chain_id".to_string(),
+    });
+
+    let viewing_key = "unguessable".to_string();
+    let admin_user = Addr::unchecked("admin");
+    let staking_user = Addr::unchecked("staking_user");
+
+    let stake_amount = Uint128::new(100);
+    let unbond_amount = Uint128::new(40);
+
+    let token = snip20::InstantiateMsg {
+        name: "stake_token".into(),
+        admin: Some(admin_user.to_string().clone()),
+        symbol: "STKN".into(),
+        decimals: 6,
+        initial_balances: Some(vec![
+            snip20::InitialBalance {
+                amount: stake_amount,
+                address: staking_user.to_string(),
+            },
+        ]),
+        query_auth: None,
+        prng_seed: to_binary("").ok().unwrap(),
+        config: Some(snip20::InitConfig {
+            public_total_supply: Some(true),
+            enable_deposit: Some(false),
+            enable_redeem: Some(false),
+            enable_mint: Some(false),
+            enable_burn: Some(false),
+            enable_transfer: Some(true),
+        }),
+    }
+    .test_init(
+        Snip20::default(),
+        &mut app,
+        admin_user.clone(),
+        "stake_token",
+        &[],
+    )
+    .unwrap();
+
+    let admin_contract = init_admin_auth(&mut app, &admin_user);
+
+    let query_contract = query_auth::InstantiateMsg {
+        admin_auth: admin_contract.clone().into(),
+        prng_seed: to_binary("").ok().unwrap(),
+    }
+    .test_init(
+        QueryAuth::default(),
+        &mut app,
+        admin_user.clone(),
+        "query_auth",
+        &[],
+    )
+    .unwrap();
+
+    query_auth::ExecuteMsg::SetViewingKey {
+        key: viewing_key.clone(),
+        padding: None,
+    }
+    .test_exec(&query_contract, &mut app, staking_user.clone(), &[])
+    .unwrap();
+
+    let basic_staking = basic_staking::InstantiateMsg {
+        admin_auth: admin_contract.into(),
+        query_auth: query_contract.into(),
+        airdrop: None,
+        stake_token: token.clone().into(),
+        unbond_period: Uint128::zero(),
+        max_user_pools: Uint128::one(),
+        viewing_key: viewing_key.clone(),
+    }
+    .test_init(
+        BasicStaking::default(),
+        &mut app,
+        admin_user.clone(),
+        "basic_staking",
+        &[],
+    )
+    .unwrap();
+
+    // Stake at t=10
+    app.set_block(BlockInfo {
+        height: 2,
+        time: Timestamp::from_seconds(10),
+        chain_id: "chain_id".to_string(),
+    });
+
+    snip20::ExecuteMsg::Send {
+        recipient: basic_staking.address.to_string().clone(),
+        recipient_code_hash: None,
+        amount: stake_amount,
+        msg: Some(
+            to_binary(&basic_staking::Action::Stake {
+                compound: None,
+                airdrop_task: None,
+                lock: None,
+            })
+            .unwrap(),
+        ),
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None,
+    }
+    .test_exec(&token, &mut app, staking_user.clone(), &[])
+    .unwrap();
+
+    // Unbond at t=20
+    app.set_block(BlockInfo {
+        height: 3,
+        time: Timestamp::from_seconds(20),
+        chain_id: "chain_id".to_string(),
+    });
+
+    basic_staking::ExecuteMsg::Unbond {
+        amount: unbond_amount,
+        compound: None,
+        instant: None,
+        padding: None,
+    }
+    .test_exec(&basic_staking, &mut app, staking_user.clone(), &[])
+    .unwrap();
+
+    // The current block's stake can still change
+    assert!(
+        (basic_staking::QueryMsg::TotalStakedAt {
+            time: Uint128::new(20),
+        })
+        .test_query::<basic_staking::QueryAnswer>(&basic_staking, &app)
+        .is_err(),
+        "Queried current stake"
+    );
+
+    app.set_block(BlockInfo {
+        height: 4,
+        time: Timestamp::from_seconds(21),
+        chain_id: "chain_id".to_string(),
+    });
+
+    for (time, expected) in vec![
+        (0, Uint128::zero()),
+        (9, Uint128::zero()),
+        (10, stake_amount),
+        (15, stake_amount),
+        (20, stake_amount - unbond_amount),
+    ] {
+        match (basic_staking::QueryMsg::StakedAt {
+            auth: basic_staking::Auth::ViewingKey {
+                key: viewing_key.clone(),
+                address: staking_user.clone().into(),
+            },
+            time: Uint128::new(time),
+        })
+        .test_query(&basic_staking, &app)
+        .unwrap()
+        {
+            basic_staking::QueryAnswer::StakedAt { amount } => {
+                assert_eq!(amount, expected, "Staked at {}", time);
+            }
+            _ => {
+                panic!("Staked at query failed");
+            }
+        };
+
+        match (basic_staking::QueryMsg::TotalStakedAt {
+            time: Uint128::new(time),
+        })
+        .test_query(&basic_staking, &app)
+        .unwrap()
+        {
+            basic_staking::QueryAnswer::TotalStakedAt { amount } => {
+                assert_eq!(amount, expected, "Total staked at {}", time);
+            }
+            _ => {
+                panic!("Total staked at query failed");
+            }
+        };
+    }
+
+    // Future balances can't be queried
+    assert!(
+        (basic_staking::QueryMsg::TotalStakedAt {
+            time: Uint128::new(22),
+        })
+        .test_query::<basic_staking::QueryAnswer>(&basic_staking, &app)
+        .is_err(),
+        "Queried future stake"
+    );
+}
@@ -156,6 +156,8 @@ fn transfer_stake(stake_amount: Uint128, transfer_amount: Uint128) {
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, reward_user.clone(), &[])
@@ -170,10 +172,13 @@ fn transfer_stake(stake_amount: Uint128, transfer_amount: Uint128) {
             to_binary(&basic_staking::Action::Stake {
                 compound: None,
                 airdrop_task: None,
+                lock: None,
             })
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, staking_user.clone(), &[])
@@ -188,10 +193,13 @@ fn transfer_stake(stake_amount: Uint128, transfer_amount: Uint128) {
             to_binary(&basic_staking::Action::Stake {
                 compound: None,
                 airdrop_task: None,
+                lock: None,
             })
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, transfer_user.clone(), &[])
@@ -139,10 +139,13 @@ fn transfer_stake(stake_amount: Uint128, transfer_amount: Uint128) {
             to_binary(&basic_staking::Action::Stake {
                 compound: None,
                 airdrop_task: None,
+                lock: None,
             })
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, receiving_user.clone(), &[])
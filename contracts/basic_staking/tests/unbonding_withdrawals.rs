@@ -130,10 +130,13 @@ fn unbonding_withdrawals(
             to_binary(&basic_staking::Action::Stake {
                 compound: None,
                 airdrop_task: None,
+                lock: None,
             })
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, staking_user.clone(), &[])
@@ -155,6 +158,7 @@ fn unbonding_withdrawals(
         basic_staking::ExecuteMsg::Unbond {
             amount: unbond_amount.clone(),
             compound: None,
+            instant: None,
             padding: None,
         }
         .test_exec(&basic_staking, &mut app, staking_user.clone(), &[])
@@ -148,6 +148,8 @@ fn unstake_softlock_bug(stake_amount: Uint128, unbond_period: Uint128, reward_am
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, reward_user.clone(), &[])
@@ -175,10 +177,13 @@ fn unstake_softlock_bug(stake_amount: Uint128, unbond_period: Uint128, reward_am
             to_binary(&basic_staking::Action::Stake {
                 compound: None,
                 airdrop_task: None,
+                lock: None,
             })
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, staking_user.clone(), &[])
@@ -225,6 +230,7 @@ fn unstake_softlock_bug(stake_amount: Uint128, unbond_period: Uint128, reward_am
     basic_staking::ExecuteMsg::Unbond {
         amount: stake_amount,
         compound: None,
+        instant: None,
         padding: None,
     }
     .test_exec(&basic_staking, &mut app, staking_user.clone(), &[])
@@ -276,10 +282,13 @@ fn unstake_softlock_bug(stake_amount: Uint128, unbond_period: Uint128, reward_am
             to_binary(&basic_staking::Action::Stake {
                 compound: None,
                 airdrop_task: None,
+                lock: None,
             })
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, staking_user.clone(), &[])
@@ -0,0 +1,365 @@
+use shade_protocol::{
+    admin::helpers::{validate_admin, AdminPermissions},
+    c_std::{
+        to_binary,
+        Addr,
+        Binary,
+        CosmosMsg,
+        Deps,
+        DepsMut,
+        Env,
+        MessageInfo,
+        Response,
+        StdError,
+        StdResult,
+        Uint128,
+    },
+    dao::{
+        adapter,
+        basket::{BasketToken, Config, ExecuteAnswer},
+    },
+    dex::dex,
+    snip20::helpers::{register_receive, send_msg, set_viewing_key_msg},
+    utils::{asset::Contract, generic_response::ResponseStatus},
+};
+
+use crate::{query, storage::*};
+
+pub static ONE_HUNDRED_PERCENT: Uint128 = Uint128::new(10u128.pow(18));
+
+pub fn receive(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    _sender: Addr,
+    _from: Addr,
+    _amount: Uint128,
+    _msg: Option<Binary>,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // deposits and swap returns are held until the next update
+    if info.sender != config.asset.address
+        && !config.basket.iter().any(|b| b.token.address == info.sender)
+    {
+        return Err(StdError::generic_err("Unrecognized token"));
+    }
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::Receive {
+            status: ResponseStatus::Success,
+        })?),
+    )
+}
+
+pub fn try_update_config(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    config: Config,
+) -> StdResult<Response> {
+    let cur_config = CONFIG.load(deps.storage)?;
+
+    validate_admin(
+        &deps.querier,
+        AdminPermissions::BasketAdmin,
+        &info.sender,
+        &cur_config.admin_auth,
+    )?;
+
+    validate_config(&config)?;
+
+    // register with any tokens we haven't seen yet
+    let key = VIEWING_KEY.load(deps.storage)?;
+    let mut messages = vec![];
+
+    for token in std::iter::once(&config.asset).chain(config.basket.iter().map(|b| &b.token)) {
+        if token.address == cur_config.asset.address
+            || cur_config
+                .basket
+                .iter()
+                .any(|b| b.token.address == token.address)
+        {
+            continue;
+        }
+        messages.push(set_viewing_key_msg(key.clone(), None, token)?);
+        messages.push(register_receive(env.contract.code_hash.clone(), None, token)?);
+    }
+
+    // Save new info
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .set_data(to_binary(&ExecuteAnswer::UpdateConfig {
+            status: ResponseStatus::Success,
+        })?))
+}
+
+/* Swap the basket back toward its target weights
+ */
+pub fn update(deps: DepsMut, _env: Env, info: MessageInfo, asset: Addr) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if validate_admin(
+        &deps.querier,
+        AdminPermissions::BasketAdmin,
+        &info.sender,
+        &config.admin_auth,
+    )
+    .is_err()
+        && config.owner != info.sender
+    {
+        return Err(StdError::generic_err("Unauthorized"));
+    }
+
+    if asset != config.asset.address {
+        return Err(StdError::generic_err("Unrecognized Asset"));
+    }
+
+    let messages = rebalance(deps.as_ref(), &config, UNBONDING.load(deps.storage)?)?;
+
+    Ok(Response::new().add_messages(messages).set_data(to_binary(
+        &adapter::ExecuteAnswer::Update {
+            status: ResponseStatus::Success,
+        },
+    )?))
+}
+
+pub fn unbond(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    asset: Addr,
+    amount: Uint128,
+) -> StdResult<Response> {
+    /* Sells enough of the basket to cover the unbonding,
+     * the proceeds are held until claimed
+     */
+    let config = CONFIG.load(deps.storage)?;
+
+    if validate_admin(
+        &deps.querier,
+        AdminPermissions::BasketAdmin,
+        &info.sender,
+        &config.admin_auth,
+    )
+    .is_err()
+        && config.owner != info.sender
+    {
+        return Err(StdError::generic_err("Unauthorized"));
+    }
+
+    if asset != config.asset.address {
+        return Err(StdError::generic_err("Unrecognized Asset"));
+    }
+
+    let unbondable = match query::unbondable(deps.as_ref(), asset)? {
+        adapter::QueryAnswer::Unbondable { amount } => amount,
+        _ => return Err(StdError::generic_err("Failed to query unbondable")),
+    };
+
+    if amount > unbondable {
+        return Err(StdError::generic_err(format!(
+            "Unbond amount {} greater than unbondable {}",
+            amount, unbondable
+        )));
+    }
+
+    let unbonding = UNBONDING.load(deps.storage)? + amount;
+    UNBONDING.save(deps.storage, &unbonding)?;
+
+    let messages = rebalance(deps.as_ref(), &config, unbonding)?;
+
+    Ok(Response::new().add_messages(messages).set_data(to_binary(
+        &adapter::ExecuteAnswer::Unbond {
+            status: ResponseStatus::Success,
+            amount,
+        },
+    )?))
+}
+
+/* Sends unbonded funds to the owner
+ */
+pub fn claim(deps: DepsMut, _env: Env, _info: MessageInfo, asset: Addr) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if asset != config.asset.address {
+        return Err(StdError::generic_err("Unrecognized Asset"));
+    }
+
+    let unbonding = UNBONDING.load(deps.storage)?;
+    let claimable = query::cash(deps.as_ref(), &config)?.min(unbonding);
+
+    let mut messages = vec![];
+
+    if !claimable.is_zero() {
+        messages.push(send_msg(
+            config.owner.clone(),
+            claimable,
+            None,
+            None,
+            None,
+            &config.asset,
+        )?);
+        UNBONDING.save(deps.storage, &(unbonding - claimable))?;
+    }
+
+    Ok(Response::new().add_messages(messages).set_data(to_binary(
+        &adapter::ExecuteAnswer::Claim {
+            status: ResponseStatus::Success,
+            amount: claimable,
+        },
+    )?))
+}
+
+/* Sells tokens above their target, then buys those below with the proceeds.
+ * A token has to drift past the tolerance before it's traded,
+ * unless there isn't enough cash to cover unbondings
+ */
+pub fn rebalance(deps: Deps, config: &Config, unbonding: Uint128) -> StdResult<Vec<CosmosMsg>> {
+    let (cash, positions) = query::positions(deps, config)?;
+
+    let total = cash + positions.iter().map(|p| p.value).sum::<Uint128>();
+    let investable = total.saturating_sub(unbonding);
+    let tolerance = investable.multiply_ratio(config.tolerance, ONE_HUNDRED_PERCENT);
+    let short = cash < unbonding;
+
+    let mut messages = vec![];
+    // swap returns are counted at their minimum
+    let mut proceeds = Uint128::zero();
+
+    for p in &positions {
+        let target = query::target_value(config, &p.token, investable);
+
+        if p.value <= target || (!short && p.value - target <= tolerance) {
+            continue;
+        }
+
+        let amount = match target.is_zero() {
+            true => p.amount,
+            false => p.amount.multiply_ratio(p.value - target, p.value),
+        };
+
+        if let Some(min_return) = min_return(
+            deps,
+            config,
+            &p.token,
+            &p.token.token,
+            amount,
+            p.to_asset(amount),
+        )? {
+            messages.push(dex::swap_msg(
+                &p.token.dex,
+                &p.token.pair,
+                &p.token.token,
+                amount,
+                Some(min_return),
+            )?);
+            proceeds += min_return;
+        }
+    }
+
+    let mut budget = (cash + proceeds).saturating_sub(unbonding);
+
+    for p in &positions {
+        if budget.is_zero() {
+            break;
+        }
+
+        let target = query::target_value(config, &p.token, investable);
+
+        if p.value >= target || target - p.value <= tolerance {
+            continue;
+        }
+
+        let amount = (target - p.value).min(budget);
+
+        if let Some(min_return) = min_return(
+            deps,
+            config,
+            &p.token,
+            &config.asset,
+            amount,
+            p.to_token(amount),
+        )? {
+            messages.push(dex::swap_msg(
+                &p.token.dex,
+                &p.token.pair,
+                &config.asset,
+                amount,
+                Some(min_return),
+            )?);
+            budget -= amount;
+        }
+    }
+
+    Ok(messages)
+}
+
+/* Least a swap can return within max slippage of the oracle price,
+ * None if the pair can't currently do that.
+ * The pair's own reserves are never trusted for the floor,
+ * as they can be moved in the same block as the swap
+ */
+fn min_return(
+    deps: Deps,
+    config: &Config,
+    token: &BasketToken,
+    offer: &Contract,
+    amount: Uint128,
+    expected: Uint128,
+) -> StdResult<Option<Uint128>> {
+    if amount.is_zero() || expected.is_zero() {
+        return Ok(None);
+    }
+
+    let min_return = expected - expected.multiply_ratio(config.max_slippage, ONE_HUNDRED_PERCENT);
+    let simulated = dex::simulate_swap(&deps, &token.dex, &token.pair, offer, amount)?;
+
+    if simulated.is_zero() || simulated < min_return {
+        return Ok(None);
+    }
+
+    Ok(Some(min_return))
+}
+
+pub fn validate_config(config: &Config) -> StdResult<()> {
+    if config.basket.is_empty() {
+        return Err(StdError::generic_err("Empty basket"));
+    }
+
+    if config.tolerance >= ONE_HUNDRED_PERCENT {
+        return Err(StdError::generic_err("Tolerance must be less than 100%"));
+    }
+
+    if config.max_slippage >= ONE_HUNDRED_PERCENT {
+        return Err(StdError::generic_err("Max slippage must be less than 100%"));
+    }
+
+    if config.basket.iter().map(|b| b.weight).sum::<Uint128>().is_zero() {
+        return Err(StdError::generic_err("Basket weights cannot all be zero"));
+    }
+
+    for (i, b) in config.basket.iter().enumerate() {
+        if let dex::Dex::Mint = b.dex {
+            return Err(StdError::generic_err(format!(
+                "{:?} cannot trade basket token {}",
+                b.dex, b.token.address
+            )));
+        }
+
+        if b.token.address == config.asset.address
+            || config.basket[..i]
+                .iter()
+                .any(|o| o.token.address == b.token.address)
+        {
+            return Err(StdError::generic_err(format!(
+                "Duplicate basket token {}",
+                b.token.address
+            )));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,4 @@
+pub mod contract;
+pub mod execute;
+pub mod query;
+pub mod storage;
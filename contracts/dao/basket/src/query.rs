@@ -0,0 +1,222 @@
+use shade_protocol::{
+    c_std::{Addr, Deps, StdError, StdResult, Uint128},
+    dao::{
+        adapter,
+        basket::{BasketToken, Config, Holding, QueryAnswer},
+    },
+    oracles::{band::ReferenceData, oracle},
+    snip20::helpers::{balance_query, token_info},
+    utils::{asset::Contract, Query},
+};
+
+use crate::storage::*;
+
+/* Oracle price of a whole token and its smallest unit */
+#[derive(Clone)]
+pub struct Price {
+    pub rate: Uint128,
+    pub precision: Uint128,
+}
+
+pub struct Position {
+    pub token: BasketToken,
+    pub amount: Uint128,
+    // Oracle value in the deposit asset
+    pub value: Uint128,
+    pub price: Price,
+    pub asset_price: Price,
+}
+
+impl Position {
+    /* Deposit asset worth amount of the token */
+    pub fn to_asset(&self, amount: Uint128) -> Uint128 {
+        amount
+            .multiply_ratio(self.price.rate, self.asset_price.rate)
+            .multiply_ratio(self.asset_price.precision, self.price.precision)
+    }
+
+    /* Token worth amount of the deposit asset */
+    pub fn to_token(&self, amount: Uint128) -> Uint128 {
+        amount
+            .multiply_ratio(self.asset_price.rate, self.price.rate)
+            .multiply_ratio(self.price.precision, self.asset_price.precision)
+    }
+}
+
+pub fn config(deps: Deps) -> StdResult<QueryAnswer> {
+    Ok(QueryAnswer::Config {
+        config: CONFIG.load(deps.storage)?,
+    })
+}
+
+/* Deposit asset held, including what is set aside for unbondings */
+pub fn cash(deps: Deps, config: &Config) -> StdResult<Uint128> {
+    balance_query(
+        &deps.querier,
+        SELF_ADDRESS.load(deps.storage)?,
+        VIEWING_KEY.load(deps.storage)?,
+        &config.asset,
+    )
+}
+
+pub fn price(deps: Deps, config: &Config, token: &Contract, symbol: &str) -> StdResult<Price> {
+    let rate = oracle::QueryMsg::Price {
+        symbol: symbol.to_string(),
+    }
+    .query::<ReferenceData>(&deps.querier, &config.oracle)?
+    .rate;
+
+    if rate.is_zero() {
+        return Err(StdError::generic_err(format!("No oracle price for {}", symbol)));
+    }
+
+    let decimals = token_info(&deps.querier, token)?.decimals;
+
+    Ok(Price {
+        rate,
+        precision: Uint128::new(10u128.pow(decimals.into())),
+    })
+}
+
+/* Deposit asset held and the position in each basket token */
+pub fn positions(deps: Deps, config: &Config) -> StdResult<(Uint128, Vec<Position>)> {
+    let self_address = SELF_ADDRESS.load(deps.storage)?;
+    let key = VIEWING_KEY.load(deps.storage)?;
+
+    let cash = cash(deps, config)?;
+    let asset_price = price(deps, config, &config.asset, &config.asset_symbol)?;
+
+    let mut positions = vec![];
+
+    for token in &config.basket {
+        let amount = balance_query(&deps.querier, self_address.clone(), key.clone(), &token.token)?;
+
+        let mut position = Position {
+            token: token.clone(),
+            amount,
+            value: Uint128::zero(),
+            price: price(deps, config, &token.token, &token.symbol)?,
+            asset_price: asset_price.clone(),
+        };
+        position.value = position.to_asset(amount);
+
+        positions.push(position);
+    }
+
+    Ok((cash, positions))
+}
+
+/* Value of each basket token once unbondings are set aside */
+pub fn target_value(config: &Config, token: &BasketToken, investable: Uint128) -> Uint128 {
+    let total_weight: Uint128 = config.basket.iter().map(|b| b.weight).sum();
+    investable.multiply_ratio(token.weight, total_weight)
+}
+
+pub fn holdings(deps: Deps) -> StdResult<QueryAnswer> {
+    let config = CONFIG.load(deps.storage)?;
+    let unbonding = UNBONDING.load(deps.storage)?;
+    let (cash, positions) = positions(deps, &config)?;
+
+    let total = cash + positions.iter().map(|p| p.value).sum::<Uint128>();
+    let investable = total.saturating_sub(unbonding);
+
+    Ok(QueryAnswer::Holdings {
+        cash,
+        unbonding,
+        holdings: positions
+            .into_iter()
+            .map(|p| Holding {
+                target_value: target_value(&config, &p.token, investable),
+                token: p.token.token.address,
+                amount: p.amount,
+                value: p.value,
+            })
+            .collect(),
+    })
+}
+
+pub fn balance(deps: Deps, asset: Addr) -> StdResult<adapter::QueryAnswer> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if asset != config.asset.address {
+        return Err(StdError::generic_err(format!(
+            "Unrecognized Asset {}",
+            asset
+        )));
+    }
+
+    let (cash, positions) = positions(deps, &config)?;
+
+    Ok(adapter::QueryAnswer::Balance {
+        amount: cash + positions.iter().map(|p| p.value).sum::<Uint128>(),
+    })
+}
+
+pub fn claimable(deps: Deps, asset: Addr) -> StdResult<adapter::QueryAnswer> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if asset != config.asset.address {
+        return Err(StdError::generic_err(format!(
+            "Unrecognized Asset {}",
+            asset
+        )));
+    }
+
+    let cash = cash(deps, &config)?;
+
+    Ok(adapter::QueryAnswer::Claimable {
+        amount: cash.min(UNBONDING.load(deps.storage)?),
+    })
+}
+
+pub fn unbonding(deps: Deps, asset: Addr) -> StdResult<adapter::QueryAnswer> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if asset != config.asset.address {
+        return Err(StdError::generic_err(format!(
+            "Unrecognized Asset {}",
+            asset
+        )));
+    }
+
+    let cash = cash(deps, &config)?;
+
+    Ok(adapter::QueryAnswer::Unbonding {
+        amount: UNBONDING.load(deps.storage)?.saturating_sub(cash),
+    })
+}
+
+pub fn unbondable(deps: Deps, asset: Addr) -> StdResult<adapter::QueryAnswer> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if asset != config.asset.address {
+        return Err(StdError::generic_err(format!(
+            "Unrecognized Asset {}",
+            asset
+        )));
+    }
+
+    let (cash, positions) = positions(deps, &config)?;
+    let total = cash + positions.iter().map(|p| p.value).sum::<Uint128>();
+
+    Ok(adapter::QueryAnswer::Unbondable {
+        amount: total.saturating_sub(UNBONDING.load(deps.storage)?),
+    })
+}
+
+pub fn reserves(deps: Deps, asset: Addr) -> StdResult<adapter::QueryAnswer> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if asset != config.asset.address {
+        return Err(StdError::generic_err(format!(
+            "Unrecognized Asset {}",
+            asset
+        )));
+    }
+
+    let cash = cash(deps, &config)?;
+
+    Ok(adapter::QueryAnswer::Reserves {
+        amount: cash.saturating_sub(UNBONDING.load(deps.storage)?),
+    })
+}
@@ -0,0 +1,390 @@
+use mock_oracle::contract as mock_oracle;
+use mock_sienna_pair::contract as mock_sienna;
+use shade_multi_test::multi::{
+    admin::init_admin_auth,
+    basket::Basket,
+    mock_oracle::MockOracle,
+    mock_sienna::MockSienna,
+    snip20::Snip20,
+};
+use shade_protocol::{
+    c_std::{to_binary, Addr, ContractInfo, Decimal, Uint128},
+    contract_interfaces::{
+        dao::{adapter, basket},
+        dex::dex::Dex,
+        snip20,
+    },
+    multi_test::App,
+    utils::{ExecuteCallback, InstantiateCallback, MultiTestable, Query},
+};
+
+fn init_token(app: &mut App, admin: &Addr, symbol: &str) -> ContractInfo {
+    snip20::InstantiateMsg {
+        name: symbol.into(),
+        admin: Some(admin.to_string()),
+        symbol: symbol.into(),
+        decimals: 6,
+        initial_balances: Some(vec![snip20::InitialBalance {
+            address: admin.to_string(),
+            amount: Uint128::new(100_000),
+        }]),
+        prng_seed: to_binary("").ok().unwrap(),
+        config: None,
+        query_auth: None,
+    }
+    .test_init(Snip20::default(), app, admin.clone(), symbol, &[])
+    .unwrap()
+}
+
+fn init_pair(
+    app: &mut App,
+    admin: &Addr,
+    asset: &ContractInfo,
+    token: &ContractInfo,
+    liquidity: Uint128,
+) -> ContractInfo {
+    let pair = mock_sienna::InstantiateMsg {
+        token_0: asset.clone().into(),
+        token_1: token.clone().into(),
+        viewing_key: "key".into(),
+        commission: Decimal::zero(),
+    }
+    .test_init(
+        MockSienna::default(),
+        app,
+        admin.clone(),
+        &format!("{}_pair", token.address),
+        &[],
+    )
+    .unwrap();
+
+    // seed liquidity
+    for t in [asset, token] {
+        snip20::ExecuteMsg::Transfer {
+            recipient: pair.address.to_string(),
+            amount: liquidity,
+            memo: None,
+            decoys: None,
+            entropy: None,
+            padding: None,
+        }
+        .test_exec(t, app, admin.clone(), &[])
+        .unwrap();
+    }
+
+    pair
+}
+
+fn init_oracle(app: &mut App, admin: &Addr, prices: &[(&str, u128)]) -> ContractInfo {
+    let oracle = mock_oracle::InstantiateMsg {}
+        .test_init(MockOracle::default(), app, admin.clone(), "oracle", &[])
+        .unwrap();
+
+    for (symbol, price) in prices {
+        mock_oracle::ExecuteMsg::MockPrice {
+            symbol: symbol.to_string(),
+            price: Uint128::new(*price),
+        }
+        .test_exec(&oracle, app, admin.clone(), &[])
+        .unwrap();
+    }
+
+    oracle
+}
+
+fn holdings(app: &App, basket: &ContractInfo) -> (Uint128, Uint128, Vec<basket::Holding>) {
+    match (basket::QueryMsg::Holdings {})
+        .test_query(basket, app)
+        .unwrap()
+    {
+        basket::QueryAnswer::Holdings {
+            cash,
+            unbonding,
+            holdings,
+        } => (cash, unbonding, holdings),
+        _ => panic!("Query failed"),
+    }
+}
+
+fn balance(app: &App, token: &ContractInfo, address: &Addr, key: &str) -> Uint128 {
+    match (snip20::QueryMsg::Balance {
+        address: address.to_string(),
+        key: key.into(),
+    })
+    .test_query(token, app)
+    .unwrap()
+    {
+        snip20::QueryAnswer::Balance { amount } => amount,
+        _ => panic!("Query failed"),
+    }
+}
+
+#[test]
+fn rebalance_unbond_claim() {
+    let mut app = App::default();
+
+    let viewing_key = "unguessable".to_string();
+    let admin = Addr::unchecked("admin");
+    let admin_auth = init_admin_auth(&mut app, &admin);
+
+    let asset = init_token(&mut app, &admin, "ASSET");
+    let token_a = init_token(&mut app, &admin, "TOKENA");
+    let token_b = init_token(&mut app, &admin, "TOKENB");
+
+    let liquidity = Uint128::new(10_000);
+    let pair_a = init_pair(&mut app, &admin, &asset, &token_a, liquidity);
+    let pair_b = init_pair(&mut app, &admin, &asset, &token_b, liquidity);
+
+    // Pools are seeded at the oracle price
+    let oracle = init_oracle(
+        &mut app,
+        &admin,
+        &[
+            ("ASSET", 10u128.pow(18)),
+            ("TOKENA", 10u128.pow(18)),
+            ("TOKENB", 10u128.pow(18)),
+        ],
+    );
+
+    let basket = basket::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        owner: admin.to_string(),
+        asset: asset.clone().into(),
+        asset_symbol: "ASSET".into(),
+        oracle: oracle.into(),
+        basket: vec![
+            basket::RawBasketToken {
+                token: token_a.clone().into(),
+                pair: pair_a.into(),
+                dex: Dex::SiennaSwap,
+                symbol: "TOKENA".into(),
+                weight: Uint128::new(1),
+            },
+            basket::RawBasketToken {
+                token: token_b.clone().into(),
+                pair: pair_b.into(),
+                dex: Dex::SiennaSwap,
+                symbol: "TOKENB".into(),
+                weight: Uint128::new(1),
+            },
+        ],
+        tolerance: Uint128::zero(),
+        // any price impact is too much
+        max_slippage: Uint128::zero(),
+        viewing_key: viewing_key.clone(),
+    }
+    .test_init(Basket::default(), &mut app, admin.clone(), "basket", &[])
+    .unwrap();
+
+    snip20::ExecuteMsg::SetViewingKey {
+        key: viewing_key.clone(),
+        padding: None,
+    }
+    .test_exec(&asset, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // Deposit
+    let deposit = Uint128::new(1_000);
+    snip20::ExecuteMsg::Send {
+        recipient: basket.address.to_string(),
+        recipient_code_hash: None,
+        amount: deposit,
+        msg: None,
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None,
+    }
+    .test_exec(&asset, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // Deposits are held until update
+    let (cash, _, holdings_before) = holdings(&app, &basket);
+    assert_eq!(cash, deposit, "Cash after deposit");
+    assert!(holdings_before.iter().all(|h| h.amount.is_zero()));
+
+    // Only the admin or owner can trigger swaps
+    assert!(
+        basket::ExecuteMsg::Adapter(adapter::SubExecuteMsg::Update {
+            asset: asset.address.to_string(),
+        })
+        .test_exec(&basket, &mut app, Addr::unchecked("random"), &[])
+        .is_err(),
+        "Unauthorized update"
+    );
+
+    basket::ExecuteMsg::Adapter(adapter::SubExecuteMsg::Update {
+        asset: asset.address.to_string(),
+    })
+    .test_exec(&basket, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // Every swap exceeds max slippage
+    let (cash, _, _) = holdings(&app, &basket);
+    assert_eq!(cash, deposit, "Cash after update without slippage");
+
+    // Allow 10% slippage
+    let mut config = match (basket::QueryMsg::Config {})
+        .test_query(&basket, &app)
+        .unwrap()
+    {
+        basket::QueryAnswer::Config { config } => config,
+        _ => panic!("Query failed"),
+    };
+    config.max_slippage = Uint128::new(10u128.pow(17));
+
+    basket::ExecuteMsg::UpdateConfig { config }
+        .test_exec(&basket, &mut app, admin.clone(), &[])
+        .unwrap();
+
+    basket::ExecuteMsg::Adapter(adapter::SubExecuteMsg::Update {
+        asset: asset.address.to_string(),
+    })
+    .test_exec(&basket, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    let (cash, _, basket_holdings) = holdings(&app, &basket);
+    assert_eq!(cash, Uint128::zero(), "Cash after rebalance");
+    assert_eq!(basket_holdings.len(), 2);
+    assert!(!basket_holdings[0].amount.is_zero(), "Token A bought");
+    assert_eq!(
+        basket_holdings[0].amount, basket_holdings[1].amount,
+        "Equal weights"
+    );
+
+    // Unbond sells down the basket
+    let unbond = Uint128::new(300);
+    basket::ExecuteMsg::Adapter(adapter::SubExecuteMsg::Unbond {
+        asset: asset.address.to_string(),
+        amount: unbond,
+    })
+    .test_exec(&basket, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    let (cash, unbonding, after_unbond) = holdings(&app, &basket);
+    assert_eq!(unbonding, unbond, "Unbonding");
+    assert!(!cash.is_zero(), "Sold for unbonding");
+    assert!(after_unbond[0].amount < basket_holdings[0].amount);
+
+    let claimable = match basket::QueryMsg::Adapter(adapter::SubQueryMsg::Claimable {
+        asset: asset.address.to_string(),
+    })
+    .test_query(&basket, &app)
+    .unwrap()
+    {
+        adapter::QueryAnswer::Claimable { amount } => amount,
+        _ => panic!("Query failed"),
+    };
+    assert_eq!(claimable, cash.min(unbond), "Claimable");
+
+    let owner_before = balance(&app, &asset, &admin, &viewing_key);
+
+    basket::ExecuteMsg::Adapter(adapter::SubExecuteMsg::Claim {
+        asset: asset.address.to_string(),
+    })
+    .test_exec(&basket, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    assert_eq!(
+        balance(&app, &asset, &admin, &viewing_key),
+        owner_before + claimable,
+        "Claimed to owner"
+    );
+
+    let (_, unbonding, _) = holdings(&app, &basket);
+    assert_eq!(unbonding, unbond - claimable, "Unbonding after claim");
+}
+
+#[test]
+fn pair_off_oracle_price_skipped() {
+    let mut app = App::default();
+
+    let viewing_key = "unguessable".to_string();
+    let admin = Addr::unchecked("admin");
+    let admin_auth = init_admin_auth(&mut app, &admin);
+
+    let asset = init_token(&mut app, &admin, "ASSET");
+    let token_a = init_token(&mut app, &admin, "TOKENA");
+    let token_b = init_token(&mut app, &admin, "TOKENB");
+
+    let liquidity = Uint128::new(10_000);
+    let pair_a = init_pair(&mut app, &admin, &asset, &token_a, liquidity);
+    let pair_b = init_pair(&mut app, &admin, &asset, &token_b, liquidity);
+
+    // Pair A trades TOKENA at twice its oracle price
+    let oracle = init_oracle(
+        &mut app,
+        &admin,
+        &[
+            ("ASSET", 10u128.pow(18)),
+            ("TOKENA", 5 * 10u128.pow(17)),
+            ("TOKENB", 10u128.pow(18)),
+        ],
+    );
+
+    let basket = basket::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        owner: admin.to_string(),
+        asset: asset.clone().into(),
+        asset_symbol: "ASSET".into(),
+        oracle: oracle.into(),
+        basket: vec![
+            basket::RawBasketToken {
+                token: token_a.clone().into(),
+                pair: pair_a.into(),
+                dex: Dex::SiennaSwap,
+                symbol: "TOKENA".into(),
+                weight: Uint128::new(1),
+            },
+            basket::RawBasketToken {
+                token: token_b.clone().into(),
+                pair: pair_b.into(),
+                dex: Dex::SiennaSwap,
+                symbol: "TOKENB".into(),
+                weight: Uint128::new(1),
+            },
+        ],
+        tolerance: Uint128::zero(),
+        // 10%
+        max_slippage: Uint128::new(10u128.pow(17)),
+        viewing_key: viewing_key.clone(),
+    }
+    .test_init(Basket::default(), &mut app, admin.clone(), "basket", &[])
+    .unwrap();
+
+    let deposit = Uint128::new(1_000);
+    snip20::ExecuteMsg::Send {
+        recipient: basket.address.to_string(),
+        recipient_code_hash: None,
+        amount: deposit,
+        msg: None,
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None,
+    }
+    .test_exec(&asset, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    basket::ExecuteMsg::Adapter(adapter::SubExecuteMsg::Update {
+        asset: asset.address.to_string(),
+    })
+    .test_exec(&basket, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // 500 buys 477 TOKENB, within 10% of the 500 the oracle expects,
+    // but only 477 TOKENA against the 1000 expected
+    let (cash, _, basket_holdings) = holdings(&app, &basket);
+    assert_eq!(cash, Uint128::new(500), "Cash kept for TOKENA");
+    assert_eq!(basket_holdings[0].amount, Uint128::zero(), "TOKENA skipped");
+    assert_eq!(
+        basket_holdings[1].amount,
+        Uint128::new(477),
+        "TOKENB bought"
+    );
+    assert_eq!(
+        basket_holdings[1].value,
+        Uint128::new(477),
+        "Valued at oracle"
+    );
+}
@@ -36,8 +36,13 @@ pub fn instantiate(
         sscrt: msg.sscrt.into_valid(deps.api)?,
         owner: deps.api.addr_validate(msg.owner.as_str())?,
         validator_bounds: msg.validator_bounds,
+        weighted_validators: msg.weighted_validators,
+        unbonding_period: msg.unbonding_period,
     };
 
+    execute::validate_unbonding_period(&config)?;
+    execute::validate_weighted_validators(deps.as_ref(), &config)?;
+
     CONFIG.save(deps.storage, &config)?;
 
     SELF_ADDRESS.save(deps.storage, &env.contract.address)?;
@@ -85,11 +90,16 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
 }
 
 #[entry_point]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&query::config(deps)?),
         QueryMsg::Delegations {} => to_binary(&query::delegations(deps)?),
         QueryMsg::Rewards {} => to_binary(&query::rewards(deps)?),
+        QueryMsg::ValidatorDistribution {} => to_binary(&query::validator_distribution(deps)?),
+        QueryMsg::Redelegations {} => {
+            to_binary(&query::redelegations(deps, env.block.time.seconds())?)
+        }
+        QueryMsg::PendingUnbondings {} => to_binary(&query::pending_unbondings(deps)?),
         QueryMsg::Adapter(adapter) => match adapter {
             adapter::SubQueryMsg::Balance { asset } => {
                 let asset = deps.api.addr_validate(&asset)?;
@@ -25,7 +25,15 @@ use shade_protocol::snip20::helpers::redeem_msg;
 use shade_protocol::{
     dao::{
         adapter,
-        scrt_staking::{Config, ExecuteAnswer, ValidatorBounds},
+        scrt_staking::{
+            Config,
+            ExecuteAnswer,
+            PendingUnbonding,
+            Redelegation,
+            RedelegationReason,
+            ValidatorBounds,
+            WeightedValidators,
+        },
     },
     utils::{
         asset::{scrt_balance, Contract},
@@ -36,9 +44,19 @@ use shade_protocol::{
 
 use crate::{
     query,
-    storage::{CONFIG, SELF_ADDRESS, UNBONDING},
+    storage::{
+        CONFIG,
+        MAX_REDELEGATION_ENTRIES,
+        PENDING_UNBONDINGS,
+        REDELEGATIONS,
+        SELF_ADDRESS,
+        UNBONDING,
+        UNBONDING_ID,
+    },
 };
 
+pub static ONE_HUNDRED_PERCENT: Uint128 = Uint128::new(10u128.pow(18));
+
 pub fn receive(
     deps: DepsMut,
     env: Env,
@@ -56,22 +74,22 @@ pub fn receive(
         return Err(StdError::generic_err("Only accepts sSCRT"));
     }
 
-    let validator = choose_validator(deps, env.block.time.seconds())?;
+    let mut messages = vec![redeem_msg(amount, None, None, &config.sscrt)?];
+    let (mut delegate_msgs, validators) =
+        delegate(deps.as_ref(), &config, env.block.time.seconds(), amount)?;
+    messages.append(&mut delegate_msgs);
+
+    let validator = match validators.first() {
+        Some(validator) => validator.clone(),
+        None => return Err(StdError::generic_err("Nothing to delegate")),
+    };
 
     Ok(Response::new()
-        .add_messages(vec![
-            redeem_msg(amount, None, None, &config.sscrt)?,
-            CosmosMsg::Staking(StakingMsg::Delegate {
-                validator: validator.address.clone(),
-                amount: Coin {
-                    amount,
-                    denom: "uscrt".to_string(),
-                },
-            }),
-        ])
+        .add_messages(messages)
         .set_data(to_binary(&ExecuteAnswer::Receive {
             status: ResponseStatus::Success,
             validator,
+            validators,
         })?))
 }
 
@@ -90,6 +108,9 @@ pub fn try_update_config(
         &cur_config.admin_auth,
     )?;
 
+    validate_unbonding_period(&config)?;
+    validate_weighted_validators(deps.as_ref(), &config)?;
+
     // Save new info
     CONFIG.save(deps.storage, &config)?;
 
@@ -131,15 +152,26 @@ pub fn update(deps: DepsMut, env: Env, _info: MessageInfo, asset: Addr) -> StdRe
         stake_amount = Uint128::zero();
     }
 
+    // Move delegations off bad validators and back toward targets
+    let now = env.block.time.seconds();
+    let mut history = recent_redelegations(deps.as_ref(), &config, now)?;
+    let redelegations = maintain(deps.as_ref(), &config, &history, now)?;
+    for redelegation in &redelegations {
+        messages.push(redelegate_msg(redelegation));
+    }
+    history.extend(redelegations);
+    REDELEGATIONS.save(deps.storage, &history)?;
+
     if stake_amount > Uint128::zero() {
-        let validator = choose_validator(deps, env.block.time.seconds())?;
-        messages.push(CosmosMsg::Staking(StakingMsg::Delegate {
-            validator: validator.address.clone(),
-            amount: Coin {
-                amount: stake_amount,
-                denom: "uscrt".to_string(),
-            },
-        }));
+        messages.append(
+            &mut delegate(
+                deps.as_ref(),
+                &config,
+                env.block.time.seconds(),
+                stake_amount,
+            )?
+            .0,
+        );
     }
 
     Ok(Response::new().add_messages(messages).set_data(to_binary(
@@ -151,7 +183,7 @@ pub fn update(deps: DepsMut, env: Env, _info: MessageInfo, asset: Addr) -> StdRe
 
 pub fn unbond(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     asset: Addr,
     amount: Uint128,
@@ -248,6 +280,9 @@ pub fn unbond(
 
     UNBONDING.save(deps.storage, &total_unbonding)?;
 
+    // (validator, amount) of each undelegation made
+    let mut pending = vec![];
+
     while !total_unbonding.is_zero() {
         // Unbond from largest validator first
         let max_delegation = delegations.iter().max_by_key(|d| {
@@ -278,6 +313,10 @@ pub fn unbond(
                         validator: delegation.validator.clone(),
                         amount: delegation.amount.clone(),
                     }));
+                    pending.push((
+                        delegation.validator.clone(),
+                        delegation.amount.amount.clone(),
+                    ));
                     unbonding = unbonding - delegation.amount.amount.clone();
                     undelegated.push(delegation.validator.clone());
                 } else if !delegation.amount.amount.clone().is_zero() {
@@ -288,6 +327,7 @@ pub fn unbond(
                             amount: unbonding,
                         },
                     }));
+                    pending.push((delegation.validator.clone(), unbonding));
                     unbonding = Uint128::zero();
                     undelegated.push(delegation.validator.clone());
                 }
@@ -295,6 +335,26 @@ pub fn unbond(
         }
     }
 
+    if !pending.is_empty() {
+        let mut unbondings = PENDING_UNBONDINGS
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        let mut id = UNBONDING_ID.may_load(deps.storage)?.unwrap_or_default();
+
+        for (validator, amount) in pending {
+            unbondings.push(PendingUnbonding {
+                id,
+                validator,
+                amount,
+                maturity: env.block.time.seconds() + config.unbonding_period,
+            });
+            id += Uint128::one();
+        }
+
+        PENDING_UNBONDINGS.save(deps.storage, &unbondings)?;
+        UNBONDING_ID.save(deps.storage, &id)?;
+    }
+
     Ok(Response::new().add_messages(messages).set_data(to_binary(
         &adapter::ExecuteAnswer::Unbond {
             status: ResponseStatus::Success,
@@ -385,6 +445,25 @@ pub fn claim(deps: DepsMut, _env: Env, _info: MessageInfo, asset: Addr) -> StdRe
         //assert!(false, "u - claim_amount: {} - {}", unbond_amount, claim_amount);
         let u = UNBONDING.load(deps.storage)?;
         UNBONDING.save(deps.storage, &(u - claim_amount))?;
+
+        // settle tracked unbondings earliest maturity first
+        let mut unbondings = PENDING_UNBONDINGS
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        unbondings.sort_by_key(|u| (u.maturity, u.id));
+
+        let mut remaining = claim_amount;
+        for unbonding in unbondings.iter_mut() {
+            if remaining.is_zero() {
+                break;
+            }
+            let settled = remaining.min(unbonding.amount);
+            unbonding.amount -= settled;
+            remaining -= settled;
+        }
+        unbondings.retain(|u| !u.amount.is_zero());
+
+        PENDING_UNBONDINGS.save(deps.storage, &unbondings)?;
     }
 
     Ok(Response::new().add_messages(messages).set_data(to_binary(
@@ -395,21 +474,309 @@ pub fn claim(deps: DepsMut, _env: Env, _info: MessageInfo, asset: Addr) -> StdRe
     )?))
 }
 
-pub fn choose_validator(deps: DepsMut, seed: u64) -> StdResult<Validator> {
-    let mut validators = deps.querier.query_all_validators()?;
+/* Delegate to a single random validator,
+ * or split across the weighted validators if configured
+ */
+pub fn delegate(
+    deps: Deps,
+    config: &Config,
+    seed: u64,
+    amount: Uint128,
+) -> StdResult<(Vec<CosmosMsg>, Vec<Validator>)> {
+    let weighted = match &config.weighted_validators {
+        Some(weighted) => weighted,
+        None => {
+            let validator = choose_validator(deps, seed)?;
+            return Ok((
+                vec![delegate_msg(validator.address.clone(), amount)],
+                vec![validator],
+            ));
+        }
+    };
+
+    let candidates = validators_inbounds(deps, config)?;
+    let weighted = weights_inbounds(&candidates, weighted);
+
+    if weighted.validators.is_empty() {
+        return Err(StdError::generic_err("No weighted validators within bounds"));
+    }
 
-    // filter down to viable candidates
-    if let Some(bounds) = (CONFIG.load(deps.storage)?).validator_bounds {
-        let mut candidates = vec![];
+    let total_weight: Uint128 = weighted.validators.iter().map(|v| v.weight).sum();
+    let mut messages = vec![];
+    let mut validators = vec![];
+    let mut remaining = amount;
+
+    for (i, v) in weighted.validators.iter().enumerate() {
+        // last validator takes the rounding dust
+        let share = if i == weighted.validators.len() - 1 {
+            remaining
+        } else {
+            amount.multiply_ratio(v.weight, total_weight)
+        };
 
-        for validator in validators {
-            if is_validator_inbounds(&validator, &bounds) {
-                candidates.push(validator);
+        if share.is_zero() {
+            continue;
+        }
+        remaining -= share;
+
+        if let Some(validator) = candidates.iter().find(|c| c.address == v.validator) {
+            validators.push(validator.clone());
+        }
+        messages.push(delegate_msg(v.validator.clone(), share));
+    }
+
+    Ok((messages, validators))
+}
+
+pub fn validate_unbonding_period(config: &Config) -> StdResult<()> {
+    if config.unbonding_period == 0 {
+        return Err(StdError::generic_err("Unbonding period must be non-zero"));
+    }
+    Ok(())
+}
+
+/* Redelegations still within the unbonding period */
+pub fn recent_redelegations(deps: Deps, config: &Config, now: u64) -> StdResult<Vec<Redelegation>> {
+    Ok(REDELEGATIONS
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|r| r.timestamp + config.unbonding_period > now)
+        .collect())
+}
+
+/* The chain rejects redelegating funds that are themselves still being redelegated,
+ * and caps the in progress redelegations per src/dst pair
+ */
+pub fn can_redelegate(recent: &[Redelegation], src: &str, dst: &str) -> bool {
+    !recent.iter().any(|r| r.dst_validator == src)
+        && recent
+            .iter()
+            .filter(|r| r.src_validator == src && r.dst_validator == dst)
+            .count()
+            < MAX_REDELEGATION_ENTRIES
+}
+
+/* Move delegations off validators that are jailed or no longer within bounds,
+ * then (weighted only) back toward targets,
+ * skipping anything the chain would reject given the recent redelegations
+ */
+pub fn maintain(
+    deps: Deps,
+    config: &Config,
+    recent: &[Redelegation],
+    now: u64,
+) -> StdResult<Vec<Redelegation>> {
+    let candidates = validators_inbounds(deps, config)?;
+
+    if let Some(weighted) = &config.weighted_validators {
+        let weighted = weights_inbounds(&candidates, weighted);
+        if weighted.validators.is_empty() {
+            return Ok(vec![]);
+        }
+        return rebalance(deps, &weighted, &candidates, recent, now);
+    }
+
+    let mut redelegations = vec![];
+
+    if candidates.is_empty() {
+        return Ok(redelegations);
+    }
+
+    let dst = &candidates[(now % candidates.len() as u64) as usize];
+
+    for d in query::delegations(deps)? {
+        if d.amount.amount.is_zero()
+            || candidates.iter().any(|c| c.address == d.validator)
+            || !can_redelegate(recent, &d.validator, &dst.address)
+        {
+            continue;
+        }
+
+        redelegations.push(Redelegation {
+            src_validator: d.validator,
+            dst_validator: dst.address.clone(),
+            amount: d.amount.amount,
+            reason: RedelegationReason::OutOfBounds,
+            timestamp: now,
+        });
+    }
+
+    Ok(redelegations)
+}
+
+/* Redelegate from validators above their target to those below,
+ * anything on an out of bounds validator is always moved,
+ * otherwise only once one of them has drifted past the tolerance
+ */
+pub fn rebalance(
+    deps: Deps,
+    weighted: &WeightedValidators,
+    candidates: &[Validator],
+    recent: &[Redelegation],
+    now: u64,
+) -> StdResult<Vec<Redelegation>> {
+    let distribution = query::distribution(deps, Some(weighted))?;
+    let total: Uint128 = distribution.iter().map(|d| d.actual).sum();
+    let tolerance = total.multiply_ratio(weighted.tolerance, ONE_HUNDRED_PERCENT);
+
+    let out_of_bounds = |validator: &String| !candidates.iter().any(|c| c.address == *validator);
+
+    if total.is_zero()
+        || !distribution.iter().any(|d| {
+            (out_of_bounds(&d.validator) && !d.actual.is_zero())
+                || d.actual.saturating_sub(d.target) > tolerance
+                || d.target.saturating_sub(d.actual) > tolerance
+        })
+    {
+        return Ok(vec![]);
+    }
+
+    // drain out of bounds validators first
+    let mut surplus: Vec<(String, Uint128)> = distribution
+        .iter()
+        .filter(|d| d.actual > d.target)
+        .map(|d| (d.validator.clone(), d.actual - d.target))
+        .collect();
+    surplus.sort_by_key(|(validator, _)| !out_of_bounds(validator));
+
+    let mut redelegations = vec![];
+
+    for d in distribution.iter().filter(|d| d.target > d.actual) {
+        let mut needed = d.target - d.actual;
+
+        for (src, available) in surplus.iter_mut() {
+            if needed.is_zero() {
+                break;
+            }
+            if available.is_zero() || !can_redelegate(recent, src, &d.validator) {
+                continue;
+            }
+
+            let amount = needed.min(*available);
+            redelegations.push(Redelegation {
+                src_validator: src.clone(),
+                dst_validator: d.validator.clone(),
+                amount,
+                reason: match out_of_bounds(src) {
+                    true => RedelegationReason::OutOfBounds,
+                    false => RedelegationReason::Rebalance,
+                },
+                timestamp: now,
+            });
+            *available -= amount;
+            needed -= amount;
+        }
+    }
+
+    Ok(redelegations)
+}
+
+pub fn validate_weighted_validators(deps: Deps, config: &Config) -> StdResult<()> {
+    let weighted = match &config.weighted_validators {
+        Some(weighted) => weighted,
+        None => return Ok(()),
+    };
+
+    if weighted.tolerance >= ONE_HUNDRED_PERCENT {
+        return Err(StdError::generic_err("Tolerance must be less than 100%"));
+    }
+
+    if weighted.validators.iter().map(|v| v.weight).sum::<Uint128>().is_zero() {
+        return Err(StdError::generic_err("Validator weights cannot all be zero"));
+    }
+
+    let validators = deps.querier.query_all_validators()?;
+
+    for (i, v) in weighted.validators.iter().enumerate() {
+        if weighted.validators[..i]
+            .iter()
+            .any(|o| o.validator == v.validator)
+        {
+            return Err(StdError::generic_err(format!(
+                "Duplicate validator {}",
+                v.validator
+            )));
+        }
+
+        match validators.iter().position(|o| o.address == v.validator) {
+            Some(position) => {
+                if let Some(bounds) = &config.validator_bounds {
+                    if !is_validator_inbounds(&validators[position], position, bounds) {
+                        return Err(StdError::generic_err(format!(
+                            "{} is not within validator bounds",
+                            v.validator
+                        )));
+                    }
+                }
+            }
+            None => {
+                return Err(StdError::generic_err(format!(
+                    "{} is not a validator",
+                    v.validator
+                )));
             }
         }
+    }
+
+    Ok(())
+}
+
+/* Active validators (jailed validators aren't returned)
+ * filtered down to those within bounds
+ */
+pub fn validators_inbounds(deps: Deps, config: &Config) -> StdResult<Vec<Validator>> {
+    let validators = deps.querier.query_all_validators()?;
 
-        validators = candidates;
+    Ok(match &config.validator_bounds {
+        Some(bounds) => validators
+            .iter()
+            .enumerate()
+            .filter(|(position, validator)| is_validator_inbounds(validator, *position, bounds))
+            .map(|(_, validator)| validator.clone())
+            .collect(),
+        None => validators,
+    })
+}
+
+/* Weighted validators excluding any that are out of bounds */
+pub fn weights_inbounds(candidates: &[Validator], weighted: &WeightedValidators) -> WeightedValidators {
+    WeightedValidators {
+        validators: weighted
+            .validators
+            .iter()
+            .filter(|v| {
+                !v.weight.is_zero() && candidates.iter().any(|c| c.address == v.validator)
+            })
+            .cloned()
+            .collect(),
+        tolerance: weighted.tolerance,
     }
+}
+
+fn delegate_msg(validator: String, amount: Uint128) -> CosmosMsg {
+    CosmosMsg::Staking(StakingMsg::Delegate {
+        validator,
+        amount: Coin {
+            amount,
+            denom: "uscrt".to_string(),
+        },
+    })
+}
+
+fn redelegate_msg(redelegation: &Redelegation) -> CosmosMsg {
+    CosmosMsg::Staking(StakingMsg::Redelegate {
+        src_validator: redelegation.src_validator.clone(),
+        dst_validator: redelegation.dst_validator.clone(),
+        amount: Coin {
+            amount: redelegation.amount,
+            denom: "uscrt".to_string(),
+        },
+    })
+}
+
+pub fn choose_validator(deps: Deps, seed: u64) -> StdResult<Validator> {
+    let validators = validators_inbounds(deps, &CONFIG.load(deps.storage)?)?;
 
     if validators.is_empty() {
         return Err(StdError::generic_err("No validators within bounds"));
@@ -419,6 +786,14 @@ pub fn choose_validator(deps: DepsMut, seed: u64) -> StdResult<Validator> {
     Ok(validators[(seed % validators.len() as u64) as usize].clone())
 }
 
-pub fn is_validator_inbounds(validator: &Validator, bounds: &ValidatorBounds) -> bool {
-    validator.commission <= bounds.max_commission && validator.commission >= bounds.min_commission
+/* position is the validator's index in the active set,
+ * a bottom_position of 0 means no lower bound
+ */
+pub fn is_validator_inbounds(validator: &Validator, position: usize, bounds: &ValidatorBounds) -> bool {
+    let rank = Uint128::new(position as u128 + 1);
+
+    validator.commission <= bounds.max_commission
+        && validator.commission >= bounds.min_commission
+        && rank > bounds.top_position
+        && (bounds.bottom_position.is_zero() || rank <= bounds.bottom_position)
 }
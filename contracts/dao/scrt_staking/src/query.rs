@@ -1,10 +1,16 @@
 use shade_protocol::{
     c_std::{Addr, Delegation, Deps, StdError, StdResult, Uint128},
-    dao::{adapter, scrt_staking::QueryAnswer},
+    dao::{
+        adapter,
+        scrt_staking::{QueryAnswer, ValidatorDistribution, WeightedValidators},
+    },
     utils::asset::scrt_balance,
 };
 
-use crate::storage::*;
+use crate::{
+    execute::{recent_redelegations, validators_inbounds, weights_inbounds, ONE_HUNDRED_PERCENT},
+    storage::*,
+};
 
 pub fn config(deps: Deps) -> StdResult<QueryAnswer> {
     Ok(QueryAnswer::Config {
@@ -17,6 +23,86 @@ pub fn delegations(deps: Deps) -> StdResult<Vec<Delegation>> {
         .query_all_delegations(SELF_ADDRESS.load(deps.storage)?)
 }
 
+pub fn validator_distribution(deps: Deps) -> StdResult<QueryAnswer> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // out of bounds validators are targeted at 0
+    let weighted = match &config.weighted_validators {
+        Some(weighted) => Some(weights_inbounds(
+            &validators_inbounds(deps, &config)?,
+            weighted,
+        )),
+        None => None,
+    };
+
+    Ok(QueryAnswer::ValidatorDistribution {
+        validators: distribution(deps, weighted.as_ref())?,
+    })
+}
+
+pub fn redelegations(deps: Deps, now: u64) -> StdResult<QueryAnswer> {
+    Ok(QueryAnswer::Redelegations {
+        redelegations: recent_redelegations(deps, &CONFIG.load(deps.storage)?, now)?,
+    })
+}
+
+/* Target vs actual delegation per validator,
+ * anything delegated outside the weighted set has a target of 0
+ */
+pub fn distribution(
+    deps: Deps,
+    weighted: Option<&WeightedValidators>,
+) -> StdResult<Vec<ValidatorDistribution>> {
+    let delegations = delegations(deps)?;
+    let total = Uint128::new(
+        delegations
+            .iter()
+            .map(|d| d.amount.amount.u128())
+            .sum::<u128>(),
+    );
+
+    let mut distribution = vec![];
+
+    if let Some(weighted) = weighted.filter(|w| !w.validators.is_empty()) {
+        let total_weight: Uint128 = weighted.validators.iter().map(|v| v.weight).sum();
+
+        for v in &weighted.validators {
+            distribution.push(ValidatorDistribution {
+                validator: v.validator.clone(),
+                target_portion: v.weight.multiply_ratio(ONE_HUNDRED_PERCENT, total_weight),
+                target: total.multiply_ratio(v.weight, total_weight),
+                actual: delegations
+                    .iter()
+                    .find(|d| d.validator == v.validator)
+                    .map(|d| d.amount.amount)
+                    .unwrap_or_default(),
+            });
+        }
+    }
+
+    for d in delegations {
+        if !distribution.iter().any(|v| v.validator == d.validator) {
+            distribution.push(ValidatorDistribution {
+                validator: d.validator,
+                target_portion: Uint128::zero(),
+                target: Uint128::zero(),
+                actual: d.amount.amount,
+            });
+        }
+    }
+
+    Ok(distribution)
+}
+
+pub fn pending_unbondings(deps: Deps) -> StdResult<QueryAnswer> {
+    let mut unbondings = PENDING_UNBONDINGS
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    unbondings.sort_by_key(|u| (u.maturity, u.id));
+
+    Ok(QueryAnswer::PendingUnbondings { unbondings })
+}
+
 pub fn rewards(deps: Deps) -> StdResult<Uint128> {
     let self_address = SELF_ADDRESS.load(deps.storage)?;
 
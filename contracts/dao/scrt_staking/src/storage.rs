@@ -7,3 +7,12 @@ pub const CONFIG: Item<scrt_staking::Config> = Item::new("config");
 pub const SELF_ADDRESS: Item<Addr> = Item::new("self_address");
 pub const VIEWING_KEY: Item<String> = Item::new("viewing_key");
 pub const UNBONDING: Item<Uint128> = Item::new("unbonding");
+// Redelegations made by update, dropped once past the unbonding period
+pub const REDELEGATIONS: Item<Vec<scrt_staking::Redelegation>> = Item::new("redelegations");
+// Chain limit on in progress redelegations per src/dst pair
+pub const MAX_REDELEGATION_ENTRIES: usize = 7;
+
+// Each undelegation made by unbond, settled earliest maturity first on claim
+pub const PENDING_UNBONDINGS: Item<Vec<scrt_staking::PendingUnbonding>> =
+    Item::new("pending_unbondings");
+pub const UNBONDING_ID: Item<Uint128> = Item::new("unbonding_id");
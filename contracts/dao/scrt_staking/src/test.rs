@@ -1,46 +1,195 @@
-/*
-#[cfg(test)]
-pub mod tests {
-    use shade_protocol::c_std::{
-        testing::{
-            mock_dependencies, mock_env, MockStorage, MockApi, MockQuerier
-        },
+use shade_protocol::{
+    c_std::{
+        coin,
+        coins,
+        testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage},
         Addr,
-        coins, from_binary, StdError, Uint128,
-        DepsMut,
-    };
-    use shade_protocol::{
-        treasury::{
-            QueryAnswer, InstantiateMsg, ExecuteMsg,
-            QueryMsg,
-        },
-        asset::Contract,
-    };
+        Decimal,
+        FullDelegation,
+        OwnedDeps,
+        Uint128,
+        Validator,
+    },
+    dao::scrt_staking::{
+        Config,
+        PendingUnbonding,
+        Redelegation,
+        RedelegationReason,
+        ValidatorBounds,
+    },
+    utils::asset::Contract,
+};
+
+use crate::{
+    execute::{claim, maintain},
+    storage::{CONFIG, PENDING_UNBONDINGS, SELF_ADDRESS, UNBONDING},
+};
 
-    use crate::{
-        contract::{
-            init, handle, query,
+fn validator(address: &str, commission: u64) -> Validator {
+    Validator {
+        address: address.to_string(),
+        commission: Decimal::percent(commission),
+        max_commission: Decimal::percent(100),
+        max_change_rate: Decimal::percent(1),
+    }
+}
+
+fn delegation(validator: &str, amount: u128) -> FullDelegation {
+    FullDelegation {
+        delegator: Addr::unchecked("scrt_staking"),
+        validator: validator.to_string(),
+        amount: coin(amount, "uscrt"),
+        can_redelegate: coin(amount, "uscrt"),
+        accumulated_rewards: vec![],
+    }
+}
+
+/* Up to 10% commission, single random validator */
+fn setup(
+    validators: &[Validator],
+    delegations: &[FullDelegation],
+) -> (OwnedDeps<MockStorage, MockApi, MockQuerier>, Config) {
+    let mut deps = mock_dependencies();
+    deps.querier
+        .update_staking("uscrt", validators, delegations);
+
+    let config = Config {
+        admin_auth: Contract {
+            address: Addr::unchecked("admin_auth"),
+            code_hash: "".to_string(),
+        },
+        owner: Addr::unchecked("treasury"),
+        sscrt: Contract {
+            address: Addr::unchecked("sscrt"),
+            code_hash: "".to_string(),
         },
+        validator_bounds: Some(ValidatorBounds {
+            min_commission: Decimal::zero(),
+            max_commission: Decimal::percent(10),
+            top_position: Uint128::zero(),
+            bottom_position: Uint128::zero(),
+        }),
+        weighted_validators: None,
+        unbonding_period: 21 * 24 * 60 * 60,
     };
+    CONFIG.save(deps.as_mut().storage, &config).unwrap();
+    SELF_ADDRESS
+        .save(deps.as_mut().storage, &Addr::unchecked("scrt_staking"))
+        .unwrap();
+
+    (deps, config)
+}
+
+#[test]
+fn commission_increase_redelegates() {
+    let delegations = vec![delegation("validator_b", 100)];
+    let (mut deps, config) = setup(
+        &[validator("validator_a", 5), validator("validator_b", 5)],
+        &delegations,
+    );
+
+    assert!(maintain(deps.as_ref(), &config, &[], 0).unwrap().is_empty());
 
-    fn create_contract(address: &str, code_hash: &str) -> Contract {
-        let env = mock_env(address.to_string(), &[]);
-        return Contract{
-            address: info.sender,
-            code_hash: code_hash.to_string()
+    // validator_b raises its commission past max_commission
+    deps.querier.update_staking(
+        "uscrt",
+        &[validator("validator_a", 5), validator("validator_b", 20)],
+        &delegations,
+    );
+
+    assert_eq!(maintain(deps.as_ref(), &config, &[], 0).unwrap(), vec![
+        Redelegation {
+            src_validator: "validator_b".to_string(),
+            dst_validator: "validator_a".to_string(),
+            amount: Uint128::new(100),
+            reason: RedelegationReason::OutOfBounds,
+            timestamp: 0,
         }
-    }
+    ]);
+}
+
+#[test]
+fn jailed_validator_redelegates() {
+    let delegations = vec![delegation("validator_b", 100)];
+    let (mut deps, config) = setup(
+        &[validator("validator_a", 5), validator("validator_b", 5)],
+        &delegations,
+    );
+
+    // Jailed validators drop out of the active set
+    deps.querier
+        .update_staking("uscrt", &[validator("validator_a", 5)], &delegations);
+
+    assert_eq!(maintain(deps.as_ref(), &config, &[], 0).unwrap(), vec![
+        Redelegation {
+            src_validator: "validator_b".to_string(),
+            dst_validator: "validator_a".to_string(),
+            amount: Uint128::new(100),
+            reason: RedelegationReason::OutOfBounds,
+            timestamp: 0,
+        }
+    ]);
+}
+
+#[test]
+fn recently_redelegated_validator_skipped() {
+    let delegations = vec![delegation("validator_b", 100)];
+    let (deps, config) = setup(&[validator("validator_a", 5)], &delegations);
 
-    fn dummy_init(admin: String, viewing_key: String) -> Extern<MockStorage, MockApi, MockQuerier> {
-        let mut deps = mock_dependencies(20, &[]);
-        let msg = InstantiateMsg {
-            admin: Option::from(Addr(admin.clone())),
-            viewing_key,
-        };
-        let env = mock_env(admin, &coins(1000, "earth"));
-        let _res = init(&mut deps, env, info, msg).unwrap();
+    // validator_b is still receiving a redelegation
+    let recent = vec![Redelegation {
+        src_validator: "validator_c".to_string(),
+        dst_validator: "validator_b".to_string(),
+        amount: Uint128::new(100),
+        reason: RedelegationReason::Rebalance,
+        timestamp: 0,
+    }];
 
-        return deps
+    assert!(maintain(deps.as_ref(), &config, &recent, 1).unwrap().is_empty());
+}
+
+fn pending(id: u128, amount: u128, maturity: u64) -> PendingUnbonding {
+    PendingUnbonding {
+        id: Uint128::new(id),
+        validator: "validator_a".to_string(),
+        amount: Uint128::new(amount),
+        maturity,
     }
 }
-*/
+
+#[test]
+fn claim_earliest_maturity_first() {
+    let (mut deps, _) = setup(&[validator("validator_a", 5)], &[]);
+
+    UNBONDING
+        .save(deps.as_mut().storage, &Uint128::new(300))
+        .unwrap();
+    PENDING_UNBONDINGS
+        .save(deps.as_mut().storage, &vec![
+            pending(0, 100, 300),
+            pending(1, 100, 100),
+            pending(2, 100, 200),
+        ])
+        .unwrap();
+
+    // Only the first unbonding and half of the second have returned
+    deps.querier
+        .update_balance("scrt_staking", coins(150, "uscrt"));
+
+    claim(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("treasury", &[]),
+        Addr::unchecked("sscrt"),
+    )
+    .unwrap();
+
+    assert_eq!(
+        UNBONDING.load(deps.as_ref().storage).unwrap(),
+        Uint128::new(150)
+    );
+    assert_eq!(PENDING_UNBONDINGS.load(deps.as_ref().storage).unwrap(), vec![
+        pending(2, 50, 200),
+        pending(0, 100, 300),
+    ]);
+}
@@ -1,5 +1,5 @@
 use shade_multi_test::multi::admin::init_admin_auth;
-use shade_protocol::c_std::{to_binary, Addr, Coin, Delegation, Uint128};
+use shade_protocol::c_std::{to_binary, Addr, Coin, Decimal, Delegation, Uint128};
 
 use shade_protocol::{
     contract_interfaces::{
@@ -12,6 +12,9 @@ use shade_protocol::{
 use shade_multi_test::multi::{scrt_staking::ScrtStaking, snip20::Snip20};
 use shade_protocol::multi_test::{App, StakingSudo, SudoMsg};
 
+// Chain unbonding period, 21 days
+const UNBONDING_PERIOD: u64 = 21 * 24 * 60 * 60;
+
 // Add other adapters here as they come
 fn basic_scrt_staking_integration(
     deposit: Uint128,
@@ -49,6 +52,8 @@ fn basic_scrt_staking_integration(
         owner: admin.clone().into(),
         sscrt: token.clone().into(),
         validator_bounds: None,
+        weighted_validators: None,
+        unbonding_period: UNBONDING_PERIOD,
         viewing_key: viewing_key.clone(),
     }
     .test_init(
@@ -98,6 +103,8 @@ fn basic_scrt_staking_integration(
             amount: deposit,
             msg: None,
             memo: None,
+            decoys: None,
+            entropy: None,
             padding: None,
         }
         .test_exec(&token, &mut app, admin.clone(), &[])
@@ -294,6 +301,28 @@ fn basic_scrt_staking_integration(
         _ => panic!("Query failed"),
     };
 
+    // Tracked unbondings
+    match (scrt_staking::QueryMsg::PendingUnbondings {}
+        .test_query(&scrt_staking, &app)
+        .unwrap())
+    {
+        scrt_staking::QueryAnswer::PendingUnbondings { unbondings } => {
+            if expected_scrt_staking.is_zero() {
+                assert!(unbondings.is_empty(), "Pending unbondings");
+            } else {
+                assert_eq!(unbondings.len(), 1, "Pending unbondings");
+                assert_eq!(unbondings[0].validator, validator.to_string());
+                assert_eq!(unbondings[0].amount, expected_scrt_staking);
+                assert_eq!(
+                    unbondings[0].maturity,
+                    app.block_info().time.seconds() + UNBONDING_PERIOD,
+                    "Unbonding maturity"
+                );
+            }
+        }
+        _ => panic!("Query failed"),
+    };
+
     // Claimable
     match adapter::QueryMsg::Adapter(adapter::SubQueryMsg::Claimable {
         asset: token.address.to_string().clone(),
@@ -334,6 +363,17 @@ fn basic_scrt_staking_integration(
     .test_exec(&scrt_staking, &mut app, admin.clone(), &[])
     .unwrap();
 
+    // Claimed unbondings are settled
+    match (scrt_staking::QueryMsg::PendingUnbondings {}
+        .test_query(&scrt_staking, &app)
+        .unwrap())
+    {
+        scrt_staking::QueryAnswer::PendingUnbondings { unbondings } => {
+            assert!(unbondings.is_empty(), "Pending unbondings post claim");
+        }
+        _ => panic!("Query failed"),
+    };
+
     // Reserves
     match adapter::QueryMsg::Adapter(adapter::SubQueryMsg::Reserves {
         asset: token.address.to_string().clone(),
@@ -385,6 +425,374 @@ fn basic_scrt_staking_integration(
     };
 }
 
+#[test]
+fn weighted_validators() {
+    let mut app = App::default();
+
+    let viewing_key = "unguessable".to_string();
+    let admin = Addr::unchecked("admin");
+    let validators = vec!["validator_a".to_string(), "validator_b".to_string()];
+    let deposit = Uint128::new(400);
+    let admin_auth = init_admin_auth(&mut app, &admin);
+
+    for validator in validators.clone() {
+        app.sudo(SudoMsg::Staking(StakingSudo::AddValidator { validator }))
+            .unwrap();
+    }
+
+    let token = snip20::InstantiateMsg {
+        name: "secretSCRT".into(),
+        admin: Some("admin".into()),
+        symbol: "SSCRT".into(),
+        decimals: 6,
+        initial_balances: None,
+        prng_seed: to_binary("").ok().unwrap(),
+        config: Some(snip20::InitConfig {
+            public_total_supply: Some(true),
+            enable_deposit: Some(true),
+            enable_redeem: Some(true),
+            enable_mint: Some(false),
+            enable_burn: Some(false),
+            enable_transfer: Some(true),
+        }),
+        query_auth: None,
+    }
+    .test_init(Snip20::default(), &mut app, admin.clone(), "token", &[])
+    .unwrap();
+
+    let weighted = |weights: Vec<u128>| scrt_staking::WeightedValidators {
+        validators: validators
+            .iter()
+            .zip(weights)
+            .map(|(validator, weight)| scrt_staking::ValidatorWeight {
+                validator: validator.clone(),
+                weight: Uint128::new(weight),
+            })
+            .collect(),
+        // 10%
+        tolerance: Uint128::new(10u128.pow(17)),
+    };
+
+    // Unknown validators are rejected
+    assert!(
+        scrt_staking::InstantiateMsg {
+            admin_auth: admin_auth.clone().into(),
+            owner: admin.clone().into(),
+            sscrt: token.clone().into(),
+            validator_bounds: None,
+            weighted_validators: Some(scrt_staking::WeightedValidators {
+                validators: vec![scrt_staking::ValidatorWeight {
+                    validator: "not_a_validator".into(),
+                    weight: Uint128::one(),
+                }],
+                tolerance: Uint128::zero(),
+            }),
+            unbonding_period: UNBONDING_PERIOD,
+            viewing_key: viewing_key.clone(),
+        }
+        .test_init(
+            ScrtStaking::default(),
+            &mut app,
+            admin.clone(),
+            "bad_scrt_staking",
+            &[],
+        )
+        .is_err()
+    );
+
+    let scrt_staking = scrt_staking::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        owner: admin.clone().into(),
+        sscrt: token.clone().into(),
+        validator_bounds: None,
+        weighted_validators: Some(weighted(vec![1, 3])),
+        unbonding_period: UNBONDING_PERIOD,
+        viewing_key: viewing_key.clone(),
+    }
+    .test_init(
+        ScrtStaking::default(),
+        &mut app,
+        admin.clone(),
+        "scrt_staking",
+        &[],
+    )
+    .unwrap();
+
+    let deposit_coin = Coin {
+        denom: "uscrt".into(),
+        amount: deposit,
+    };
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &admin.clone(), vec![deposit_coin.clone()])
+            .unwrap();
+    });
+
+    snip20::ExecuteMsg::Deposit { padding: None }
+        .test_exec(&token, &mut app, admin.clone(), &vec![deposit_coin])
+        .unwrap();
+
+    snip20::ExecuteMsg::Send {
+        recipient: scrt_staking.address.to_string().clone(),
+        recipient_code_hash: None,
+        amount: deposit,
+        msg: None,
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None,
+    }
+    .test_exec(&token, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    let distribution = |app: &App| -> Vec<(Uint128, Uint128)> {
+        match (scrt_staking::QueryMsg::ValidatorDistribution {}
+            .test_query(&scrt_staking, app)
+            .unwrap())
+        {
+            scrt_staking::QueryAnswer::ValidatorDistribution { validators } => validators
+                .into_iter()
+                .map(|v| (v.target, v.actual))
+                .collect(),
+            _ => panic!("Query failed"),
+        }
+    };
+
+    // Deposit split 1:3
+    assert_eq!(distribution(&app), vec![
+        (Uint128::new(100), Uint128::new(100)),
+        (Uint128::new(300), Uint128::new(300)),
+    ]);
+
+    let query_config = |app: &App| -> scrt_staking::Config {
+        match (scrt_staking::QueryMsg::Config {}
+            .test_query(&scrt_staking, app)
+            .unwrap())
+        {
+            scrt_staking::QueryAnswer::Config { config } => config,
+            _ => panic!("Query failed"),
+        }
+    };
+
+    let mut config = query_config(&app);
+    config.weighted_validators = Some(weighted(vec![1, 1]));
+
+    scrt_staking::ExecuteMsg::UpdateConfig { config }
+        .test_exec(&scrt_staking, &mut app, admin.clone(), &[])
+        .unwrap();
+
+    // New targets, nothing moved yet
+    assert_eq!(distribution(&app), vec![
+        (Uint128::new(200), Uint128::new(100)),
+        (Uint128::new(200), Uint128::new(300)),
+    ]);
+
+    adapter::ExecuteMsg::Adapter(adapter::SubExecuteMsg::Update {
+        asset: token.address.to_string().clone(),
+    })
+    .test_exec(&scrt_staking, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // Redelegated toward targets
+    assert_eq!(distribution(&app), vec![
+        (Uint128::new(200), Uint128::new(200)),
+        (Uint128::new(200), Uint128::new(200)),
+    ]);
+
+    let mut config = query_config(&app);
+    config.weighted_validators = Some(weighted(vec![1, 3]));
+
+    scrt_staking::ExecuteMsg::UpdateConfig { config }
+        .test_exec(&scrt_staking, &mut app, admin.clone(), &[])
+        .unwrap();
+
+    adapter::ExecuteMsg::Adapter(adapter::SubExecuteMsg::Update {
+        asset: token.address.to_string().clone(),
+    })
+    .test_exec(&scrt_staking, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // validator_a is still receiving a redelegation, so it can't be redelegated from
+    assert_eq!(distribution(&app), vec![
+        (Uint128::new(100), Uint128::new(200)),
+        (Uint128::new(300), Uint128::new(200)),
+    ]);
+
+    app.update_block(|block| block.time = block.time.plus_seconds(UNBONDING_PERIOD));
+
+    adapter::ExecuteMsg::Adapter(adapter::SubExecuteMsg::Update {
+        asset: token.address.to_string().clone(),
+    })
+    .test_exec(&scrt_staking, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // Redelegation completed
+    assert_eq!(distribution(&app), vec![
+        (Uint128::new(100), Uint128::new(100)),
+        (Uint128::new(300), Uint128::new(300)),
+    ]);
+}
+
+#[test]
+fn out_of_bounds_redelegation() {
+    let mut app = App::default();
+
+    let viewing_key = "unguessable".to_string();
+    let admin = Addr::unchecked("admin");
+    let good_validator = "validator_a".to_string();
+    let bad_validator = "validator_b".to_string();
+    let deposit = Uint128::new(100);
+    let admin_auth = init_admin_auth(&mut app, &admin);
+
+    for validator in vec![good_validator.clone(), bad_validator.clone()] {
+        app.sudo(SudoMsg::Staking(StakingSudo::AddValidator { validator }))
+            .unwrap();
+    }
+
+    let token = snip20::InstantiateMsg {
+        name: "secretSCRT".into(),
+        admin: Some("admin".into()),
+        symbol: "SSCRT".into(),
+        decimals: 6,
+        initial_balances: None,
+        prng_seed: to_binary("").ok().unwrap(),
+        config: Some(snip20::InitConfig {
+            public_total_supply: Some(true),
+            enable_deposit: Some(true),
+            enable_redeem: Some(true),
+            enable_mint: Some(false),
+            enable_burn: Some(false),
+            enable_transfer: Some(true),
+        }),
+        query_auth: None,
+    }
+    .test_init(Snip20::default(), &mut app, admin.clone(), "token", &[])
+    .unwrap();
+
+    // Everything starts on the validator that will fall out of bounds
+    let scrt_staking = scrt_staking::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        owner: admin.clone().into(),
+        sscrt: token.clone().into(),
+        validator_bounds: None,
+        weighted_validators: Some(scrt_staking::WeightedValidators {
+            validators: vec![scrt_staking::ValidatorWeight {
+                validator: bad_validator.clone(),
+                weight: Uint128::one(),
+            }],
+            tolerance: Uint128::zero(),
+        }),
+        unbonding_period: UNBONDING_PERIOD,
+        viewing_key: viewing_key.clone(),
+    }
+    .test_init(
+        ScrtStaking::default(),
+        &mut app,
+        admin.clone(),
+        "scrt_staking",
+        &[],
+    )
+    .unwrap();
+
+    let deposit_coin = Coin {
+        denom: "uscrt".into(),
+        amount: deposit,
+    };
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &admin.clone(), vec![deposit_coin.clone()])
+            .unwrap();
+    });
+
+    snip20::ExecuteMsg::Deposit { padding: None }
+        .test_exec(&token, &mut app, admin.clone(), &vec![deposit_coin])
+        .unwrap();
+
+    snip20::ExecuteMsg::Send {
+        recipient: scrt_staking.address.to_string().clone(),
+        recipient_code_hash: None,
+        amount: deposit,
+        msg: None,
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None,
+    }
+    .test_exec(&token, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    let delegated = |app: &App, validator: &String| -> Uint128 {
+        let delegations: Vec<Delegation> = scrt_staking::QueryMsg::Delegations {}
+            .test_query(&scrt_staking, app)
+            .unwrap();
+        delegations
+            .into_iter()
+            .filter(|d| d.validator == *validator)
+            .map(|d| d.amount.amount)
+            .sum()
+    };
+
+    assert_eq!(delegated(&app, &bad_validator), deposit, "Initial delegation");
+
+    // Multi-test validators can't have their commission changed or be jailed,
+    // those are covered against the mock querier in src/test.rs,
+    // so push the validator out of the position bounds instead
+    let mut config = match (scrt_staking::QueryMsg::Config {}
+        .test_query(&scrt_staking, &app)
+        .unwrap())
+    {
+        scrt_staking::QueryAnswer::Config { config } => config,
+        _ => panic!("Query failed"),
+    };
+    config.weighted_validators = None;
+    config.validator_bounds = Some(scrt_staking::ValidatorBounds {
+        min_commission: Decimal::zero(),
+        max_commission: Decimal::one(),
+        top_position: Uint128::zero(),
+        bottom_position: Uint128::one(),
+    });
+
+    scrt_staking::ExecuteMsg::UpdateConfig { config }
+        .test_exec(&scrt_staking, &mut app, admin.clone(), &[])
+        .unwrap();
+
+    adapter::ExecuteMsg::Adapter(adapter::SubExecuteMsg::Update {
+        asset: token.address.to_string().clone(),
+    })
+    .test_exec(&scrt_staking, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    assert_eq!(
+        delegated(&app, &bad_validator),
+        Uint128::zero(),
+        "Out of bounds delegation"
+    );
+    assert_eq!(
+        delegated(&app, &good_validator),
+        deposit,
+        "Redelegated delegation"
+    );
+
+    match (scrt_staking::QueryMsg::Redelegations {}
+        .test_query(&scrt_staking, &app)
+        .unwrap())
+    {
+        scrt_staking::QueryAnswer::Redelegations { redelegations } => {
+            assert_eq!(redelegations.len(), 1, "Redelegations recorded");
+            assert_eq!(redelegations[0].src_validator, bad_validator);
+            assert_eq!(redelegations[0].dst_validator, good_validator);
+            assert_eq!(redelegations[0].amount, deposit);
+            assert_eq!(
+                redelegations[0].reason,
+                scrt_staking::RedelegationReason::OutOfBounds
+            );
+        }
+        _ => panic!("Query failed"),
+    };
+}
+
 macro_rules! basic_scrt_staking_tests {
     ($($name:ident: $value:expr,)*) => {
         $(
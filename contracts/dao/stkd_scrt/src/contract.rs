@@ -31,18 +31,30 @@ pub fn instantiate(
         admin_auth: msg.admin_auth.into_valid(deps.api)?,
         sscrt: msg.sscrt.into_valid(deps.api)?,
         owner: deps.api.addr_validate(msg.owner.as_str())?,
-        staking_derivatives: msg.staking_derivatives.into_valid(deps.api)?,
+        staking_derivatives: msg
+            .staking_derivatives
+            .into_iter()
+            .map(|d| d.into_valid(deps.api))
+            .collect::<StdResult<Vec<_>>>()?,
     };
 
+    execute::validate_derivatives(&config, &[])?;
+
     CONFIG.save(deps.storage, &config)?;
 
     SELF_ADDRESS.save(deps.storage, &env.contract.address)?;
     VIEWING_KEY.save(deps.storage, &msg.viewing_key)?;
 
-    Ok(Response::new().add_messages(vec![
-        set_viewing_key_msg(msg.viewing_key, None, &config.sscrt)?,
+    let mut messages = vec![
+        set_viewing_key_msg(msg.viewing_key.clone(), None, &config.sscrt)?,
         register_receive(env.contract.code_hash, None, &config.sscrt)?,
-    ]))
+    ];
+    messages.append(&mut execute::derivative_viewing_keys(
+        &config,
+        &msg.viewing_key,
+    )?);
+
+    Ok(Response::new().add_messages(messages))
 }
 
 #[shd_entry_point]
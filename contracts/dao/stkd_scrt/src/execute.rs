@@ -4,6 +4,7 @@ use shade_protocol::{
         to_binary,
         Addr,
         Binary,
+        CosmosMsg,
         DepsMut,
         Env,
         MessageInfo,
@@ -17,15 +18,16 @@ use shade_protocol::{
 use shade_protocol::{
     dao::{
         adapter,
-        stkd_scrt::{staking_derivatives, Config, ExecuteAnswer},
+        stkd_scrt::{staking_derivatives, Config, Derivative, ExecuteAnswer},
     },
+    snip20::helpers::set_viewing_key_msg,
     utils::{
         generic_response::ResponseStatus,
         wrap::{unwrap, wrap_and_send},
     },
 };
 
-use crate::storage::*;
+use crate::{query, storage::*};
 
 pub fn receive(
     deps: DepsMut,
@@ -42,13 +44,33 @@ pub fn receive(
         return Err(StdError::generic_err("Only accepts sSCRT"));
     }
 
+    let mut messages = vec![unwrap(amount, config.sscrt.clone())?];
+
+    // Split across derivatives by weight, last one takes the rounding dust
+    let total_weight: Uint128 = config.staking_derivatives.iter().map(|d| d.weight).sum();
+    let mut remaining = amount;
+
+    for (i, derivative) in config.staking_derivatives.iter().enumerate() {
+        let share = if i == config.staking_derivatives.len() - 1 {
+            remaining
+        } else {
+            amount.multiply_ratio(derivative.weight, total_weight)
+        };
+
+        if share.is_zero() {
+            continue;
+        }
+        remaining -= share;
+
+        messages.push(staking_derivatives::stake_msg(
+            share,
+            &derivative.contract,
+        )?);
+    }
+
     // Unwrap & stake
     Ok(Response::new()
-        .add_message(unwrap(amount, config.sscrt.clone())?)
-        .add_message(staking_derivatives::stake_msg(
-            amount,
-            &config.staking_derivatives,
-        )?)
+        .add_messages(messages)
         .set_data(to_binary(&ExecuteAnswer::Receive {
             status: ResponseStatus::Success,
         })?))
@@ -69,19 +91,35 @@ pub fn try_update_config(
         &cur_config.admin_auth,
     )?;
 
+    validate_derivatives(&config, &cur_config.staking_derivatives)?;
+
     // Save new info
     CONFIG.save(deps.storage, &config)?;
 
-    Ok(
-        Response::new().set_data(to_binary(&ExecuteAnswer::UpdateConfig {
+    Ok(Response::new()
+        .add_messages(derivative_viewing_keys(
+            &config,
+            &VIEWING_KEY.load(deps.storage)?,
+        )?)
+        .set_data(to_binary(&ExecuteAnswer::UpdateConfig {
             status: ResponseStatus::Success,
-        })?),
-    )
+        })?))
+}
+
+/* Holdings are queried with the viewing key,
+ * so it's set on every configured derivative
+ */
+pub fn derivative_viewing_keys(config: &Config, key: &str) -> StdResult<Vec<CosmosMsg>> {
+    config
+        .staking_derivatives
+        .iter()
+        .map(|d| set_viewing_key_msg(key.to_string(), None, &d.contract))
+        .collect()
 }
 
 pub fn unbond(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     asset: Addr,
     amount: Uint128,
@@ -108,11 +146,70 @@ pub fn unbond(
         return Err(StdError::generic_err("Unrecognized Asset"));
     }
 
+    let holdings = query::holdings(deps.as_ref(), &env, &config)?;
+    let total: Uint128 = holdings
+        .iter()
+        .map(|(_, h)| h.token_balance_value_in_scrt)
+        .sum();
+
+    if amount > total {
+        return Err(StdError::generic_err(format!(
+            "Unbond amount {} greater than unbondable {}",
+            amount, total
+        )));
+    }
+
+    /* Unbond from the derivatives furthest above their target first,
+     * so what remains ends up closest to the target weights
+     */
+    let total_weight: Uint128 = config.staking_derivatives.iter().map(|d| d.weight).sum();
+    let mut surplus: Vec<(usize, Uint128)> = holdings
+        .iter()
+        .enumerate()
+        .map(|(i, (d, h))| {
+            let target = (total - amount).multiply_ratio(d.weight, total_weight);
+            (i, h.token_balance_value_in_scrt.saturating_sub(target))
+        })
+        .collect();
+    surplus.sort_by(|a, b| b.1.cmp(&a.1));
+
+    let mut unbonds = vec![Uint128::zero(); holdings.len()];
+    let mut remaining = amount;
+
+    for (i, available) in surplus {
+        let unbond = remaining.min(available);
+        unbonds[i] += unbond;
+        remaining -= unbond;
+    }
+
+    // rounding leftovers from whatever is still bonded
+    for (i, (_, h)) in holdings.iter().enumerate() {
+        let unbond = remaining.min(h.token_balance_value_in_scrt - unbonds[i]);
+        unbonds[i] += unbond;
+        remaining -= unbond;
+    }
+
+    let mut messages = vec![];
+
+    for (i, (d, h)) in holdings.iter().enumerate() {
+        if unbonds[i].is_zero() {
+            continue;
+        }
+
+        // derivatives redeem in their own token, not scrt
+        let redeem_amount = match unbonds[i] == h.token_balance_value_in_scrt {
+            true => h.token_balance,
+            false => unbonds[i].multiply_ratio(h.token_balance, h.token_balance_value_in_scrt),
+        };
+
+        messages.push(staking_derivatives::unbond_msg(
+            redeem_amount,
+            &d.contract,
+        )?);
+    }
+
     Ok(Response::new()
-        .add_message(staking_derivatives::unbond_msg(
-            amount,
-            &config.staking_derivatives,
-        )?)
+        .add_messages(messages)
         .set_data(to_binary(&adapter::ExecuteAnswer::Unbond {
             status: ResponseStatus::Success,
             amount,
@@ -129,18 +226,17 @@ pub fn claim(deps: DepsMut, env: Env, _info: MessageInfo, asset: Addr) -> StdRes
         return Err(StdError::generic_err("Unrecognized Asset"));
     }
 
-    let claimable = staking_derivatives::holdings_query(
-        &deps.querier,
-        env.contract.address,
-        VIEWING_KEY.load(deps.storage)?,
-        env.block.time.seconds(),
-        &config.staking_derivatives,
-    )?
-    .claimable_scrt;
-
     let mut messages = vec![];
+    let mut claimable = Uint128::zero();
+
+    for (d, h) in query::holdings(deps.as_ref(), &env, &config)? {
+        if !h.claimable_scrt.is_zero() {
+            messages.push(staking_derivatives::claim_msg(&d.contract)?);
+            claimable += h.claimable_scrt;
+        }
+    }
+
     if !claimable.is_zero() {
-        messages.push(staking_derivatives::claim_msg(&config.staking_derivatives)?);
         messages.append(&mut wrap_and_send(
             claimable,
             config.owner,
@@ -156,3 +252,49 @@ pub fn claim(deps: DepsMut, env: Env, _info: MessageInfo, asset: Addr) -> StdRes
         },
     )?))
 }
+
+pub fn validate_derivatives(config: &Config, current: &[Derivative]) -> StdResult<()> {
+    if config.staking_derivatives.is_empty() {
+        return Err(StdError::generic_err("No staking derivatives"));
+    }
+
+    if config
+        .staking_derivatives
+        .iter()
+        .map(|d| d.weight)
+        .sum::<Uint128>()
+        .is_zero()
+    {
+        return Err(StdError::generic_err(
+            "Derivative weights cannot all be zero",
+        ));
+    }
+
+    for (i, d) in config.staking_derivatives.iter().enumerate() {
+        if config.staking_derivatives[..i]
+            .iter()
+            .any(|o| o.contract.address == d.contract.address)
+        {
+            return Err(StdError::generic_err(format!(
+                "Duplicate staking derivative {}",
+                d.contract.address
+            )));
+        }
+    }
+
+    // Holdings of a removed derivative could no longer be unbonded or claimed
+    for d in current.iter() {
+        if !config
+            .staking_derivatives
+            .iter()
+            .any(|o| o.contract.address == d.contract.address)
+        {
+            return Err(StdError::generic_err(format!(
+                "Staking derivative {} can't be removed, set its weight to 0 instead",
+                d.contract.address
+            )));
+        }
+    }
+
+    Ok(())
+}
@@ -2,7 +2,7 @@ use shade_protocol::{
     c_std::{Addr, Deps, Env, StdError, StdResult, Uint128},
     dao::{
         adapter,
-        stkd_scrt::{staking_derivatives, QueryAnswer},
+        stkd_scrt::{staking_derivatives, Config, Derivative, QueryAnswer},
     },
 };
 
@@ -14,6 +14,32 @@ pub fn config(deps: Deps) -> StdResult<QueryAnswer> {
     })
 }
 
+/* Holdings in each derivative */
+pub fn holdings(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+) -> StdResult<Vec<(Derivative, staking_derivatives::Holdings)>> {
+    let key = VIEWING_KEY.load(deps.storage)?;
+
+    config
+        .staking_derivatives
+        .iter()
+        .map(|d| {
+            Ok((
+                d.clone(),
+                staking_derivatives::holdings_query(
+                    &deps.querier,
+                    env.contract.address.clone(),
+                    key.clone(),
+                    env.block.time.seconds(),
+                    &d.contract,
+                )?,
+            ))
+        })
+        .collect()
+}
+
 pub fn balance(deps: Deps, env: Env, asset: Addr) -> StdResult<adapter::QueryAnswer> {
     let config = CONFIG.load(deps.storage)?;
 
@@ -24,18 +50,11 @@ pub fn balance(deps: Deps, env: Env, asset: Addr) -> StdResult<adapter::QueryAns
         )));
     }
 
-    let holdings = staking_derivatives::holdings_query(
-        &deps.querier,
-        env.contract.address,
-        VIEWING_KEY.load(deps.storage)?,
-        env.block.time.seconds(),
-        &config.staking_derivatives,
-    )?;
-
     Ok(adapter::QueryAnswer::Balance {
-        amount: holdings.claimable_scrt
-            + holdings.unbonding_scrt
-            + holdings.token_balance_value_in_scrt,
+        amount: holdings(deps, &env, &config)?
+            .iter()
+            .map(|(_, h)| h.claimable_scrt + h.unbonding_scrt + h.token_balance_value_in_scrt)
+            .sum(),
     })
 }
 
@@ -49,16 +68,11 @@ pub fn claimable(deps: Deps, env: Env, asset: Addr) -> StdResult<adapter::QueryA
         )));
     }
 
-    let holdings = staking_derivatives::holdings_query(
-        &deps.querier,
-        env.contract.address,
-        VIEWING_KEY.load(deps.storage)?,
-        env.block.time.seconds(),
-        &config.staking_derivatives,
-    )?;
-
     Ok(adapter::QueryAnswer::Claimable {
-        amount: holdings.claimable_scrt,
+        amount: holdings(deps, &env, &config)?
+            .iter()
+            .map(|(_, h)| h.claimable_scrt)
+            .sum(),
     })
 }
 
@@ -72,16 +86,11 @@ pub fn unbonding(deps: Deps, env: Env, asset: Addr) -> StdResult<adapter::QueryA
         )));
     }
 
-    let holdings = staking_derivatives::holdings_query(
-        &deps.querier,
-        env.contract.address,
-        VIEWING_KEY.load(deps.storage)?,
-        env.block.time.seconds(),
-        &config.staking_derivatives,
-    )?;
-
     Ok(adapter::QueryAnswer::Unbonding {
-        amount: holdings.unbonding_scrt,
+        amount: holdings(deps, &env, &config)?
+            .iter()
+            .map(|(_, h)| h.unbonding_scrt)
+            .sum(),
     })
 }
 
@@ -95,16 +104,11 @@ pub fn unbondable(deps: Deps, env: Env, asset: Addr) -> StdResult<adapter::Query
         )));
     }
 
-    let holdings = staking_derivatives::holdings_query(
-        &deps.querier,
-        env.contract.address,
-        VIEWING_KEY.load(deps.storage)?,
-        env.block.time.seconds(),
-        &config.staking_derivatives,
-    )?;
-
     Ok(adapter::QueryAnswer::Unbondable {
-        amount: holdings.token_balance_value_in_scrt,
+        amount: holdings(deps, &env, &config)?
+            .iter()
+            .map(|(_, h)| h.token_balance_value_in_scrt)
+            .sum(),
     })
 }
 
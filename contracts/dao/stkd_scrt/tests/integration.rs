@@ -1,24 +1,36 @@
-/*use shade_multi_test::multi::admin::init_admin_auth;
-use shade_protocol::c_std::{to_binary, Addr, Coin, Decimal, Delegation, Uint128, Validator};
-
+use mock_stkd_derivative::contract as mock_stkd;
+use shade_multi_test::multi::{
+    admin::init_admin_auth,
+    mock_stkd::MockStkd,
+    snip20::Snip20,
+    stkd_scrt::StkdScrt,
+};
 use shade_protocol::{
+    c_std::{to_binary, Addr, Coin, ContractInfo, Decimal, Uint128},
     contract_interfaces::{
         dao::{adapter, stkd_scrt},
         snip20,
     },
-    utils::{asset::Contract, ExecuteCallback, InstantiateCallback, MultiTestable, Query},
+    multi_test::App,
+    utils::{ExecuteCallback, InstantiateCallback, MultiTestable, Query},
 };
 
-use shade_multi_test::multi::{snip20::Snip20, stkd_scrt::StkdScrt};
-use shade_protocol::multi_test::{App, StakingSudo, SudoMsg};
+struct Setup {
+    app: App,
+    admin: Addr,
+    token: ContractInfo,
+    stkd_scrt: ContractInfo,
+    derivatives: Vec<ContractInfo>,
+}
 
-fn bonded_adapter_test(deposit: Uint128, rewards: Uint128, reserves: Uint128, balance: Uint128) {
+/* Derivatives priced at 1 & 2 SCRT, deposit split by weights */
+fn setup(deposit: Uint128, weights: Vec<u128>) -> Setup {
     let mut app = App::default();
 
     let viewing_key = "unguessable".to_string();
     let admin = Addr::unchecked("admin");
-    let validator = Addr::unchecked("validator");
     let admin_auth = init_admin_auth(&mut app, &admin);
+
     let token = snip20::InstantiateMsg {
         name: "secretSCRT".into(),
         admin: Some("admin".into()),
@@ -39,30 +51,48 @@ fn bonded_adapter_test(deposit: Uint128, rewards: Uint128, reserves: Uint128, ba
     .test_init(Snip20::default(), &mut app, admin.clone(), "token", &[])
     .unwrap();
 
+    let derivatives: Vec<ContractInfo> = vec![1_000_000u128, 2_000_000u128]
+        .into_iter()
+        .enumerate()
+        .map(|(i, price)| {
+            mock_stkd::InstantiateMsg {
+                name: "Staking Derivative".to_string(),
+                symbol: "stkd-SCRT".to_string(),
+                decimals: 6,
+                price: Uint128::new(price),
+                unbonding_time: 10,
+                unbonding_batch_interval: 1,
+                staking_commission: Decimal::zero(),
+                unbond_commission: Decimal::zero(),
+            }
+            .test_init(
+                MockStkd::default(),
+                &mut app,
+                admin.clone(),
+                &format!("derivative_{}", i),
+                &[],
+            )
+            .unwrap()
+        })
+        .collect();
+
     let stkd_scrt = stkd_scrt::InstantiateMsg {
         admin_auth: admin_auth.into(),
         owner: admin.clone().into(),
         sscrt: token.clone().into(),
-        validator_bounds: None,
         viewing_key: viewing_key.clone(),
+        staking_derivatives: derivatives
+            .iter()
+            .zip(weights)
+            .map(|(derivative, weight)| stkd_scrt::RawDerivative {
+                contract: derivative.clone().into(),
+                weight: Uint128::new(weight),
+            })
+            .collect(),
     }
-    .test_init(
-        ScrtStaking::default(),
-        &mut app,
-        admin.clone(),
-        "stkd_scrt",
-        &[],
-    )
+    .test_init(StkdScrt::default(), &mut app, admin.clone(), "stkd_scrt", &[])
     .unwrap();
 
-    app.sudo(SudoMsg::Staking(StakingSudo::AddValidator {
-        validator: validator.to_string().clone(),
-    }))
-    .unwrap();
-
-    //TODO deploy staking_derivatives
-
-    // set admin owner key
     snip20::ExecuteMsg::SetViewingKey {
         key: viewing_key.clone(),
         padding: None,
@@ -81,251 +111,224 @@ fn bonded_adapter_test(deposit: Uint128, rewards: Uint128, reserves: Uint128, ba
             .unwrap();
     });
 
-    // Wrap L1 into tokens
     snip20::ExecuteMsg::Deposit { padding: None }
         .test_exec(&token, &mut app, admin.clone(), &vec![deposit_coin])
         .unwrap();
 
-    // Send funds to adapter
     snip20::ExecuteMsg::Send {
         recipient: stkd_scrt.address.to_string().clone(),
         recipient_code_hash: None,
         amount: deposit,
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, admin.clone(), &[])
     .unwrap();
 
-    // reserves
-    match adapter::QueryMsg::Adapter(adapter::SubQueryMsg::Reserves {
-        asset: token.address.to_string().clone(),
-    })
-    .test_query(&stkd_scrt, &app)
-    .unwrap()
+    Setup {
+        app,
+        admin,
+        token,
+        stkd_scrt,
+        derivatives,
+    }
+}
+
+// Derivative tokens held by stkd_scrt
+fn derivative_balance(setup: &Setup, derivative: &ContractInfo) -> Uint128 {
+    match (mock_stkd::QueryMsg::Balance {
+        address: setup.stkd_scrt.address.clone(),
+        key: "unguessable".to_string(),
+    }
+    .test_query(derivative, &setup.app)
+    .unwrap())
     {
-        adapter::QueryAnswer::Reserves { amount } => {
-            assert_eq!(amount, reserves, "Reserves Pre-Rewards");
-        }
+        mock_stkd::QueryAnswer::Balance { amount } => amount,
         _ => panic!("Query failed"),
-    };
+    }
+}
 
-    // Balance
-    match adapter::QueryMsg::Adapter(adapter::SubQueryMsg::Balance {
-        asset: token.address.to_string().clone(),
-    })
-    .test_query(&stkd_scrt, &app)
-    .unwrap()
+fn adapter_query(setup: &Setup, msg: adapter::SubQueryMsg) -> Uint128 {
+    match adapter::QueryMsg::Adapter(msg)
+        .test_query(&setup.stkd_scrt, &setup.app)
+        .unwrap()
     {
-        adapter::QueryAnswer::Balance { amount } => {
-            assert_eq!(amount, balance, "Balance Pre-Rewards");
-        }
-        _ => panic!("Query failed"),
-    };
+        adapter::QueryAnswer::Balance { amount }
+        | adapter::QueryAnswer::Claimable { amount }
+        | adapter::QueryAnswer::Unbonding { amount }
+        | adapter::QueryAnswer::Unbondable { amount }
+        | adapter::QueryAnswer::Reserves { amount } => amount,
+    }
+}
 
-    // Rewards
-    let cur_rewards: Uint128 = stkd_scrt::QueryMsg::Rewards {}
-        .test_query(&stkd_scrt, &app)
-        .unwrap();
-    assert_eq!(cur_rewards, Uint128::zero(), "Rewards Pre-add");
+#[test]
+fn weighted_receive_split() {
+    let setup = setup(Uint128::new(400), vec![1, 3]);
+    let asset = setup.token.address.to_string();
 
-    //ensemble.add_rewards(rewards);
-    app.sudo(SudoMsg::Staking(StakingSudo::AddRewards {
-        amount: Coin {
-            amount: rewards,
-            denom: "uscrt".into(),
-        },
-    }))
-    .unwrap();
+    // 100 SCRT at 1 SCRT each, 300 SCRT at 2 SCRT each
+    assert_eq!(
+        derivative_balance(&setup, &setup.derivatives[0]),
+        Uint128::new(100)
+    );
+    assert_eq!(
+        derivative_balance(&setup, &setup.derivatives[1]),
+        Uint128::new(150)
+    );
 
-    // Reserves
-    match adapter::QueryMsg::Adapter(adapter::SubQueryMsg::Reserves {
-        asset: token.address.to_string().clone(),
-    })
-    .test_query(&stkd_scrt, &app)
-    .unwrap()
-    {
-        adapter::QueryAnswer::Reserves { amount } => {
-            assert_eq!(amount, reserves, "Reserves Post-Rewards");
-        }
-        _ => panic!("Query failed"),
-    };
+    assert_eq!(
+        adapter_query(&setup, adapter::SubQueryMsg::Balance {
+            asset: asset.clone()
+        }),
+        Uint128::new(400),
+        "Balance"
+    );
+    assert_eq!(
+        adapter_query(&setup, adapter::SubQueryMsg::Unbondable {
+            asset: asset.clone()
+        }),
+        Uint128::new(400),
+        "Unbondable"
+    );
+    assert_eq!(
+        adapter_query(&setup, adapter::SubQueryMsg::Reserves { asset }),
+        Uint128::zero(),
+        "Reserves"
+    );
+}
 
-    // Balance
-    match adapter::QueryMsg::Adapter(adapter::SubQueryMsg::Balance {
-        asset: token.address.to_string().clone(),
-    })
-    .test_query(&stkd_scrt, &app)
-    .unwrap()
-    {
-        adapter::QueryAnswer::Balance { amount } => {
-            assert_eq!(amount, deposit + rewards, "Balance Post-Rewards");
-        }
-        _ => panic!("Query failed"),
-    };
+#[test]
+fn surplus_first_unbond() {
+    let mut setup = setup(Uint128::new(400), vec![1, 3]);
+    let asset = setup.token.address.to_string();
 
-    // Claimable
-    match adapter::QueryMsg::Adapter(adapter::SubQueryMsg::Claimable {
-        asset: token.address.to_string().clone(),
-    })
-    .test_query(&stkd_scrt, &app)
-    .unwrap()
+    let mut config = match (stkd_scrt::QueryMsg::Config {}
+        .test_query(&setup.stkd_scrt, &setup.app)
+        .unwrap())
     {
-        adapter::QueryAnswer::Claimable { amount } => {
-            assert_eq!(amount, Uint128::zero(), "Claimable Pre-Unbond");
-        }
-        _ => panic!("Query failed"),
+        stkd_scrt::QueryAnswer::Config { config } => config,
     };
+    config.staking_derivatives[1].weight = Uint128::one();
 
-    // Unbondable
-    match adapter::QueryMsg::Adapter(adapter::SubQueryMsg::Unbondable {
-        asset: token.address.to_string().clone(),
-    })
-    .test_query(&stkd_scrt, &app)
-    .unwrap()
-    {
-        adapter::QueryAnswer::Unbondable { amount } => {
-            assert_eq!(amount, balance, "Unbondable Pre-Unbond");
-        }
-        _ => panic!("Query failed"),
-    };
+    // Derivatives holding stake can only be weighted down
+    let mut removed = config.clone();
+    removed.staking_derivatives.remove(1);
+    assert!(
+        stkd_scrt::ExecuteMsg::UpdateConfig { config: removed }
+            .test_exec(&setup.stkd_scrt, &mut setup.app, setup.admin.clone(), &[])
+            .is_err()
+    );
+
+    stkd_scrt::ExecuteMsg::UpdateConfig { config }
+        .test_exec(&setup.stkd_scrt, &mut setup.app, setup.admin.clone(), &[])
+        .unwrap();
 
-    // Unbond all
+    // 1:1 targets leave 100 on each, so the full unbond comes from the second derivative
     adapter::ExecuteMsg::Adapter(adapter::SubExecuteMsg::Unbond {
-        amount: balance,
-        asset: token.address.to_string().clone().to_string(),
+        asset: asset.clone(),
+        amount: Uint128::new(200),
     })
-    .test_exec(&stkd_scrt, &mut app, admin.clone(), &[])
+    .test_exec(&setup.stkd_scrt, &mut setup.app, setup.admin.clone(), &[])
     .unwrap();
-    println!("SCRT STAKING ADDR {}", stkd_scrt.address);
 
-    // Unbonding
-    match adapter::QueryMsg::Adapter(adapter::SubQueryMsg::Unbonding {
-        asset: token.address.to_string().clone(),
-    })
-    .test_query(&stkd_scrt, &app)
-    .unwrap()
-    {
-        adapter::QueryAnswer::Unbonding { amount } => {
-            assert_eq!(amount, deposit, "Unbonding Pre fast forward");
-        }
-        _ => panic!("Query failed"),
-    };
+    assert_eq!(
+        derivative_balance(&setup, &setup.derivatives[0]),
+        Uint128::new(100)
+    );
+    assert_eq!(
+        derivative_balance(&setup, &setup.derivatives[1]),
+        Uint128::new(50)
+    );
 
-    // Claimable
-    match adapter::QueryMsg::Adapter(adapter::SubQueryMsg::Claimable {
-        asset: token.address.to_string().clone(),
-    })
-    .test_query(&stkd_scrt, &app)
-    .unwrap()
-    {
-        adapter::QueryAnswer::Claimable { amount } => {
-            assert_eq!(amount, Uint128::zero(), "Claimable Pre unbond fast forward");
-        }
-        _ => panic!("Query failed"),
-    };
+    assert_eq!(
+        adapter_query(&setup, adapter::SubQueryMsg::Unbonding {
+            asset: asset.clone()
+        }),
+        Uint128::new(200),
+        "Unbonding"
+    );
+    assert_eq!(
+        adapter_query(&setup, adapter::SubQueryMsg::Unbondable {
+            asset: asset.clone()
+        }),
+        Uint128::new(200),
+        "Unbondable"
+    );
+    assert_eq!(
+        adapter_query(&setup, adapter::SubQueryMsg::Balance {
+            asset: asset.clone()
+        }),
+        Uint128::new(400),
+        "Balance"
+    );
 
-    app.sudo(SudoMsg::Staking(StakingSudo::FastForwardUndelegate {}))
+    // Can't unbond more than is bonded
+    assert!(
+        adapter::ExecuteMsg::Adapter(adapter::SubExecuteMsg::Unbond {
+            asset: asset.clone(),
+            amount: Uint128::new(201),
+        })
+        .test_exec(&setup.stkd_scrt, &mut setup.app, setup.admin.clone(), &[])
+        .is_err()
+    );
+
+    mock_stkd::ExecuteMsg::MockFastForward { steps: 11 }
+        .test_exec(
+            &setup.derivatives[1],
+            &mut setup.app,
+            setup.admin.clone(),
+            &[],
+        )
         .unwrap();
 
-    // Claimable
-    match adapter::QueryMsg::Adapter(adapter::SubQueryMsg::Claimable {
-        asset: token.address.to_string().clone(),
-    })
-    .test_query(&stkd_scrt, &app)
-    .unwrap()
-    {
-        adapter::QueryAnswer::Claimable { amount } => {
-            assert_eq!(amount, deposit, "Claimable post fast forward");
-        }
-        _ => panic!("Query failed"),
-    };
+    assert_eq!(
+        adapter_query(&setup, adapter::SubQueryMsg::Claimable {
+            asset: asset.clone()
+        }),
+        Uint128::new(200),
+        "Claimable"
+    );
+    assert_eq!(
+        adapter_query(&setup, adapter::SubQueryMsg::Unbonding {
+            asset: asset.clone()
+        }),
+        Uint128::zero(),
+        "Unbonding post maturity"
+    );
 
-    // Claim
     adapter::ExecuteMsg::Adapter(adapter::SubExecuteMsg::Claim {
-        asset: token.address.to_string().clone().to_string(),
+        asset: asset.clone(),
     })
-    .test_exec(&stkd_scrt, &mut app, admin.clone(), &[])
+    .test_exec(&setup.stkd_scrt, &mut setup.app, setup.admin.clone(), &[])
     .unwrap();
 
-    // Reserves
-    match adapter::QueryMsg::Adapter(adapter::SubQueryMsg::Reserves {
-        asset: token.address.to_string().clone(),
-    })
-    .test_query(&stkd_scrt, &app)
-    .unwrap()
-    {
-        adapter::QueryAnswer::Reserves { amount } => {
-            assert_eq!(amount, Uint128::zero(), "Reserves Post Claim");
-        }
-        _ => panic!("Query failed"),
-    };
-
-    // Balance
-    match adapter::QueryMsg::Adapter(adapter::SubQueryMsg::Balance {
-        asset: token.address.to_string().clone(),
-    })
-    .test_query(&stkd_scrt, &app)
-    .unwrap()
-    {
-        adapter::QueryAnswer::Balance { amount } => {
-            assert_eq!(amount, Uint128::zero(), "Balance Post Claim");
-        }
-        _ => panic!("Query failed"),
-    };
-
-    // Unbonding
-    match adapter::QueryMsg::Adapter(adapter::SubQueryMsg::Unbonding {
-        asset: token.address.to_string().clone(),
-    })
-    .test_query(&stkd_scrt, &app)
-    .unwrap()
-    {
-        adapter::QueryAnswer::Unbonding { amount } => {
-            assert_eq!(amount, Uint128::zero(), "Unbonding Post Claim");
-        }
-        _ => panic!("Query failed"),
-    };
+    assert_eq!(
+        adapter_query(&setup, adapter::SubQueryMsg::Claimable {
+            asset: asset.clone()
+        }),
+        Uint128::zero(),
+        "Claimable post claim"
+    );
+    assert_eq!(
+        adapter_query(&setup, adapter::SubQueryMsg::Balance { asset }),
+        Uint128::new(200),
+        "Balance post claim"
+    );
 
-    // ensure wrapped tokens were returned
+    // Claimed funds are wrapped and returned to the owner
     match (snip20::QueryMsg::Balance {
-        address: admin.to_string().clone(),
-        key: viewing_key.clone(),
-    })
-    .test_query(&token, &app)
-    .unwrap()
+        address: setup.admin.to_string(),
+        key: "unguessable".to_string(),
+    }
+    .test_query(&setup.token, &setup.app)
+    .unwrap())
     {
         snip20::QueryAnswer::Balance { amount } => {
-            assert_eq!(amount, deposit + rewards, "Final User balance");
-        }
-        _ => {
-            panic!("snip20 balance query failed");
+            assert_eq!(amount, Uint128::new(200), "Owner balance");
         }
+        _ => panic!("Query failed"),
     };
 }
-
-macro_rules! basic_stkd_scrt_tests {
-    ($($name:ident: $value:expr,)*) => {
-        $(
-            #[test]
-            fn $name() {
-                let (
-                    deposit,
-                    rewards,
-                    expected_stkd_scrt,
-                ) = $value;
-                basic_stkd_scrt_integration(deposit, rewards, expected_stkd_scrt);
-            }
-        )*
-    }
-}
-
-basic_stkd_scrt_tests! {
-    basic_stkd_scrt_0: (
-        Uint128::new(100), // deposit
-        Uint128::new(10),   // rewards
-        Uint128::new(100), // reserves
-        Uint128::new(100), // balance
-    ),
-}*/
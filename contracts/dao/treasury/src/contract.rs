@@ -12,7 +12,10 @@ use shade_protocol::{
         Response,
         StdResult,
     },
-    dao::treasury::{Config, ExecuteMsg, InstantiateMsg, QueryAnswer, QueryMsg, RunLevel},
+    dao::{
+        metrics,
+        treasury::{Config, ExecuteMsg, InstantiateMsg, QueryAnswer, QueryMsg, RunLevel},
+    },
     utils::asset::Contract,
 };
 
@@ -26,6 +29,8 @@ pub fn instantiate(
     CONFIG.save(deps.storage, &Config {
         admin_auth: msg.admin_auth.into_valid(deps.api)?,
         multisig: deps.api.addr_validate(&msg.multisig)?,
+        timelock: msg.timelock,
+        oracle: None,
     })?;
 
     VIEWING_KEY.save(deps.storage, &msg.viewing_key)?;
@@ -51,7 +56,19 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
         ExecuteMsg::UpdateConfig {
             admin_auth,
             multisig,
-        } => execute::try_update_config(deps, env, info, admin_auth, multisig),
+            timelock,
+            oracle,
+            clear_oracle,
+        } => execute::try_update_config(
+            deps,
+            env,
+            info,
+            admin_auth,
+            multisig,
+            timelock,
+            oracle,
+            clear_oracle.unwrap_or(false),
+        ),
         ExecuteMsg::RegisterAsset { contract } => {
             let contract = contract.into_valid(deps.api)?;
             execute::try_register_asset(deps, &env, info, &contract)
@@ -81,6 +98,12 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             execute::set_run_level(deps, &env, info, run_level)
         }
         ExecuteMsg::WrapCoins {} => execute::wrap_coins(deps, &env, info),
+        ExecuteMsg::SetOutflowLimit { asset, limit } => {
+            let asset = deps.api.addr_validate(&asset)?;
+            execute::set_outflow_limit(deps, &env, info, asset, limit)
+        }
+        ExecuteMsg::ApplyChange { id } => execute::apply_change(deps, &env, info, id),
+        ExecuteMsg::CancelChange { id } => execute::cancel_change(deps, &env, info, id),
     }
 }
 
@@ -107,6 +130,33 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             epoch,
             period,
         } => to_binary(&query::metrics(deps, env, date, epoch, period)?),
+        QueryMsg::MetricsPage {
+            date,
+            epoch,
+            period,
+            filter,
+            start_after,
+            limit,
+        } => {
+            let key = metrics::period_key(&env, date, epoch)?;
+            to_binary(&query::metrics_page(
+                deps,
+                key,
+                period,
+                filter,
+                start_after,
+                limit,
+            )?)
+        }
+        QueryMsg::MetricsSummary {
+            date,
+            epoch,
+            period,
+            token,
+        } => {
+            let key = metrics::period_key(&env, date, epoch)?;
+            to_binary(&query::metrics_summary(deps, key, period, token)?)
+        }
         QueryMsg::Balance { asset } => {
             let asset = deps.api.addr_validate(&asset)?;
             to_binary(&query::balance(deps, env, asset)?)
@@ -124,5 +174,12 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             let asset = deps.api.addr_validate(&asset)?;
             to_binary(&query::reserves(deps, env, asset)?)
         }
+        QueryMsg::PendingChanges {} => to_binary(&query::pending_changes(deps)?),
+        QueryMsg::PendingChange { id } => to_binary(&query::pending_change(deps, id)?),
+        QueryMsg::OutflowLimit { asset } => {
+            let asset = deps.api.addr_validate(&asset)?;
+            to_binary(&query::outflow_limit(deps, env, asset)?)
+        }
+        QueryMsg::Portfolio {} => to_binary(&query::portfolio(deps, env)?),
     }
 }
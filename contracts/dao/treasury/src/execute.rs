@@ -10,6 +10,7 @@ use shade_protocol::{
         Response,
         StdError,
         StdResult,
+        Storage,
         Uint128,
     },
     contract_interfaces::{
@@ -21,9 +22,13 @@ use shade_protocol::{
                 Allowance,
                 AllowanceMeta,
                 AllowanceType,
+                Config,
                 Context,
                 ExecuteAnswer,
                 Metric,
+                OutflowLimit,
+                PendingChange,
+                QueuedAction,
                 RunLevel,
             },
         },
@@ -75,12 +80,19 @@ pub fn receive(
     })?))
 }
 
+/* Lowering the timelock & changing the multisig are queued like any other change,
+ * so neither can be used to skip the timelock or the multisig's veto
+ */
+#[allow(clippy::too_many_arguments)]
 pub fn try_update_config(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     admin_auth: Option<RawContract>,
     multisig: Option<String>,
+    timelock: Option<Uint128>,
+    oracle: Option<RawContract>,
+    clear_oracle: bool,
 ) -> StdResult<Response> {
     let mut config = CONFIG.load(deps.storage)?;
 
@@ -91,21 +103,51 @@ pub fn try_update_config(
         &config.admin_auth,
     )?;
 
+    let mut queued = vec![];
+
+    if let Some(multisig) = multisig {
+        let multisig = deps.api.addr_validate(&multisig)?;
+        if config.timelock.is_zero() {
+            config.multisig = multisig;
+        } else {
+            queued.push(QueuedAction::SetMultisig { multisig });
+        }
+    }
+    if let Some(timelock) = timelock {
+        if timelock >= config.timelock {
+            config.timelock = timelock;
+        } else {
+            queued.push(QueuedAction::SetTimelock { timelock });
+        }
+    }
     if let Some(admin_auth) = admin_auth {
         config.admin_auth = admin_auth.into_valid(deps.api)?;
     }
-    if let Some(multisig) = multisig {
-        config.multisig = deps.api.addr_validate(&multisig)?;
+    if clear_oracle {
+        if oracle.is_some() {
+            return Err(StdError::generic_err("Cannot set and clear the oracle"));
+        }
+        config.oracle = None;
+    }
+    if let Some(oracle) = oracle {
+        config.oracle = Some(oracle.into_valid(deps.api)?);
     }
 
     CONFIG.save(deps.storage, &config)?;
 
-    Ok(
-        Response::new().set_data(to_binary(&ExecuteAnswer::UpdateConfig {
-            config,
-            status: ResponseStatus::Success,
-        })?),
-    )
+    let mut response = Response::new();
+    for action in queued {
+        let (id, execute_after) =
+            push_pending_change(deps.storage, &env, &config, info.sender.clone(), action)?;
+        response = response
+            .add_attribute("queued_change", id)
+            .add_attribute("execute_after", execute_after);
+    }
+
+    Ok(response.set_data(to_binary(&ExecuteAnswer::UpdateConfig {
+        config,
+        status: ResponseStatus::Success,
+    })?))
 }
 
 pub fn update(deps: DepsMut, env: &Env, info: MessageInfo, asset: Addr) -> StdResult<Response> {
@@ -158,6 +200,22 @@ fn rebalance(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdRe
     // allowances marked for removal
     let mut stale_allowances = vec![];
 
+    let outflow_limit = OUTFLOW_LIMIT.may_load(deps.storage, asset.clone())?;
+    let mut outflow = match &outflow_limit {
+        Some(limit) => Some(current_outflow(
+            deps.storage,
+            asset.clone(),
+            limit,
+            env.block.time.seconds(),
+        )?),
+        None => None,
+    };
+
+    // allowance left to each spender, ordered as allowances
+    let mut granted = vec![];
+    // funds pulled through allowances since the last update
+    let mut pulled = Uint128::zero();
+
     for (i, a) in allowances.clone().iter().enumerate() {
         let manager = MANAGER.may_load(deps.storage, a.spender.clone())?;
         let mut claimable = Uint128::zero();
@@ -210,6 +268,18 @@ fn rebalance(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdRe
         )?
         .allowance;
 
+        if let Some(outflow) = &outflow {
+            // whatever is missing from the last grant has been pulled since
+            let last_granted = outflow
+                .granted
+                .iter()
+                .find(|(spender, _)| spender == &a.spender)
+                .map(|(_, amount)| *amount)
+                .unwrap_or(allowance);
+            pulled += last_granted.saturating_sub(allowance);
+        }
+        granted.push((a.spender.clone(), allowance));
+
         if token_balance > allowance {
             token_balance -= allowance;
         } else {
@@ -230,6 +300,18 @@ fn rebalance(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdRe
         total_balance += balance + unbonding;
     }
 
+    // Room left under the outflow limit, allowance still outstanding can be pulled at any time
+    let mut outflow_room = match (&outflow_limit, &mut outflow) {
+        (Some(limit), Some(outflow)) => {
+            if !pulled.is_zero() {
+                outflow.pulls.push((env.block.time.seconds(), pulled));
+            }
+            let outstanding: Uint128 = granted.iter().map(|(_, amount)| *amount).sum();
+            Some(limit.amount.saturating_sub(outflow.pulled() + outstanding))
+        }
+        _ => None,
+    };
+
     /* Amounts given priority sice the array is sorted
      * portions are calculated after amounts are taken from total
      */
@@ -302,6 +384,10 @@ fn rebalance(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdRe
                             vec![],
                         )?);
                         token_balance += decrease;
+                        granted[i].1 -= decrease;
+                        if let Some(room) = &mut outflow_room {
+                            *room += decrease;
+                        }
                         metrics.push(Metric {
                             action: Action::DecreaseAllowance,
                             context: Context::Rebalance,
@@ -325,6 +411,10 @@ fn rebalance(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdRe
                             vec![],
                         )?);
                         token_balance += cur_allowance;
+                        granted[i].1 = Uint128::zero();
+                        if let Some(room) = &mut outflow_room {
+                            *room += cur_allowance;
+                        }
                         metrics.push(Metric {
                             action: Action::DecreaseAllowance,
                             context: Context::Rebalance,
@@ -371,7 +461,27 @@ fn rebalance(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdRe
                 if increase <= threshold {
                     continue;
                 }
+
+                // outflow limit check, refuse anything over the room left in the window
+                if let Some(room) = &mut outflow_room {
+                    if increase > *room {
+                        let refused = increase - *room;
+                        token_balance += refused;
+                        increase = *room;
+                        metrics.push(Metric {
+                            action: Action::OutflowCapped,
+                            context: Context::Rebalance,
+                            timestamp: env.block.time.seconds(),
+                            token: asset.clone(),
+                            amount: refused,
+                            user: allowance.spender.clone(),
+                        });
+                    }
+                    *room -= increase;
+                }
+
                 if !increase.is_zero() {
+                    granted[i].1 += increase;
                     messages.push(increase_allowance_msg(
                         allowance.spender.clone(),
                         increase,
@@ -402,6 +512,14 @@ fn rebalance(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdRe
     }
     ALLOWANCES.save(deps.storage, asset.clone(), &allowances)?;
 
+    if let Some(mut outflow) = outflow {
+        outflow.granted = granted
+            .into_iter()
+            .filter(|(_, amount)| !amount.is_zero())
+            .collect();
+        OUTFLOW.save(deps.storage, asset.clone(), &outflow)?;
+    }
+
     METRICS.append(deps.storage, env.block.time, &mut metrics)?;
 
     Ok(Response::new()
@@ -498,6 +616,12 @@ pub fn migrate(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> Std
         }
     }
 
+    // Allowances withdrawn above were not pulled
+    if let Some(mut outflow) = OUTFLOW.may_load(deps.storage, asset.clone())? {
+        outflow.granted = vec![];
+        OUTFLOW.save(deps.storage, asset.clone(), &outflow)?;
+    }
+
     // Send full balance to multisig
     let balance = balance_query(
         &deps.querier,
@@ -558,6 +682,77 @@ pub fn set_run_level(
     Ok(Response::new().set_data(to_binary(&ExecuteAnswer::RunLevel { run_level })?))
 }
 
+pub fn set_outflow_limit(
+    deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    asset: Addr,
+    limit: Option<OutflowLimit>,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    validate_admin(
+        &deps.querier,
+        AdminPermissions::TreasuryAdmin,
+        &info.sender,
+        &config.admin_auth,
+    )?;
+
+    if config.timelock.is_zero() {
+        apply_outflow_limit(deps.storage, asset, limit)?;
+        return Ok(
+            Response::new().set_data(to_binary(&ExecuteAnswer::SetOutflowLimit {
+                status: ResponseStatus::Success,
+            })?),
+        );
+    }
+
+    // Fail early, this is checked again when applied
+    check_outflow_limit(deps.storage, &asset, &limit)?;
+
+    queue_change(
+        deps.storage,
+        env,
+        &config,
+        info.sender,
+        QueuedAction::SetOutflowLimit { asset, limit },
+    )
+}
+
+fn check_outflow_limit(
+    storage: &dyn Storage,
+    asset: &Addr,
+    limit: &Option<OutflowLimit>,
+) -> StdResult<()> {
+    if ASSET.may_load(storage, asset.clone())?.is_none() {
+        return Err(StdError::generic_err("Not a registered asset"));
+    }
+
+    if let Some(limit) = limit {
+        outflow_window(&limit.cycle)?;
+    }
+
+    Ok(())
+}
+
+fn apply_outflow_limit(
+    storage: &mut dyn Storage,
+    asset: Addr,
+    limit: Option<OutflowLimit>,
+) -> StdResult<()> {
+    check_outflow_limit(storage, &asset, &limit)?;
+
+    match limit {
+        Some(limit) => OUTFLOW_LIMIT.save(storage, asset, &limit)?,
+        None => {
+            OUTFLOW_LIMIT.remove(storage, asset.clone());
+            OUTFLOW.remove(storage, asset);
+        }
+    }
+
+    Ok(())
+}
+
 pub fn try_register_asset(
     deps: DepsMut,
     env: &Env,
@@ -639,7 +834,7 @@ pub fn register_wrap(
 
 pub fn register_manager(
     deps: DepsMut,
-    _env: &Env,
+    env: &Env,
     info: MessageInfo,
     contract: &mut Contract,
 ) -> StdResult<Response> {
@@ -652,23 +847,43 @@ pub fn register_manager(
         &config.admin_auth,
     )?;
 
-    // Ensure it isn't already registered
+    if config.timelock.is_zero() {
+        apply_register_manager(deps.storage, contract)?;
+        return Ok(
+            Response::new().set_data(to_binary(&ExecuteAnswer::RegisterManager {
+                status: ResponseStatus::Success,
+            })?),
+        );
+    }
+
+    // Fail early, this is checked again when applied
     if let Some(_) = MANAGER.may_load(deps.storage, contract.address.clone())? {
         return Err(StdError::generic_err("Manager already registered"));
     }
 
-    MANAGER.save(deps.storage, contract.address.clone(), &contract)?;
-
-    Ok(
-        Response::new().set_data(to_binary(&ExecuteAnswer::RegisterManager {
-            status: ResponseStatus::Success,
-        })?),
+    queue_change(
+        deps.storage,
+        env,
+        &config,
+        info.sender,
+        QueuedAction::RegisterManager {
+            contract: contract.clone(),
+        },
     )
 }
 
+fn apply_register_manager(storage: &mut dyn Storage, contract: &Contract) -> StdResult<()> {
+    // Ensure it isn't already registered
+    if let Some(_) = MANAGER.may_load(storage, contract.address.clone())? {
+        return Err(StdError::generic_err("Manager already registered"));
+    }
+
+    MANAGER.save(storage, contract.address.clone(), contract)
+}
+
 pub fn allowance(
     deps: DepsMut,
-    _env: &Env,
+    env: &Env,
     info: MessageInfo,
     asset: Addr,
     allowance: Allowance,
@@ -683,7 +898,33 @@ pub fn allowance(
         &config.admin_auth,
     )?;
 
-    if ASSET.may_load(deps.storage, asset.clone())?.is_none() {
+    if config.timelock.is_zero() {
+        apply_allowance(deps.storage, asset, allowance, refresh_now)?;
+        return Ok(
+            Response::new().set_data(to_binary(&ExecuteAnswer::Allowance {
+                status: ResponseStatus::Success,
+            })?),
+        );
+    }
+
+    // Fail early, the full validation runs again when applied
+    check_allowance(deps.storage, &asset, &allowance)?;
+
+    queue_change(
+        deps.storage,
+        env,
+        &config,
+        info.sender,
+        QueuedAction::Allowance {
+            asset,
+            allowance,
+            refresh_now,
+        },
+    )
+}
+
+fn check_allowance(storage: &dyn Storage, asset: &Addr, allowance: &Allowance) -> StdResult<()> {
+    if ASSET.may_load(storage, asset.clone())?.is_none() {
         return Err(StdError::generic_err("Not a registered asset"));
     }
 
@@ -694,8 +935,19 @@ pub fn allowance(
         )));
     }
 
+    Ok(())
+}
+
+fn apply_allowance(
+    storage: &mut dyn Storage,
+    asset: Addr,
+    allowance: Allowance,
+    refresh_now: bool,
+) -> StdResult<()> {
+    check_allowance(storage, &asset, &allowance)?;
+
     let mut allowances = ALLOWANCES
-        .may_load(deps.storage, asset.clone())?
+        .may_load(storage, asset.clone())?
         .unwrap_or(vec![]);
 
     // This will cause allowance refresh asap, changed below if !refresh_now
@@ -755,10 +1007,132 @@ pub fn allowance(
         },
     });
 
-    ALLOWANCES.save(deps.storage, asset, &allowances)?;
+    ALLOWANCES.save(storage, asset, &allowances)
+}
+
+fn queue_change(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    proposer: Addr,
+    action: QueuedAction,
+) -> StdResult<Response> {
+    let (id, execute_after) = push_pending_change(storage, env, config, proposer, action)?;
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::QueueChange {
+            id,
+            execute_after,
+            status: ResponseStatus::Success,
+        })?),
+    )
+}
+
+/* Returns the change id & when it can be applied */
+fn push_pending_change(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    proposer: Addr,
+    action: QueuedAction,
+) -> StdResult<(Uint128, Uint128)> {
+    let id = PENDING_CHANGE_ID
+        .may_load(storage)?
+        .unwrap_or(Uint128::zero());
+    PENDING_CHANGE_ID.save(storage, &(id + Uint128::one()))?;
+
+    let now = Uint128::new(env.block.time.seconds() as u128);
+    let execute_after = now + config.timelock;
+
+    let mut changes = PENDING_CHANGES.may_load(storage)?.unwrap_or(vec![]);
+    changes.push(PendingChange {
+        id,
+        action,
+        proposer,
+        queued_at: now,
+        execute_after,
+    });
+    PENDING_CHANGES.save(storage, &changes)?;
+
+    Ok((id, execute_after))
+}
+
+fn take_pending_change(storage: &mut dyn Storage, id: Uint128) -> StdResult<PendingChange> {
+    let mut changes = PENDING_CHANGES.may_load(storage)?.unwrap_or(vec![]);
+
+    match changes.iter().position(|c| c.id == id) {
+        Some(i) => {
+            let change = changes.remove(i);
+            PENDING_CHANGES.save(storage, &changes)?;
+            Ok(change)
+        }
+        None => Err(StdError::generic_err(format!("No pending change {}", id))),
+    }
+}
+
+pub fn apply_change(
+    deps: DepsMut,
+    env: &Env,
+    _info: MessageInfo,
+    id: Uint128,
+) -> StdResult<Response> {
+    let change = take_pending_change(deps.storage, id)?;
+
+    let now = Uint128::new(env.block.time.seconds() as u128);
+    if now < change.execute_after {
+        return Err(StdError::generic_err(format!(
+            "Change {} is timelocked until {}",
+            id, change.execute_after
+        )));
+    }
+
+    match change.action {
+        QueuedAction::Allowance {
+            asset,
+            allowance,
+            refresh_now,
+        } => apply_allowance(deps.storage, asset, allowance, refresh_now)?,
+        QueuedAction::RegisterManager { contract } => {
+            apply_register_manager(deps.storage, &contract)?
+        }
+        QueuedAction::SetTimelock { timelock } => {
+            let mut config = CONFIG.load(deps.storage)?;
+            config.timelock = timelock;
+            CONFIG.save(deps.storage, &config)?;
+        }
+        QueuedAction::SetMultisig { multisig } => {
+            let mut config = CONFIG.load(deps.storage)?;
+            config.multisig = multisig;
+            CONFIG.save(deps.storage, &config)?;
+        }
+        QueuedAction::SetOutflowLimit { asset, limit } => {
+            apply_outflow_limit(deps.storage, asset, limit)?
+        }
+    };
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::ApplyChange {
+            status: ResponseStatus::Success,
+        })?),
+    )
+}
+
+pub fn cancel_change(
+    deps: DepsMut,
+    _env: &Env,
+    info: MessageInfo,
+    id: Uint128,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.multisig {
+        return Err(StdError::generic_err("Unauthorized"));
+    }
+
+    take_pending_change(deps.storage, id)?;
 
     Ok(
-        Response::new().set_data(to_binary(&ExecuteAnswer::Allowance {
+        Response::new().set_data(to_binary(&ExecuteAnswer::CancelChange {
             status: ResponseStatus::Success,
         })?),
     )
@@ -1,9 +1,16 @@
 use crate::storage::*;
 use shade_protocol::{
     c_std::{Addr, Deps, Env, StdError, StdResult, Uint128},
-    contract_interfaces::dao::{adapter, manager, treasury},
+    contract_interfaces::{
+        dao::{adapter, manager, metrics, treasury, treasury_manager},
+        oracles::{band::ReferenceData, oracle},
+    },
     snip20::helpers::{allowance_query, balance_query},
-    utils::{asset::Contract, cycle::parse_utc_datetime, storage::plus::period_storage::Period},
+    utils::{
+        asset::Contract,
+        storage::plus::period_storage::Period,
+        Query,
+    },
 };
 use std::collections::HashSet;
 
@@ -13,6 +20,36 @@ pub fn config(deps: Deps) -> StdResult<treasury::QueryAnswer> {
     })
 }
 
+pub fn pending_changes(deps: Deps) -> StdResult<treasury::QueryAnswer> {
+    Ok(treasury::QueryAnswer::PendingChanges {
+        changes: PENDING_CHANGES
+            .may_load(deps.storage)?
+            .unwrap_or(vec![]),
+    })
+}
+
+pub fn pending_change(deps: Deps, id: Uint128) -> StdResult<treasury::QueryAnswer> {
+    match PENDING_CHANGES
+        .may_load(deps.storage)?
+        .unwrap_or(vec![])
+        .into_iter()
+        .find(|c| c.id == id)
+    {
+        Some(change) => Ok(treasury::QueryAnswer::PendingChange { change }),
+        None => Err(StdError::generic_err(format!("No pending change {}", id))),
+    }
+}
+
+pub fn outflow_limit(deps: Deps, env: Env, asset: Addr) -> StdResult<treasury::QueryAnswer> {
+    let limit = OUTFLOW_LIMIT.may_load(deps.storage, asset.clone())?;
+    let outflow = match &limit {
+        Some(l) => current_outflow(deps.storage, asset, l, env.block.time.seconds())?.pulled(),
+        None => Uint128::zero(),
+    };
+
+    Ok(treasury::QueryAnswer::OutflowLimit { limit, outflow })
+}
+
 pub fn metrics(
     deps: Deps,
     env: Env,
@@ -20,23 +57,36 @@ pub fn metrics(
     epoch: Option<Uint128>,
     period: Period,
 ) -> StdResult<treasury::QueryAnswer> {
-    if date.is_some() && epoch.is_some() {
-        return Err(StdError::generic_err("cannot pass both epoch and date"));
-    }
-    let key = {
-        if let Some(d) = date {
-            parse_utc_datetime(&d)?.timestamp() as u64
-        } else if let Some(e) = epoch {
-            e.u128() as u64
-        } else {
-            env.block.time.seconds()
-        }
-    };
+    let key = metrics::period_key(&env, date, epoch)?;
     Ok(treasury::QueryAnswer::Metrics {
         metrics: METRICS.load_period(deps.storage, key, period)?,
     })
 }
 
+pub fn metrics_page(
+    deps: Deps,
+    key: u64,
+    period: Period,
+    filter: Option<treasury::MetricFilter>,
+    start_after: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<treasury::QueryAnswer> {
+    let (metrics, next) =
+        metrics::metrics_page(deps.storage, &METRICS, key, period, filter, start_after, limit)?;
+    Ok(treasury::QueryAnswer::MetricsPage { metrics, next })
+}
+
+pub fn metrics_summary(
+    deps: Deps,
+    key: u64,
+    period: Period,
+    token: Option<String>,
+) -> StdResult<treasury::QueryAnswer> {
+    Ok(treasury::QueryAnswer::MetricsSummary {
+        flows: metrics::metrics_summary(deps.storage, &METRICS, key, period, token)?,
+    })
+}
+
 pub fn batch_balance(deps: Deps, env: Env, assets: Vec<Addr>) -> StdResult<Vec<Uint128>> {
     let mut balances = vec![];
     let mut managers: HashSet<Contract> = HashSet::new();
@@ -171,3 +221,142 @@ pub fn allowances(deps: Deps, asset: Addr) -> StdResult<treasury::QueryAnswer> {
         allowances: ALLOWANCES.may_load(deps.storage, asset)?.unwrap_or(vec![]),
     })
 }
+
+pub fn portfolio(deps: Deps, env: Env) -> StdResult<treasury::QueryAnswer> {
+    let config = CONFIG.load(deps.storage)?;
+    let oracle = match config.oracle {
+        Some(o) => o,
+        None => {
+            return Err(StdError::generic_err("No oracle configured"));
+        }
+    };
+    let viewing_key = VIEWING_KEY.load(deps.storage)?;
+
+    let mut assets = vec![];
+    let mut total_value = Uint128::zero();
+
+    for asset in ASSET_LIST.iter(deps.storage) {
+        let full_asset = ASSET.load(deps.storage, asset.clone())?;
+        let precision = Uint128::new(10u128.pow(full_asset.token_info.decimals.into()));
+
+        let price = oracle::QueryMsg::Price {
+            symbol: full_asset.token_info.symbol.clone(),
+        }
+        .query::<ReferenceData>(&deps.querier, &oracle)?
+        .rate;
+
+        let reserves = balance_query(
+            &deps.querier,
+            env.contract.address.clone(),
+            viewing_key.clone(),
+            &full_asset.contract,
+        )?;
+
+        let mut positions = vec![treasury::Position {
+            holder: env.contract.address.clone(),
+            balance: reserves,
+            unbonding: Uint128::zero(),
+            claimable: Uint128::zero(),
+            value: reserves.multiply_ratio(price, precision),
+            adapters: vec![],
+        }];
+
+        // managers report the totals across their adapters
+        for allowance in ALLOWANCES.load(deps.storage, asset.clone())? {
+            if let Some(m) = MANAGER.may_load(deps.storage, allowance.spender)? {
+                let balance = manager::balance_query(
+                    deps.querier,
+                    &asset,
+                    env.contract.address.clone(),
+                    m.clone(),
+                )?;
+                let unbonding = manager::unbonding_query(
+                    deps.querier,
+                    &asset,
+                    env.contract.address.clone(),
+                    m.clone(),
+                )?;
+                let claimable = manager::claimable_query(
+                    deps.querier,
+                    &asset,
+                    env.contract.address.clone(),
+                    m.clone(),
+                )?;
+
+                positions.push(treasury::Position {
+                    holder: m.address.clone(),
+                    balance,
+                    unbonding,
+                    claimable,
+                    value: (balance + unbonding + claimable).multiply_ratio(price, precision),
+                    adapters: adapter_positions(deps, &asset, &m, price, precision)?,
+                });
+            }
+        }
+
+        let amount = positions
+            .iter()
+            .map(|p| p.balance + p.unbonding + p.claimable)
+            .sum::<Uint128>();
+        let value = positions.iter().map(|p| p.value).sum::<Uint128>();
+        total_value += value;
+
+        assets.push(treasury::AssetValuation {
+            asset,
+            symbol: full_asset.token_info.symbol,
+            price,
+            amount,
+            value,
+            positions,
+        });
+    }
+
+    Ok(treasury::QueryAnswer::Portfolio {
+        assets,
+        total_value,
+    })
+}
+
+/* Break a manager's funds down by adapter, these cover all of the manager's holders
+ * so they are informational and not added to the asset totals
+ */
+fn adapter_positions(
+    deps: Deps,
+    asset: &Addr,
+    manager: &Contract,
+    price: Uint128,
+    precision: Uint128,
+) -> StdResult<Vec<treasury::Position>> {
+    let allocations = match treasury_manager::QueryMsg::Allocations {
+        asset: asset.to_string(),
+    }
+    .query(&deps.querier, manager)?
+    {
+        treasury_manager::QueryAnswer::Allocations { allocations } => allocations,
+        _ => {
+            return Err(StdError::generic_err(format!(
+                "Failed to query allocations from {}",
+                manager.address
+            )));
+        }
+    };
+
+    let mut positions = vec![];
+
+    for alloc in allocations {
+        let balance = adapter::balance_query(deps.querier, asset, alloc.contract.clone())?;
+        let unbonding = adapter::unbonding_query(deps.querier, asset, alloc.contract.clone())?;
+        let claimable = adapter::claimable_query(deps.querier, asset, alloc.contract.clone())?;
+
+        positions.push(treasury::Position {
+            holder: alloc.contract.address,
+            balance,
+            unbonding,
+            claimable,
+            value: (balance + unbonding + claimable).multiply_ratio(price, precision),
+            adapters: vec![],
+        });
+    }
+
+    Ok(positions)
+}
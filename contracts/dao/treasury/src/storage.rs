@@ -1,10 +1,19 @@
 use shade_protocol::{
-    c_std::Addr,
-    dao::treasury::{AllowanceMeta, Config, Metric, RunLevel},
+    c_std::{Addr, StdError, StdResult, Storage, Uint128},
+    dao::treasury::{
+        AllowanceMeta,
+        Config,
+        Metric,
+        Outflow,
+        OutflowLimit,
+        PendingChange,
+        RunLevel,
+    },
     secret_storage_plus::{Item, Map},
     snip20::helpers::Snip20Asset,
     utils::{
         asset::Contract,
+        cycle::Cycle,
         storage::plus::{iter_item::IterItem, period_storage::PeriodStorage},
     },
 };
@@ -21,7 +30,53 @@ pub const WRAP: Map<String, Addr> = Map::new("wrap");
 pub const MANAGER: Map<Addr, Contract> = Map::new("managers");
 pub const ALLOWANCES: Map<Addr, Vec<AllowanceMeta>> = Map::new("allowances");
 
+// Changes waiting on the timelock, ordered by id
+pub const PENDING_CHANGES: Item<Vec<PendingChange>> = Item::new("pending_changes");
+pub const PENDING_CHANGE_ID: Item<Uint128> = Item::new("pending_change_id");
+
+pub const OUTFLOW_LIMIT: Map<Addr, OutflowLimit> = Map::new("outflow_limit");
+pub const OUTFLOW: Map<Addr, Outflow> = Map::new("outflow");
+
+/// Length of the rolling outflow window, only fixed length cycles are allowed
+pub fn outflow_window(cycle: &Cycle) -> StdResult<u64> {
+    let seconds = match cycle {
+        Cycle::Seconds { seconds } => seconds.u128(),
+        Cycle::Minutes { minutes } => minutes.u128() * 60,
+        Cycle::Hourly { hours } => hours.u128() * 60 * 60,
+        Cycle::Daily { days } => days.u128() * 60 * 60 * 24,
+        _ => {
+            return Err(StdError::generic_err(
+                "Outflow limit cycle must be seconds, minutes, hours or days",
+            ));
+        }
+    };
+
+    if seconds == 0 || seconds > u64::MAX as u128 {
+        return Err(StdError::generic_err("Invalid outflow limit window"));
+    }
+
+    Ok(seconds as u64)
+}
+
+/// Outflow with the pulls older than the window dropped
+pub fn current_outflow(
+    storage: &dyn Storage,
+    asset: Addr,
+    limit: &OutflowLimit,
+    now: u64,
+) -> StdResult<Outflow> {
+    let window = outflow_window(&limit.cycle)?;
+    let mut outflow = OUTFLOW.may_load(storage, asset)?.unwrap_or(Outflow {
+        pulls: vec![],
+        granted: vec![],
+    });
+    outflow
+        .pulls
+        .retain(|(timestamp, _)| timestamp.saturating_add(window) > now);
+
+    Ok(outflow)
+}
+
 pub const RUN_LEVEL: Item<RunLevel> = Item::new("runlevel");
 
-pub const METRICS: PeriodStorage<Metric> =
-    PeriodStorage::new("metrics-all", "metrics-recent", "metrics-timed");
+pub const METRICS: PeriodStorage<Metric> = PeriodStorage::new("metrics-all", "metrics-timed");
@@ -70,6 +70,7 @@ fn allowance_cycle(
         admin_auth: admin_auth.clone().into(),
         viewing_key: viewing_key.clone(),
         multisig: admin.to_string().clone(),
+        timelock: Uint128::zero(),
     }
     .test_init(Treasury::default(), &mut app, admin.clone(), "treasury", &[
     ])
@@ -114,6 +115,8 @@ fn allowance_cycle(
         amount: deposit,
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, admin.clone(), &[])
@@ -160,6 +163,8 @@ fn allowance_cycle(
         amount: removed,
         memo: None,
         msg: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, spender.clone(), &[])
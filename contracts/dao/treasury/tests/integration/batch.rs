@@ -22,6 +22,7 @@ fn batch_balance_test(amounts: Vec<Uint128>) {
         admin_auth: admin_auth.clone().into(),
         viewing_key: viewing_key.clone(),
         multisig: admin.to_string().clone(),
+        timelock: Uint128::zero(),
     }
     .test_init(Treasury::default(), &mut app, admin.clone(), "treasury", &[
     ])
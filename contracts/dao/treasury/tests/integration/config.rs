@@ -75,6 +75,8 @@ pub fn update_config() {
                 code_hash: "rando3".to_string(),
             },
             multisig: Addr::unchecked("rando"),
+            timelock: Uint128::zero(),
+            oracle: None,
         }
     );
 }
@@ -5,8 +5,10 @@ pub mod config;
 pub mod execute_errors;
 pub mod migration;
 pub mod non_manager_allowances;
+pub mod outflow_limit;
 pub mod query;
 pub mod scrt_staking;
+pub mod timelock;
 pub mod tolerance;
 pub mod treasury;
 pub mod wrap;
@@ -0,0 +1,259 @@
+use shade_multi_test::multi::{admin::init_admin_auth, snip20::Snip20, treasury::Treasury};
+use shade_protocol::{
+    c_std::{to_binary, Addr, BlockInfo, Timestamp, Uint128},
+    contract_interfaces::{
+        dao::{treasury, treasury::AllowanceType},
+        snip20,
+    },
+    multi_test::App,
+    utils::{
+        cycle::Cycle,
+        storage::plus::period_storage::Period,
+        ExecuteCallback,
+        InstantiateCallback,
+        MultiTestable,
+        Query,
+    },
+};
+
+#[test]
+fn outflow_limit() {
+    let mut app = App::default();
+
+    app.set_block(BlockInfo {
+        height: 1,
+        time: Timestamp::from_seconds(0),
+        chain_id: "chain_id".to_string(),
+    });
+
+    let admin = Addr::unchecked("admin");
+    let spenders = vec![Addr::unchecked("spender_a"), Addr::unchecked("spender_b")];
+    let admin_auth = init_admin_auth(&mut app, &admin);
+
+    let viewing_key = "viewing_key".to_string();
+    let deposit = Uint128::new(1000);
+    let allowance = Uint128::new(300);
+    let cap = Uint128::new(400);
+
+    let token = snip20::InstantiateMsg {
+        name: "token".into(),
+        admin: Some("admin".into()),
+        symbol: "TKN".into(),
+        decimals: 6,
+        initial_balances: Some(vec![snip20::InitialBalance {
+            address: admin.to_string().clone(),
+            amount: deposit,
+        }]),
+        prng_seed: to_binary("").ok().unwrap(),
+        config: Some(snip20::InitConfig {
+            public_total_supply: Some(true),
+            enable_deposit: Some(true),
+            enable_redeem: Some(true),
+            enable_mint: Some(false),
+            enable_burn: Some(false),
+            enable_transfer: Some(true),
+        }),
+        query_auth: None,
+    }
+    .test_init(Snip20::default(), &mut app, admin.clone(), "token", &[])
+    .unwrap();
+
+    let treasury = treasury::InstantiateMsg {
+        admin_auth: admin_auth.clone().into(),
+        viewing_key: viewing_key.clone(),
+        multisig: admin.to_string().clone(),
+        timelock: Uint128::zero(),
+    }
+    .test_init(Treasury::default(), &mut app, admin.clone(), "treasury", &[
+    ])
+    .unwrap();
+
+    treasury::ExecuteMsg::RegisterAsset {
+        contract: token.clone().into(),
+    }
+    .test_exec(&treasury, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    for spender in spenders.clone() {
+        treasury::ExecuteMsg::Allowance {
+            asset: token.address.to_string().clone(),
+            allowance: treasury::RawAllowance {
+                spender: spender.to_string(),
+                allowance_type: AllowanceType::Amount,
+                cycle: Cycle::Constant,
+                amount: allowance,
+                tolerance: Uint128::zero(),
+            },
+            refresh_now: true,
+        }
+        .test_exec(&treasury, &mut app, admin.clone(), &[])
+        .unwrap();
+    }
+
+    treasury::ExecuteMsg::SetOutflowLimit {
+        asset: token.address.to_string().clone(),
+        limit: Some(treasury::OutflowLimit {
+            amount: cap,
+            cycle: Cycle::Daily {
+                days: Uint128::one(),
+            },
+        }),
+    }
+    .test_exec(&treasury, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    snip20::ExecuteMsg::Send {
+        recipient: treasury.address.to_string().clone(),
+        recipient_code_hash: None,
+        amount: deposit,
+        msg: None,
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None,
+    }
+    .test_exec(&token, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury::ExecuteMsg::Update {
+        asset: token.address.to_string().clone(),
+    }
+    .test_exec(&treasury, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    let total_allowance = |app: &App| -> Uint128 {
+        spenders
+            .iter()
+            .map(|spender| {
+                match (treasury::QueryMsg::Allowance {
+                    asset: token.address.to_string().clone(),
+                    spender: spender.to_string().clone(),
+                }
+                .test_query(&treasury, app)
+                .unwrap())
+                {
+                    treasury::QueryAnswer::Allowance { amount } => amount,
+                    _ => panic!("query failed"),
+                }
+            })
+            .sum()
+    };
+
+    // 600 requested, only the cap is granted
+    assert_eq!(total_allowance(&app), cap, "Capped allowances");
+
+    match (treasury::QueryMsg::OutflowLimit {
+        asset: token.address.to_string().clone(),
+    }
+    .test_query(&treasury, &app)
+    .unwrap())
+    {
+        treasury::QueryAnswer::OutflowLimit { limit, outflow } => {
+            assert_eq!(limit.unwrap().amount, cap, "Limit");
+            assert!(outflow.is_zero(), "Nothing pulled yet");
+        }
+        _ => panic!("query failed"),
+    };
+
+    match (treasury::QueryMsg::Metrics {
+        date: None,
+        epoch: None,
+        period: Period::Hour,
+    }
+    .test_query(&treasury, &app)
+    .unwrap())
+    {
+        treasury::QueryAnswer::Metrics { metrics } => {
+            let capped = metrics
+                .iter()
+                .filter(|m| m.action == treasury::Action::OutflowCapped)
+                .collect::<Vec<_>>();
+            assert_eq!(capped.len(), 1, "Capped metric");
+            assert_eq!(
+                capped[0].amount,
+                allowance * Uint128::new(2) - cap,
+                "Refused amount"
+            );
+        }
+        _ => panic!("query failed"),
+    };
+
+    let outflow = |app: &App| -> Uint128 {
+        match (treasury::QueryMsg::OutflowLimit {
+            asset: token.address.to_string().clone(),
+        }
+        .test_query(&treasury, app)
+        .unwrap())
+        {
+            treasury::QueryAnswer::OutflowLimit { outflow, .. } => outflow,
+            _ => panic!("query failed"),
+        }
+    };
+
+    // spender_a pulls its full allowance
+    snip20::ExecuteMsg::TransferFrom {
+        owner: treasury.address.to_string().clone(),
+        recipient: spenders[0].to_string().clone(),
+        amount: allowance,
+        memo: None,
+        padding: None,
+    }
+    .test_exec(&token, &mut app, spenders[0].clone(), &[])
+    .unwrap();
+
+    app.set_block(BlockInfo {
+        height: 2,
+        time: Timestamp::from_seconds(60 * 60),
+        chain_id: "chain_id".to_string(),
+    });
+
+    treasury::ExecuteMsg::Update {
+        asset: token.address.to_string().clone(),
+    }
+    .test_exec(&treasury, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // Pulled funds & the outstanding allowance fill the cap, nothing is refilled
+    assert_eq!(outflow(&app), allowance, "Pulled outflow");
+    assert_eq!(
+        total_allowance(&app),
+        cap - allowance,
+        "Allowances after pull"
+    );
+
+    // Still within a day of the pull
+    app.set_block(BlockInfo {
+        height: 3,
+        time: Timestamp::from_seconds(60 * 60 * 24),
+        chain_id: "chain_id".to_string(),
+    });
+
+    treasury::ExecuteMsg::Update {
+        asset: token.address.to_string().clone(),
+    }
+    .test_exec(&treasury, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    assert_eq!(outflow(&app), allowance, "Outflow within window");
+    assert_eq!(
+        total_allowance(&app),
+        cap - allowance,
+        "Allowances within window"
+    );
+
+    // A day after the pull was observed it leaves the window
+    app.set_block(BlockInfo {
+        height: 4,
+        time: Timestamp::from_seconds(60 * 60 * 25),
+        chain_id: "chain_id".to_string(),
+    });
+
+    treasury::ExecuteMsg::Update {
+        asset: token.address.to_string().clone(),
+    }
+    .test_exec(&treasury, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    assert!(outflow(&app).is_zero(), "Outflow after window");
+    assert_eq!(total_allowance(&app), cap, "Allowances after window");
+}
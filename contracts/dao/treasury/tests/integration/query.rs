@@ -1,16 +1,23 @@
-use shade_multi_test::interfaces::{
-    dao::{init_dao, mock_adapter_sub_tokens, update_dao},
-    snip20,
-    treasury,
-    utils::{DeployedContracts, SupportedContracts},
+use mock_oracle::contract as mock_oracle;
+use shade_multi_test::{
+    interfaces::{
+        dao::{init_dao, mock_adapter_sub_tokens, update_dao},
+        snip20,
+        treasury,
+        utils::{DeployedContracts, SupportedContracts},
+    },
+    multi::mock_oracle::MockOracle,
 };
 use shade_protocol::{
-    c_std::{BlockInfo, Timestamp, Uint128},
+    c_std::{Addr, BlockInfo, ContractInfo, Timestamp, Uint128},
     contract_interfaces::dao::{self, treasury::AllowanceType, treasury_manager::AllocationType},
     multi_test::App,
     utils::{
         cycle::{parse_utc_datetime, Cycle},
         storage::plus::period_storage::Period,
+        ExecuteCallback,
+        InstantiateCallback,
+        MultiTestable,
     },
 };
 
@@ -96,6 +103,46 @@ pub fn query() {
         treasury::run_level_query(&app, &contracts,).unwrap(),
         dao::treasury::RunLevel::Normal
     );
+    let date = Some("1995-11-13T00:00:00.00Z".to_string());
+    let increases = Some(dao::treasury::MetricFilter {
+        action: Some(dao::treasury::Action::IncreaseAllowance),
+        ..Default::default()
+    });
+    let (first, next) = treasury::metrics_page_query(
+        &app,
+        &contracts,
+        date.clone(),
+        Period::Day,
+        increases.clone(),
+        None,
+        Some(2),
+    )
+    .unwrap();
+    assert_eq!(first.len(), 2);
+    assert!(next.is_some());
+    assert!(
+        first
+            .iter()
+            .all(|m| m.action == dao::treasury::Action::IncreaseAllowance)
+    );
+    let (rest, _) = treasury::metrics_page_query(
+        &app,
+        &contracts,
+        date.clone(),
+        Period::Day,
+        increases,
+        next,
+        None,
+    )
+    .unwrap();
+    assert_eq!(rest.len(), 2, "4 allowances increased");
+    let flows =
+        treasury::metrics_summary_query(&app, &contracts, date.clone(), Period::Day, Some("SSCRT"))
+            .unwrap();
+    assert_eq!(flows.len(), 1);
+    assert_eq!(flows[0].inflow, Uint128::new(1500));
+    assert_eq!(flows[0].net_inflow, Uint128::new(1500));
+    assert!(flows[0].outflow.is_zero());
     assert!(
         !treasury::metrics_query(
             &app,
@@ -237,4 +284,110 @@ pub fn query() {
         .unwrap()
         .is_empty()
     );
+    // Portfolio valuation needs an oracle
+    assert!(treasury::portfolio_query(&app, &contracts).is_err());
+}
+
+#[test]
+pub fn portfolio() {
+    let mut app = App::default();
+    let mut contracts = DeployedContracts::new();
+    let admin = Addr::unchecked("admin");
+    init_dao(
+        &mut app,
+        "admin",
+        &mut contracts,
+        Uint128::new(1500),
+        "SSCRT",
+        vec![AllowanceType::Amount],
+        vec![Cycle::Constant],
+        vec![Uint128::new(500)],
+        vec![Uint128::zero()],
+        vec![vec![AllocationType::Amount]],
+        vec![vec![Uint128::new(200)]],
+        vec![vec![Uint128::zero()]],
+        true,
+        true,
+    )
+    .unwrap();
+
+    let oracle = mock_oracle::InstantiateMsg {}
+        .test_init(
+            MockOracle::default(),
+            &mut app,
+            admin.clone(),
+            "oracle",
+            &[],
+        )
+        .unwrap();
+    // $2 per token
+    mock_oracle::ExecuteMsg::MockPrice {
+        symbol: "SSCRT".to_string(),
+        price: Uint128::new(2 * 10u128.pow(18)),
+    }
+    .test_exec(&oracle, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    let treasury_contract: ContractInfo = contracts
+        .get(&SupportedContracts::Treasury)
+        .unwrap()
+        .clone()
+        .into();
+    let update_oracle = |app: &mut App, oracle: Option<ContractInfo>, clear: bool| {
+        dao::treasury::ExecuteMsg::UpdateConfig {
+            admin_auth: None,
+            multisig: None,
+            timelock: None,
+            oracle: oracle.map(|o| o.into()),
+            clear_oracle: Some(clear),
+        }
+        .test_exec(&treasury_contract, app, admin.clone(), &[])
+    };
+    update_oracle(&mut app, Some(oracle.clone()), false).unwrap();
+
+    let (assets, total_value) = treasury::portfolio_query(&app, &contracts).unwrap();
+    assert_eq!(assets.len(), 1);
+    let sscrt = &assets[0];
+    assert_eq!(sscrt.symbol, "SSCRT");
+    assert_eq!(sscrt.price, Uint128::new(2 * 10u128.pow(18)));
+    assert_eq!(sscrt.amount, Uint128::new(1500));
+    // 1500 tokens w/ 6 decimals at $2
+    assert_eq!(sscrt.value, Uint128::new(3 * 10u128.pow(15)));
+    assert_eq!(total_value, sscrt.value);
+
+    assert_eq!(sscrt.positions.len(), 2);
+    let reserves = &sscrt.positions[0];
+    assert_eq!(reserves.holder, treasury_contract.address);
+    assert_eq!(reserves.balance, Uint128::new(1000));
+    assert!(reserves.adapters.is_empty());
+
+    let manager = &sscrt.positions[1];
+    assert_eq!(
+        manager.holder,
+        contracts
+            .get(&SupportedContracts::TreasuryManager(0))
+            .unwrap()
+            .address
+    );
+    assert_eq!(manager.balance, Uint128::new(500));
+    assert_eq!(manager.value, Uint128::new(10u128.pow(15)));
+    assert_eq!(manager.adapters.len(), 1);
+    assert_eq!(
+        manager.adapters[0].holder,
+        contracts
+            .get(&SupportedContracts::MockAdapter(0))
+            .unwrap()
+            .address
+    );
+    assert_eq!(manager.adapters[0].balance, Uint128::new(200));
+    assert_eq!(manager.adapters[0].value, Uint128::new(4 * 10u128.pow(14)));
+
+    // Can't set & clear at once
+    assert!(update_oracle(&mut app, Some(oracle), true).is_err());
+    update_oracle(&mut app, None, true).unwrap();
+    assert_eq!(
+        treasury::config_query(&app, &contracts).unwrap().oracle,
+        None
+    );
+    assert!(treasury::portfolio_query(&app, &contracts).is_err());
 }
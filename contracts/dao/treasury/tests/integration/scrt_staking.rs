@@ -93,6 +93,7 @@ fn single_asset_manager_scrt_staking_integration(
         admin_auth: admin_auth.clone().into(),
         viewing_key: viewing_key.clone(),
         multisig: admin.to_string().clone(),
+        timelock: Uint128::zero(),
     }
     .test_init(Treasury::default(), &mut app, admin.clone(), "treasury", &[
     ])
@@ -117,6 +118,8 @@ fn single_asset_manager_scrt_staking_integration(
         owner: manager.address.clone().to_string(),
         sscrt: token.clone().into(),
         validator_bounds: None,
+        weighted_validators: None,
+        unbonding_period: 21 * 24 * 60 * 60,
         viewing_key: viewing_key.clone(),
     }
     .test_init(
@@ -220,6 +223,8 @@ fn single_asset_manager_scrt_staking_integration(
         amount: Uint128::new(deposit.u128()),
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, admin.clone(), &[])
@@ -0,0 +1,364 @@
+use shade_multi_test::multi::{admin::init_admin_auth, snip20::Snip20, treasury::Treasury};
+use shade_protocol::{
+    c_std::{to_binary, Addr, BlockInfo, Timestamp, Uint128},
+    contract_interfaces::{
+        dao::{treasury, treasury::AllowanceType},
+        snip20,
+    },
+    multi_test::App,
+    utils::{
+        asset::RawContract,
+        cycle::Cycle,
+        ExecuteCallback,
+        InstantiateCallback,
+        MultiTestable,
+        Query,
+    },
+};
+
+#[test]
+fn timelocked_changes() {
+    let mut app = App::default();
+
+    app.set_block(BlockInfo {
+        height: 1,
+        time: Timestamp::from_seconds(0),
+        chain_id: "chain_id".to_string(),
+    });
+
+    let admin = Addr::unchecked("admin");
+    let multisig = Addr::unchecked("multisig");
+    let spender = Addr::unchecked("spender");
+    let rando = Addr::unchecked("rando");
+    let admin_auth = init_admin_auth(&mut app, &admin);
+
+    let timelock = Uint128::new(100);
+
+    let token = snip20::InstantiateMsg {
+        name: "token".into(),
+        admin: Some("admin".into()),
+        symbol: "TKN".into(),
+        decimals: 6,
+        initial_balances: None,
+        prng_seed: to_binary("").ok().unwrap(),
+        config: Some(snip20::InitConfig {
+            public_total_supply: Some(true),
+            enable_deposit: Some(true),
+            enable_redeem: Some(true),
+            enable_mint: Some(false),
+            enable_burn: Some(false),
+            enable_transfer: Some(true),
+        }),
+        query_auth: None,
+    }
+    .test_init(Snip20::default(), &mut app, admin.clone(), "token", &[])
+    .unwrap();
+
+    let treasury = treasury::InstantiateMsg {
+        admin_auth: admin_auth.clone().into(),
+        viewing_key: "viewing_key".to_string(),
+        multisig: multisig.to_string().clone(),
+        timelock: Uint128::zero(),
+    }
+    .test_init(Treasury::default(), &mut app, admin.clone(), "treasury", &[
+    ])
+    .unwrap();
+
+    treasury::ExecuteMsg::RegisterAsset {
+        contract: token.clone().into(),
+    }
+    .test_exec(&treasury, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury::ExecuteMsg::UpdateConfig {
+        admin_auth: None,
+        multisig: None,
+        timelock: Some(timelock),
+        oracle: None,
+        clear_oracle: None,
+    }
+    .test_exec(&treasury, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // Queued as change 0
+    treasury::ExecuteMsg::Allowance {
+        asset: token.address.to_string().clone(),
+        allowance: treasury::RawAllowance {
+            spender: spender.clone().to_string(),
+            allowance_type: AllowanceType::Amount,
+            cycle: Cycle::Constant,
+            amount: Uint128::new(100),
+            tolerance: Uint128::zero(),
+        },
+        refresh_now: true,
+    }
+    .test_exec(&treasury, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // Queued as change 1
+    treasury::ExecuteMsg::RegisterManager {
+        contract: RawContract {
+            address: "manager".to_string(),
+            code_hash: "manager_hash".to_string(),
+        },
+    }
+    .test_exec(&treasury, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // Calendar cycles can't be used as a rolling window
+    assert!(
+        treasury::ExecuteMsg::SetOutflowLimit {
+            asset: token.address.to_string().clone(),
+            limit: Some(treasury::OutflowLimit {
+                amount: Uint128::new(100),
+                cycle: Cycle::Monthly {
+                    months: Uint128::one(),
+                },
+            }),
+        }
+        .test_exec(&treasury, &mut app, admin.clone(), &[])
+        .is_err(),
+        "Queued monthly outflow limit"
+    );
+
+    // Queued as change 2
+    treasury::ExecuteMsg::SetOutflowLimit {
+        asset: token.address.to_string().clone(),
+        limit: Some(treasury::OutflowLimit {
+            amount: Uint128::new(100),
+            cycle: Cycle::Daily {
+                days: Uint128::one(),
+            },
+        }),
+    }
+    .test_exec(&treasury, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    match (treasury::QueryMsg::OutflowLimit {
+        asset: token.address.to_string().clone(),
+    }
+    .test_query(&treasury, &app)
+    .unwrap())
+    {
+        treasury::QueryAnswer::OutflowLimit { limit, .. } => {
+            assert!(limit.is_none(), "Outflow limit applied before timelock");
+        }
+        _ => panic!("query failed"),
+    };
+
+    match (treasury::QueryMsg::Allowances {
+        asset: token.address.to_string().clone(),
+    }
+    .test_query(&treasury, &app)
+    .unwrap())
+    {
+        treasury::QueryAnswer::Allowances { allowances } => {
+            assert!(allowances.is_empty(), "Allowance applied before timelock");
+        }
+        _ => panic!("query failed"),
+    };
+
+    match (treasury::QueryMsg::PendingChanges {}
+        .test_query(&treasury, &app)
+        .unwrap())
+    {
+        treasury::QueryAnswer::PendingChanges { changes } => {
+            assert_eq!(changes.len(), 3, "Pending changes");
+            assert_eq!(changes[0].id, Uint128::zero(), "Allowance change id");
+            assert_eq!(changes[0].proposer, admin, "Proposer");
+            assert_eq!(changes[0].execute_after, timelock, "Execute after");
+        }
+        _ => panic!("query failed"),
+    };
+
+    assert!(
+        treasury::ExecuteMsg::ApplyChange { id: Uint128::zero() }
+            .test_exec(&treasury, &mut app, rando.clone(), &[])
+            .is_err(),
+        "Applied before timelock"
+    );
+
+    // Only the multisig can cancel
+    assert!(
+        treasury::ExecuteMsg::CancelChange { id: Uint128::one() }
+            .test_exec(&treasury, &mut app, admin.clone(), &[])
+            .is_err(),
+        "Non-multisig cancelled"
+    );
+    treasury::ExecuteMsg::CancelChange { id: Uint128::one() }
+        .test_exec(&treasury, &mut app, multisig.clone(), &[])
+        .unwrap();
+
+    app.set_block(BlockInfo {
+        height: 2,
+        time: Timestamp::from_seconds(timelock.u128() as u64),
+        chain_id: "chain_id".to_string(),
+    });
+
+    assert!(
+        treasury::ExecuteMsg::ApplyChange { id: Uint128::one() }
+            .test_exec(&treasury, &mut app, rando.clone(), &[])
+            .is_err(),
+        "Applied cancelled change"
+    );
+
+    // Anyone can apply once unlocked
+    treasury::ExecuteMsg::ApplyChange { id: Uint128::zero() }
+        .test_exec(&treasury, &mut app, rando.clone(), &[])
+        .unwrap();
+    treasury::ExecuteMsg::ApplyChange { id: Uint128::new(2) }
+        .test_exec(&treasury, &mut app, rando.clone(), &[])
+        .unwrap();
+
+    match (treasury::QueryMsg::OutflowLimit {
+        asset: token.address.to_string().clone(),
+    }
+    .test_query(&treasury, &app)
+    .unwrap())
+    {
+        treasury::QueryAnswer::OutflowLimit { limit, .. } => {
+            assert_eq!(limit.unwrap().amount, Uint128::new(100), "Outflow limit");
+        }
+        _ => panic!("query failed"),
+    };
+
+    match (treasury::QueryMsg::Allowances {
+        asset: token.address.to_string().clone(),
+    }
+    .test_query(&treasury, &app)
+    .unwrap())
+    {
+        treasury::QueryAnswer::Allowances { allowances } => {
+            assert_eq!(allowances.len(), 1, "Allowance applied");
+            assert_eq!(allowances[0].spender, spender, "Allowance spender");
+        }
+        _ => panic!("query failed"),
+    };
+
+    match (treasury::QueryMsg::PendingChanges {}
+        .test_query(&treasury, &app)
+        .unwrap())
+    {
+        treasury::QueryAnswer::PendingChanges { changes } => {
+            assert!(changes.is_empty(), "Pending changes remaining");
+        }
+        _ => panic!("query failed"),
+    };
+}
+
+#[test]
+fn timelocked_config() {
+    let mut app = App::default();
+
+    app.set_block(BlockInfo {
+        height: 1,
+        time: Timestamp::from_seconds(0),
+        chain_id: "chain_id".to_string(),
+    });
+
+    let admin = Addr::unchecked("admin");
+    let multisig = Addr::unchecked("multisig");
+    let rando = Addr::unchecked("rando");
+    let admin_auth = init_admin_auth(&mut app, &admin);
+
+    let timelock = Uint128::new(100);
+
+    let treasury = treasury::InstantiateMsg {
+        admin_auth: admin_auth.clone().into(),
+        viewing_key: "viewing_key".to_string(),
+        multisig: multisig.to_string().clone(),
+        timelock,
+    }
+    .test_init(Treasury::default(), &mut app, admin.clone(), "treasury", &[
+    ])
+    .unwrap();
+
+    // Raising the timelock applies immediately
+    treasury::ExecuteMsg::UpdateConfig {
+        admin_auth: None,
+        multisig: None,
+        timelock: Some(Uint128::new(200)),
+        oracle: None,
+        clear_oracle: None,
+    }
+    .test_exec(&treasury, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // Queued as changes 0 & 1
+    treasury::ExecuteMsg::UpdateConfig {
+        admin_auth: None,
+        multisig: Some(rando.to_string()),
+        timelock: Some(Uint128::zero()),
+        oracle: None,
+        clear_oracle: None,
+    }
+    .test_exec(&treasury, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    match (treasury::QueryMsg::Config {}
+        .test_query(&treasury, &app)
+        .unwrap())
+    {
+        treasury::QueryAnswer::Config { config } => {
+            assert_eq!(config.multisig, multisig, "Multisig changed before timelock");
+            assert_eq!(config.timelock, Uint128::new(200), "Timelock");
+        }
+        _ => panic!("query failed"),
+    };
+
+    match (treasury::QueryMsg::PendingChanges {}
+        .test_query(&treasury, &app)
+        .unwrap())
+    {
+        treasury::QueryAnswer::PendingChanges { changes } => {
+            assert_eq!(changes.len(), 3, "Pending changes");
+            assert_eq!(
+                changes[0].action,
+                treasury::QueuedAction::SetMultisig {
+                    multisig: rando.clone()
+                },
+                "Multisig change"
+            );
+            assert_eq!(
+                changes[1].action,
+                treasury::QueuedAction::SetTimelock {
+                    timelock: Uint128::zero()
+                },
+                "Timelock change"
+            );
+            assert_eq!(changes[1].execute_after, Uint128::new(200), "Execute after");
+        }
+        _ => panic!("query failed"),
+    };
+
+    assert!(
+        treasury::ExecuteMsg::ApplyChange { id: Uint128::one() }
+            .test_exec(&treasury, &mut app, rando.clone(), &[])
+            .is_err(),
+        "Applied before timelock"
+    );
+
+    app.set_block(BlockInfo {
+        height: 2,
+        time: Timestamp::from_seconds(200),
+        chain_id: "chain_id".to_string(),
+    });
+
+    treasury::ExecuteMsg::ApplyChange { id: Uint128::zero() }
+        .test_exec(&treasury, &mut app, rando.clone(), &[])
+        .unwrap();
+    treasury::ExecuteMsg::ApplyChange { id: Uint128::one() }
+        .test_exec(&treasury, &mut app, rando.clone(), &[])
+        .unwrap();
+
+    match (treasury::QueryMsg::Config {}
+        .test_query(&treasury, &app)
+        .unwrap())
+    {
+        treasury::QueryAnswer::Config { config } => {
+            assert_eq!(config.multisig, rando, "Multisig");
+            assert_eq!(config.timelock, Uint128::zero(), "Timelock");
+        }
+        _ => panic!("query failed"),
+    };
+}
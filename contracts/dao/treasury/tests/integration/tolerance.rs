@@ -56,6 +56,7 @@ fn underfunded_tolerance(
         admin_auth: admin_auth.clone().into(),
         viewing_key: viewing_key.clone(),
         multisig: admin.to_string().clone(),
+        timelock: Uint128::zero(),
     }
     .test_init(Treasury::default(), &mut app, admin.clone(), "treasury", &[
     ])
@@ -100,6 +101,8 @@ fn underfunded_tolerance(
         amount: deposit,
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, admin.clone(), &[])
@@ -133,6 +136,8 @@ fn underfunded_tolerance(
         amount: added,
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, admin.clone(), &[])
@@ -266,6 +271,7 @@ fn overfunded_tolerance(
         admin_auth: admin_auth.clone().into(),
         viewing_key: viewing_key.clone(),
         multisig: admin.to_string().clone(),
+        timelock: Uint128::zero(),
     }
     .test_init(Treasury::default(), &mut app, admin.clone(), "treasury", &[
     ])
@@ -310,6 +316,8 @@ fn overfunded_tolerance(
         amount: deposit,
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, admin.clone(), &[])
@@ -3,6 +3,7 @@ use shade_protocol::{
     c_std::{
         shd_entry_point,
         to_binary,
+        Addr,
         Binary,
         Deps,
         DepsMut,
@@ -13,6 +14,7 @@ use shade_protocol::{
     },
     dao::{
         manager,
+        metrics,
         treasury_manager::{Config, ExecuteMsg, Holding, InstantiateMsg, QueryMsg, Status},
     },
 };
@@ -78,6 +80,19 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             let holder = deps.api.addr_validate(&holder)?;
             execute::remove_holder(deps, &env, info, holder)
         }
+        ExecuteMsg::EmergencyUnwind { asset, adapters } => {
+            let asset = deps.api.addr_validate(&asset)?;
+            let adapters = match adapters {
+                Some(adapters) => Some(
+                    adapters
+                        .iter()
+                        .map(|a| deps.api.addr_validate(a))
+                        .collect::<StdResult<Vec<Addr>>>()?,
+                ),
+                None => None,
+            };
+            execute::emergency_unwind(deps, &env, info, asset, adapters)
+        }
         ExecuteMsg::Manager(a) => match a {
             manager::SubExecuteMsg::Unbond { asset, amount } => {
                 let asset = deps.api.addr_validate(&asset)?;
@@ -118,6 +133,33 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             epoch,
             period,
         } => to_binary(&query::metrics(deps, env, date, epoch, period)?),
+        QueryMsg::MetricsPage {
+            date,
+            epoch,
+            period,
+            filter,
+            start_after,
+            limit,
+        } => {
+            let key = metrics::period_key(&env, date, epoch)?;
+            to_binary(&query::metrics_page(
+                deps,
+                key,
+                period,
+                filter,
+                start_after,
+                limit,
+            )?)
+        }
+        QueryMsg::MetricsSummary {
+            date,
+            epoch,
+            period,
+            token,
+        } => {
+            let key = metrics::period_key(&env, date, epoch)?;
+            to_binary(&query::metrics_summary(deps, key, period, token)?)
+        }
 
         QueryMsg::Manager(a) => match a {
             manager::SubQueryMsg::Balance { asset, holder } => {
@@ -12,6 +12,7 @@ use shade_protocol::{
         Response,
         StdError,
         StdResult,
+        Storage,
         Uint128,
     },
     dao::{
@@ -20,6 +21,7 @@ use shade_protocol::{
             Action,
             Allocation,
             AllocationMeta,
+            AllocationStatus,
             AllocationTempData,
             AllocationType,
             Balance,
@@ -239,6 +241,7 @@ pub fn allocate(
         amount: allocation.amount,
         alloc_type: allocation.alloc_type,
         tolerance: allocation.tolerance,
+        status: AllocationStatus::Active,
     });
 
     // ensure that the portion allocations don't go above 100%
@@ -414,13 +417,16 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
     let mut stale_allocs = vec![];
     let mut messages = vec![];
     let mut adapter_info = vec![];
+    // claimed funds from unwinding adapters being returned to the holders
+    let mut swept = Uint128::zero();
+    let mut metrics = vec![];
 
     /* this loop has 2 purposes
      * - check for stale allocaitons that need to be removed
      * - fill the amount_total and portion_total vars with data
      */
     for (i, a) in allocations.clone().iter().enumerate() {
-        let bal = adapter::balance_query(
+        let mut bal = adapter::balance_query(
             deps.querier,
             &full_asset.contract.address,
             a.contract.clone(),
@@ -445,7 +451,13 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
                 &full_asset.contract.address.clone(),
                 a.contract.clone(),
             )?);
-            unbonding += claimable;
+            if a.status == AllocationStatus::Unwinding {
+                // swept back to the holders below, the claim above lands first
+                swept += claimable;
+                bal = bal.saturating_sub(claimable);
+            } else {
+                unbonding += claimable;
+            }
         }
         // if all these values are zero we can safely drop the alloc
         if bal.is_zero()
@@ -483,6 +495,41 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
         ALLOCATIONS.save(deps.storage, asset.clone(), &allocations)?;
     }
 
+    // swept funds are returned to the holders pro rata and are no longer unbonding
+    if !swept.is_zero() {
+        for (holder, amount) in sweep_shares(deps.storage, &asset, swept)? {
+            let mut holding = HOLDING.load(deps.storage, holder.clone())?;
+            if let Some(b) = holding.balances.iter_mut().find(|b| b.token == asset) {
+                b.amount = b.amount.checked_sub(amount)?;
+            }
+            HOLDING.save(deps.storage, holder.clone(), &holding)?;
+
+            messages.push(send_msg(
+                holder.clone(),
+                amount,
+                None,
+                None,
+                None,
+                &full_asset.contract.clone(),
+            )?);
+            metrics.push(Metric {
+                action: Action::SendFunds,
+                context: Context::Unwind,
+                timestamp: env.block.time.seconds(),
+                token: asset.clone(),
+                amount,
+                user: holder,
+            });
+        }
+
+        let unbondings = UNBONDINGS.load(deps.storage, full_asset.contract.address.clone())?;
+        UNBONDINGS.save(
+            deps.storage,
+            full_asset.contract.address.clone(),
+            &unbondings.checked_sub(swept)?,
+        )?;
+    }
+
     // the holder is the entity that actually holds the tokens that the treasury manager can spend
     // holder_unbonding represents how much the holder has currently asked to unbond
     let mut holder_unbonding = Uint128::zero();
@@ -514,7 +561,6 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
     // Batch send_from actions
     let mut send_from_actions = vec![];
     let mut send_actions = vec![];
-    let mut metrics = vec![];
 
     let key = VIEWING_KEY.load(deps.storage)?;
 
@@ -613,6 +659,7 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
                         amount: desired_input,
                         msg: None,
                         memo: None,
+                        decoys: None,
                     });
                     metrics.push(Metric {
                         action: Action::SendFunds,
@@ -638,6 +685,7 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
                         amount: balance,
                         msg: None,
                         memo: None,
+                        decoys: None,
                     });
                     metrics.push(Metric {
                         action: Action::SendFunds,
@@ -1361,6 +1409,139 @@ pub fn unbond(
     }
 }
 
+/* Splits swept funds across the holders by their balance of the asset,
+ * the rounding remainder goes to the largest holder
+ */
+fn sweep_shares(
+    storage: &dyn Storage,
+    asset: &Addr,
+    swept: Uint128,
+) -> StdResult<Vec<(Addr, Uint128)>> {
+    let mut balances = vec![];
+    for holder in HOLDERS.load(storage)? {
+        let holding = HOLDING.load(storage, holder.clone())?;
+        if let Some(b) = holding.balances.iter().find(|b| b.token == *asset) {
+            if !b.amount.is_zero() {
+                balances.push((holder, b.amount));
+            }
+        }
+    }
+
+    let total: Uint128 = balances.iter().map(|(_, amount)| *amount).sum();
+    if swept > total {
+        return Err(StdError::generic_err(format!(
+            "Swept {} exceeds the {} held for {}",
+            swept, total, asset
+        )));
+    }
+
+    let mut shares: Vec<(Addr, Uint128)> = balances
+        .iter()
+        .map(|(holder, amount)| (holder.clone(), swept.multiply_ratio(*amount, total)))
+        .collect();
+
+    let remainder = swept - shares.iter().map(|(_, share)| *share).sum::<Uint128>();
+    if let Some(i) = (0..balances.len()).max_by_key(|i| balances[*i].1) {
+        shares[i].1 += remainder;
+    }
+
+    Ok(shares
+        .into_iter()
+        .filter(|(_, share)| !share.is_zero())
+        .collect())
+}
+
+pub fn emergency_unwind(
+    deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    asset: Addr,
+    adapters: Option<Vec<Addr>>,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    validate_admin(
+        &deps.querier,
+        AdminPermissions::TreasuryManager,
+        &info.sender,
+        &config.admin_auth,
+    )?;
+
+    let full_asset = match ASSETS.may_load(deps.storage, asset.clone())? {
+        Some(a) => a,
+        None => {
+            return Err(StdError::generic_err("Unrecognized asset"));
+        }
+    };
+
+    let mut allocations = ALLOCATIONS
+        .may_load(deps.storage, asset.clone())?
+        .unwrap_or_default();
+
+    if let Some(adapters) = &adapters {
+        for adapter in adapters {
+            if !allocations.iter().any(|a| a.contract.address == *adapter) {
+                return Err(StdError::generic_err(format!(
+                    "{} has no allocation for {}",
+                    adapter, asset
+                )));
+            }
+        }
+    }
+
+    let mut messages = vec![];
+    let mut metrics = vec![];
+    let mut unbonded = Uint128::zero();
+
+    for alloc in allocations.iter_mut() {
+        if let Some(adapters) = &adapters {
+            if !adapters.contains(&alloc.contract.address) {
+                continue;
+            }
+        }
+
+        let unbondable = adapter::unbondable_query(deps.querier, &asset, alloc.contract.clone())?;
+        if !unbondable.is_zero() {
+            messages.push(adapter::unbond_msg(
+                &asset,
+                unbondable,
+                alloc.contract.clone(),
+            )?);
+            metrics.push(Metric {
+                action: Action::Unbond,
+                context: Context::Unwind,
+                timestamp: env.block.time.seconds(),
+                token: asset.clone(),
+                amount: unbondable,
+                user: alloc.contract.address.clone(),
+            });
+            unbonded += unbondable;
+        }
+
+        alloc.amount = Uint128::zero();
+        alloc.status = AllocationStatus::Unwinding;
+    }
+
+    ALLOCATIONS.save(deps.storage, asset.clone(), &allocations)?;
+
+    // tracked like update unbondings, independent of any holder
+    let unbondings = UNBONDINGS.load(deps.storage, full_asset.contract.address.clone())?;
+    UNBONDINGS.save(
+        deps.storage,
+        full_asset.contract.address.clone(),
+        &(unbondings + unbonded),
+    )?;
+
+    METRICS.append(deps.storage, env.block.time, &mut metrics)?;
+
+    Ok(Response::new().add_messages(messages).set_data(to_binary(
+        &ExecuteAnswer::EmergencyUnwind {
+            status: ResponseStatus::Success,
+            unbonded,
+        },
+    )?))
+}
+
 pub fn add_holder(
     deps: DepsMut,
     env: &Env,
@@ -1,9 +1,9 @@
 use crate::storage::*;
 use shade_protocol::{
     c_std::{Addr, Deps, Env, StdError, StdResult, Uint128},
-    dao::{adapter, manager, treasury_manager},
+    dao::{adapter, manager, metrics, treasury_manager},
     snip20::helpers::{allowance_query, balance_query},
-    utils::{cycle::parse_utc_datetime, storage::plus::period_storage::Period},
+    utils::storage::plus::period_storage::Period,
 };
 
 pub fn config(deps: Deps) -> StdResult<treasury_manager::QueryAnswer> {
@@ -19,23 +19,36 @@ pub fn metrics(
     epoch: Option<Uint128>,
     period: Period,
 ) -> StdResult<treasury_manager::QueryAnswer> {
-    if date.is_some() && epoch.is_some() {
-        return Err(StdError::generic_err("cannot pass both epoch and date"));
-    }
-    let key = {
-        if let Some(d) = date {
-            parse_utc_datetime(&d)?.timestamp() as u64
-        } else if let Some(e) = epoch {
-            e.u128() as u64
-        } else {
-            env.block.time.seconds()
-        }
-    };
+    let key = metrics::period_key(&env, date, epoch)?;
     Ok(treasury_manager::QueryAnswer::Metrics {
         metrics: METRICS.load_period(deps.storage, key, period)?,
     })
 }
 
+pub fn metrics_page(
+    deps: Deps,
+    key: u64,
+    period: Period,
+    filter: Option<treasury_manager::MetricFilter>,
+    start_after: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<treasury_manager::QueryAnswer> {
+    let (metrics, next) =
+        metrics::metrics_page(deps.storage, &METRICS, key, period, filter, start_after, limit)?;
+    Ok(treasury_manager::QueryAnswer::MetricsPage { metrics, next })
+}
+
+pub fn metrics_summary(
+    deps: Deps,
+    key: u64,
+    period: Period,
+    token: Option<String>,
+) -> StdResult<treasury_manager::QueryAnswer> {
+    Ok(treasury_manager::QueryAnswer::MetricsSummary {
+        flows: metrics::metrics_summary(deps.storage, &METRICS, key, period, token)?,
+    })
+}
+
 pub fn pending_allowance(
     deps: Deps,
     env: Env,
@@ -17,5 +17,4 @@ pub const HOLDERS: Item<Vec<Addr>> = Item::new("holders");
 pub const HOLDING: Map<Addr, Holding> = Map::new("holding");
 pub const UNBONDINGS: Map<Addr, Uint128> = Map::new("unbondings");
 
-pub const METRICS: PeriodStorage<Metric> =
-    PeriodStorage::new("metrics-all", "metrics-recent", "metrics-timed");
+pub const METRICS: PeriodStorage<Metric> = PeriodStorage::new("metrics-all", "metrics-timed");
@@ -94,6 +94,8 @@ fn batch_balance_test(balances: Vec<Uint128>) {
             amount: bal,
             msg: None,
             memo: None,
+            decoys: None,
+            entropy: None,
             padding: None,
         }
         .test_exec(&token, &mut app, admin.clone(), &[])
@@ -0,0 +1,213 @@
+use shade_multi_test::interfaces::{
+    dao::{self, init_dao, mock_adapter_complete_unbonding},
+    snip20,
+    treasury,
+    treasury_manager,
+    utils::{DeployedContracts, SupportedContracts},
+};
+use shade_protocol::{
+    c_std::Uint128,
+    contract_interfaces::dao::{
+        treasury::AllowanceType,
+        treasury_manager::{AllocationStatus, AllocationType},
+    },
+    multi_test::App,
+    utils::cycle::Cycle,
+};
+
+#[test]
+pub fn emergency_unwind() {
+    let mut app = App::default();
+    let mut contracts = DeployedContracts::new();
+    init_dao(
+        &mut app,
+        "admin",
+        &mut contracts,
+        Uint128::new(1000),
+        "SSCRT",
+        vec![AllowanceType::Amount],
+        vec![Cycle::Constant],
+        vec![Uint128::new(500)],
+        vec![Uint128::zero()],
+        vec![vec![AllocationType::Amount, AllocationType::Amount]],
+        vec![vec![Uint128::new(100), Uint128::new(200)]],
+        vec![vec![Uint128::zero(); 2]],
+        false,
+        true,
+    )
+    .unwrap();
+
+    let treasury_address = contracts
+        .get(&SupportedContracts::Treasury)
+        .unwrap()
+        .address
+        .to_string();
+    let reserves = treasury::reserves_query(&app, &contracts, "SSCRT").unwrap();
+
+    // A second holder with as much deposited as the treasury
+    treasury_manager::register_holder_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        SupportedContracts::TreasuryManager(0),
+        "holder",
+    )
+    .unwrap();
+    snip20::send_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        "SSCRT",
+        "holder".to_string(),
+        Uint128::new(300),
+        None,
+    )
+    .unwrap();
+    snip20::send_exec(
+        &mut app,
+        "holder",
+        &contracts,
+        "SSCRT",
+        contracts
+            .get(&SupportedContracts::TreasuryManager(0))
+            .unwrap()
+            .address
+            .to_string(),
+        Uint128::new(300),
+        None,
+    )
+    .unwrap();
+
+    // Non-admin can't unwind
+    assert!(
+        treasury_manager::emergency_unwind_exec(
+            &mut app,
+            "not_admin",
+            &contracts,
+            "SSCRT",
+            SupportedContracts::TreasuryManager(0),
+            None,
+        )
+        .is_err()
+    );
+
+    treasury_manager::emergency_unwind_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        "SSCRT",
+        SupportedContracts::TreasuryManager(0),
+        Some(vec![SupportedContracts::MockAdapter(0)]),
+    )
+    .unwrap();
+
+    let allocations = treasury_manager::allocations_query(
+        &app,
+        &contracts,
+        SupportedContracts::TreasuryManager(0),
+        "SSCRT",
+    )
+    .unwrap();
+    let unwound = contracts
+        .get(&SupportedContracts::MockAdapter(0))
+        .unwrap()
+        .address
+        .clone();
+    for alloc in allocations {
+        if alloc.contract.address == unwound {
+            assert_eq!(alloc.status, AllocationStatus::Unwinding);
+            assert!(alloc.amount.is_zero());
+        } else {
+            assert_eq!(alloc.status, AllocationStatus::Active);
+            assert_eq!(alloc.amount, Uint128::new(200));
+        }
+    }
+    assert_eq!(
+        dao::unbonding_query(&app, &contracts, "SSCRT", SupportedContracts::MockAdapter(0))
+            .unwrap(),
+        Uint128::new(100)
+    );
+
+    // Nothing claimable yet, nothing swept
+    treasury_manager::update_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        "SSCRT",
+        SupportedContracts::TreasuryManager(0),
+    )
+    .unwrap();
+    assert_eq!(
+        treasury::reserves_query(&app, &contracts, "SSCRT").unwrap(),
+        reserves
+    );
+
+    mock_adapter_complete_unbonding(
+        &mut app,
+        "admin",
+        &contracts,
+        SupportedContracts::MockAdapter(0),
+    )
+    .unwrap();
+    treasury_manager::update_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        "SSCRT",
+        SupportedContracts::TreasuryManager(0),
+    )
+    .unwrap();
+
+    // Claimed funds are split between the holders by their balances
+    assert_eq!(
+        treasury::reserves_query(&app, &contracts, "SSCRT").unwrap(),
+        reserves + Uint128::new(50)
+    );
+    assert!(
+        dao::balance_query(&app, &contracts, "SSCRT", SupportedContracts::MockAdapter(0))
+            .unwrap()
+            .is_zero()
+    );
+    assert_eq!(
+        dao::balance_query(&app, &contracts, "SSCRT", SupportedContracts::MockAdapter(1))
+            .unwrap(),
+        Uint128::new(200)
+    );
+    let holding = treasury_manager::holding_query(
+        &app,
+        &contracts,
+        SupportedContracts::TreasuryManager(0),
+        treasury_address,
+    )
+    .unwrap();
+    assert_eq!(holding.balances[0].amount, Uint128::new(250));
+    let holding = treasury_manager::holding_query(
+        &app,
+        &contracts,
+        SupportedContracts::TreasuryManager(0),
+        "holder".to_string(),
+    )
+    .unwrap();
+    assert_eq!(holding.balances[0].amount, Uint128::new(250));
+
+    // Fully unwound allocation is dropped on the next update
+    treasury_manager::update_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        "SSCRT",
+        SupportedContracts::TreasuryManager(0),
+    )
+    .unwrap();
+    assert_eq!(
+        treasury_manager::allocations_query(
+            &app,
+            &contracts,
+            SupportedContracts::TreasuryManager(0),
+            "SSCRT",
+        )
+        .unwrap()
+        .len(),
+        1
+    );
+}
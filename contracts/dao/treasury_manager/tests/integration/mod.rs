@@ -1,5 +1,6 @@
 pub mod batch;
 pub mod config;
+pub mod emergency_unwind;
 pub mod execute_error;
 pub mod holder_integration;
 pub mod multiple_holders;
@@ -88,6 +88,8 @@ fn single_holder_scrt_staking_adapter(
         owner: manager.address.to_string().clone().into(),
         sscrt: token.clone().into(),
         validator_bounds: None,
+        weighted_validators: None,
+        unbonding_period: 21 * 24 * 60 * 60,
         viewing_key: viewing_key.clone(),
     }
     .test_init(
@@ -170,6 +172,8 @@ fn single_holder_scrt_staking_adapter(
         amount: deposit,
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, holder.clone(), &[])
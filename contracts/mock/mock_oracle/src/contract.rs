@@ -0,0 +1,86 @@
+use shade_protocol::{
+    c_std::{
+        shd_entry_point,
+        to_binary,
+        Binary,
+        Deps,
+        DepsMut,
+        Env,
+        MessageInfo,
+        Response,
+        StdError,
+        StdResult,
+        Uint128,
+    },
+    contract_interfaces::oracles::band::ReferenceData,
+    cosmwasm_schema::cw_serde,
+    utils::{
+        storage::plus::Map,
+        ExecuteCallback,
+        InstantiateCallback,
+    },
+};
+pub use shade_protocol::contract_interfaces::oracles::oracle::QueryMsg;
+
+const PRICES: Map<'static, String, Uint128> = Map::new("prices");
+
+#[cw_serde]
+pub struct InstantiateMsg {}
+
+impl InstantiateCallback for InstantiateMsg {
+    const BLOCK_SIZE: usize = 256;
+}
+
+#[shd_entry_point]
+pub fn instantiate(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    MockPrice { symbol: String, price: Uint128 },
+}
+
+impl ExecuteCallback for ExecuteMsg {
+    const BLOCK_SIZE: usize = 256;
+}
+
+#[shd_entry_point]
+pub fn execute(deps: DepsMut, _env: Env, _info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::MockPrice { symbol, price } => {
+            PRICES.save(deps.storage, symbol, &price)?;
+            Ok(Response::default())
+        }
+    }
+}
+
+fn price(deps: Deps, symbol: String) -> StdResult<ReferenceData> {
+    match PRICES.may_load(deps.storage, symbol.clone())? {
+        Some(rate) => Ok(ReferenceData {
+            rate,
+            last_updated_base: 0,
+            last_updated_quote: 0,
+        }),
+        None => Err(StdError::generic_err(format!("No price for {}", symbol))),
+    }
+}
+
+#[shd_entry_point]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Price { symbol } => to_binary(&price(deps, symbol)?),
+        QueryMsg::Prices { symbols } => to_binary(
+            &symbols
+                .into_iter()
+                .map(|s| price(deps, s))
+                .collect::<StdResult<Vec<_>>>()?,
+        ),
+        QueryMsg::Config {} => Err(StdError::generic_err("Not supported")),
+    }
+}
@@ -381,6 +381,31 @@ pub fn query(
                 estimated_time_of_maturity_for_next_batch: None,
             })
         },
+        QueryMsg::Holdings { address, key, .. } => {
+            if key != ViewingKey::load(deps.storage, address.clone())?.0 {
+                return Err(StdError::generic_err("unauthorized"));
+            }
+
+            let price = Price::load(deps.storage)?.0;
+            let time = Time::load(deps.storage)?.0;
+            let mut claimable = Uint128::zero();
+            let mut unbonding = Uint128::zero();
+            for u in Unbondings::load(deps.storage, address.clone()).unwrap_or_default().0 {
+                if u.maturity <= time {
+                    claimable += u.amount;
+                } else {
+                    unbonding += u.amount;
+                }
+            }
+            let balance = Balance::load(deps.storage, address).unwrap_or_default().0;
+
+            to_binary(&QueryAnswer::Holdings {
+                claimable_scrt: claimable.multiply_ratio(price, Uint128::new(1_000_000)),
+                unbonding_scrt: unbonding.multiply_ratio(price, Uint128::new(1_000_000)),
+                token_balance: balance,
+                token_balance_value_in_scrt: balance.multiply_ratio(price, Uint128::new(1_000_000)),
+            })
+        },
     }
 }
 
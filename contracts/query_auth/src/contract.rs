@@ -20,7 +20,12 @@ use shade_protocol::{
         QueryMsg,
         RngSeed,
     },
-    utils::{pad_handle_result, pad_query_result, storage::plus::ItemStorage},
+    utils::{
+        asset::validate_vec,
+        pad_handle_result,
+        pad_query_result,
+        storage::plus::ItemStorage,
+    },
 };
 
 // Used to pad up responses for better privacy.
@@ -51,12 +56,18 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
         ContractStatus::Default => {}
         // No permit interactions
         ContractStatus::DisablePermit => match msg {
-            ExecuteMsg::BlockPermitKey { .. } => return Err(StdError::generic_err("unauthorized")),
+            ExecuteMsg::BlockPermitKey { .. }
+            | ExecuteMsg::BlockPermitKeys { .. }
+            | ExecuteMsg::BlockAllPermits { .. } => {
+                return Err(StdError::generic_err("unauthorized"));
+            }
             _ => {}
         },
         // No VK interactions
         ContractStatus::DisableVK => match msg {
-            ExecuteMsg::CreateViewingKey { .. } | ExecuteMsg::SetViewingKey { .. } => {
+            ExecuteMsg::CreateViewingKey { .. }
+            | ExecuteMsg::SetViewingKey { .. }
+            | ExecuteMsg::CreateSubKey { .. } => {
                 return Err(StdError::generic_err("unauthorized"));
             }
             _ => {}
@@ -65,7 +76,10 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
         ContractStatus::DisableAll => match msg {
             ExecuteMsg::CreateViewingKey { .. }
             | ExecuteMsg::SetViewingKey { .. }
-            | ExecuteMsg::BlockPermitKey { .. } => {
+            | ExecuteMsg::CreateSubKey { .. }
+            | ExecuteMsg::BlockPermitKey { .. }
+            | ExecuteMsg::BlockPermitKeys { .. }
+            | ExecuteMsg::BlockAllPermits { .. } => {
                 return Err(StdError::generic_err("unauthorized"));
             }
             _ => {}
@@ -84,26 +98,48 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             ExecuteMsg::CreateViewingKey { entropy, .. } => {
                 handle::try_create_viewing_key(deps, env, info, entropy)
             }
+            ExecuteMsg::CreateSubKey {
+                entropy,
+                expiration,
+                contracts,
+                ..
+            } => {
+                let contracts = match contracts {
+                    None => None,
+                    Some(contracts) => Some(validate_vec(deps.api, contracts)?),
+                };
+                handle::try_create_sub_key(deps, env, info, entropy, expiration, contracts)
+            }
+            ExecuteMsg::RevokeSubKey { key, .. } => {
+                handle::try_revoke_sub_key(deps, env, info, key)
+            }
             ExecuteMsg::BlockPermitKey { key, .. } => {
                 handle::try_block_permit_key(deps, env, info, key)
             }
+            ExecuteMsg::BlockPermitKeys { keys, .. } => {
+                handle::try_block_permit_keys(deps, env, info, keys)
+            }
+            ExecuteMsg::BlockAllPermits { .. } => handle::try_block_all_permits(deps, env, info),
         },
         RESPONSE_BLOCK_SIZE,
     )
 }
 
 #[shd_entry_point]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     let status = ContractStatus::load(deps.storage)?;
     match status {
         // Do nothing
         ContractStatus::Default => {}
         // No permit interactions
-        ContractStatus::DisablePermit => {
-            if let QueryMsg::ValidatePermit { .. } = msg {
+        ContractStatus::DisablePermit => match msg {
+            QueryMsg::ValidatePermit { .. }
+            | QueryMsg::ValidatePermitFor { .. }
+            | QueryMsg::PermitEpoch { .. } => {
                 return Err(StdError::generic_err("unauthorized"));
             }
-        }
+            _ => {}
+        },
         // No VK interactions
         ContractStatus::DisableVK => {
             if let QueryMsg::ValidateViewingKey { .. } = msg {
@@ -122,8 +158,18 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     pad_query_result(
         to_binary(&match msg {
             QueryMsg::Config { .. } => query::config(deps)?,
-            QueryMsg::ValidateViewingKey { user, key } => query::validate_vk(deps, user, key)?,
+            QueryMsg::ValidateViewingKey {
+                user,
+                key,
+                contract,
+            } => query::validate_vk(deps, env, user, key, contract)?,
             QueryMsg::ValidatePermit { permit } => query::validate_permit(deps, permit)?,
+            QueryMsg::ValidatePermitFor {
+                permit,
+                required_permissions,
+                contract,
+            } => query::validate_permit_for(deps, permit, required_permissions, contract)?,
+            QueryMsg::PermitEpoch { user } => query::permit_epoch(deps, user)?,
         }),
         RESPONSE_BLOCK_SIZE,
     )
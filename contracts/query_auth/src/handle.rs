@@ -1,8 +1,8 @@
 use shade_protocol::{
     admin::helpers::{validate_admin, AdminPermissions},
-    c_std::{to_binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult},
+    c_std::{to_binary, Addr, Deps, DepsMut, Env, MessageInfo, Response, StdResult},
     contract_interfaces::query_auth::{
-        auth::{HashedKey, Key, PermitKey},
+        auth::{HashedKey, Key, PermitEpoch, PermitKey, SubKey},
         Admin,
         ContractStatus,
         ExecuteAnswer,
@@ -80,6 +80,38 @@ pub fn try_set_viewing_key(
     Ok(Response::new().set_data(to_binary(&ExecuteAnswer::SetViewingKey { status: Success })?))
 }
 
+pub fn try_create_sub_key(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    entropy: String,
+    expiration: Option<u64>,
+    contracts: Option<Vec<Addr>>,
+) -> StdResult<Response> {
+    let seed = RngSeed::load(deps.storage)?.0;
+
+    let key = Key::generate(&info, &env, seed.as_slice(), &entropy.as_ref());
+
+    SubKey {
+        expiration,
+        contracts,
+    }
+    .save(deps.storage, (info.sender, key.hash().to_vec()))?;
+
+    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::CreateSubKey { key: key.0 })?))
+}
+
+pub fn try_revoke_sub_key(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    key: String,
+) -> StdResult<Response> {
+    SubKey::remove(deps.storage, (info.sender, Key(key).hash().to_vec()));
+
+    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::RevokeSubKey { status: Success })?))
+}
+
 pub fn try_block_permit_key(
     deps: DepsMut,
     _env: Env,
@@ -93,3 +125,29 @@ pub fn try_block_permit_key(
         })?),
     )
 }
+
+pub fn try_block_permit_keys(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    keys: Vec<String>,
+) -> StdResult<Response> {
+    for key in keys {
+        PermitKey::revoke(deps.storage, key, info.sender.clone())?;
+    }
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::BlockPermitKeys {
+            status: Success,
+        })?),
+    )
+}
+
+pub fn try_block_all_permits(deps: DepsMut, _env: Env, info: MessageInfo) -> StdResult<Response> {
+    let epoch = PermitEpoch::bump(deps.storage, info.sender)?;
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::BlockAllPermits {
+            status: Success,
+            epoch,
+        })?),
+    )
+}
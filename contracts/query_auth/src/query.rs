@@ -1,9 +1,10 @@
 use shade_protocol::{
-    c_std::{Addr, Deps, StdResult},
+    c_std::{from_binary, Addr, Deps, Env, StdResult},
     contract_interfaces::query_auth::{
-        auth::{Key, PermitKey},
+        auth::{Key, PermitEpoch, PermitKey},
         Admin,
         ContractStatus,
+        PermitScope,
         QueryAnswer,
         QueryPermit,
     },
@@ -17,9 +18,21 @@ pub fn config(deps: Deps) -> StdResult<QueryAnswer> {
     })
 }
 
-pub fn validate_vk(deps: Deps, user: Addr, key: String) -> StdResult<QueryAnswer> {
+pub fn validate_vk(
+    deps: Deps,
+    env: Env,
+    user: Addr,
+    key: String,
+    contract: Option<Addr>,
+) -> StdResult<QueryAnswer> {
     Ok(QueryAnswer::ValidateViewingKey {
-        is_valid: Key::verify(deps.storage, user, key)?,
+        is_valid: Key::verify_with_scope(
+            deps.storage,
+            user,
+            key,
+            env.block.time.seconds(),
+            contract.as_ref(),
+        )?,
     })
 }
 
@@ -28,6 +41,50 @@ pub fn validate_permit(deps: Deps, permit: QueryPermit) -> StdResult<QueryAnswer
 
     Ok(QueryAnswer::ValidatePermit {
         user: user.clone(),
-        is_revoked: PermitKey::may_load(deps.storage, (user, permit.params.key))?.is_some(),
+        is_revoked: is_permit_revoked(deps, &user, &permit)?,
+    })
+}
+
+pub fn validate_permit_for(
+    deps: Deps,
+    permit: QueryPermit,
+    required_permissions: Vec<String>,
+    contract: Addr,
+) -> StdResult<QueryAnswer> {
+    let user = permit.validate(deps.api, None)?.as_addr(None)?;
+
+    let is_valid = match from_binary::<PermitScope>(&permit.params.data) {
+        // Data is not a scope so nothing can be verified
+        Err(_) => false,
+        Ok(scope) => {
+            !is_permit_revoked(deps, &user, &permit)?
+                && required_permissions
+                    .iter()
+                    .all(|perm| scope.permissions.contains(perm))
+                && match scope.contracts {
+                    None => true,
+                    Some(contracts) => contracts.contains(&contract),
+                }
+        }
+    };
+
+    Ok(QueryAnswer::ValidatePermitFor { user, is_valid })
+}
+
+fn is_permit_revoked(deps: Deps, user: &Addr, permit: &QueryPermit) -> StdResult<bool> {
+    if PermitKey::may_load(deps.storage, (user.clone(), permit.params.key.clone()))?.is_some() {
+        return Ok(true);
+    }
+
+    // Only scoped permits can carry an epoch, other layouts could never be re-signed for it
+    match from_binary::<PermitScope>(&permit.params.data) {
+        Ok(scope) => PermitEpoch::is_revoked(deps.storage, user.clone(), scope.epoch),
+        Err(_) => Ok(false),
+    }
+}
+
+pub fn permit_epoch(deps: Deps, user: Addr) -> StdResult<QueryAnswer> {
+    Ok(QueryAnswer::PermitEpoch {
+        epoch: PermitEpoch::current(deps.storage, user)?,
     })
 }
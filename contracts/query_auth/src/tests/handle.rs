@@ -1,4 +1,12 @@
-use crate::tests::{get_config, init_contract, validate_permit, validate_vk};
+use crate::tests::{
+    get_config,
+    init_contract,
+    permit_epoch,
+    validate_permit,
+    validate_permit_for,
+    validate_scoped_vk,
+    validate_vk,
+};
 use shade_protocol::{
     c_std::{from_binary, Addr},
     contract_interfaces::{query_auth, query_auth::ContractStatus},
@@ -104,8 +112,17 @@ fn runstate_block_permits() {
             .is_ok()
     );
 
+    let msg = query_auth::ExecuteMsg::BlockAllPermits { padding: None };
+
+    assert!(
+        &msg.test_exec(&auth, &mut chain, Addr::unchecked("user"), &[])
+            .is_err()
+    );
+
     assert!(validate_permit(&chain, &auth).is_err());
 
+    assert!(validate_permit_for(&chain, &auth, vec![], "contract").is_err());
+
     assert!(validate_vk(&chain, &auth, "user", "key").is_ok());
 }
 
@@ -251,6 +268,83 @@ fn create_vk() {
     assert!(validate_vk(&chain, &auth, "user", &key).unwrap());
 }
 
+#[test]
+fn create_sub_key() {
+    let (mut chain, auth) = init_contract().unwrap();
+
+    query_auth::ExecuteMsg::SetViewingKey {
+        key: "password".to_string(),
+        padding: None,
+    }
+    .test_exec(&auth, &mut chain, Addr::unchecked("user"), &[])
+    .unwrap();
+
+    let expiration = chain.block_info().time.seconds() + 100;
+
+    let data = query_auth::ExecuteMsg::CreateSubKey {
+        entropy: "blah".to_string(),
+        expiration: Some(expiration),
+        contracts: Some(vec!["dashboard".to_string()]),
+        padding: None,
+    }
+    .test_exec(&auth, &mut chain, Addr::unchecked("user"), &[])
+    .unwrap()
+    .data
+    .unwrap();
+
+    let msg: query_auth::ExecuteAnswer = from_binary(&data).unwrap();
+    let key = match msg {
+        query_auth::ExecuteAnswer::CreateSubKey { key } => key,
+        _ => panic!("Wrong response"),
+    };
+
+    // Only within scope
+    assert!(validate_scoped_vk(&chain, &auth, "user", &key, Some("dashboard")).unwrap());
+    assert!(!validate_scoped_vk(&chain, &auth, "user", &key, Some("other")).unwrap());
+    assert!(!validate_vk(&chain, &auth, "user", &key).unwrap());
+    assert!(!validate_scoped_vk(&chain, &auth, "other_user", &key, Some("dashboard")).unwrap());
+
+    // Main key is unaffected
+    assert!(validate_scoped_vk(&chain, &auth, "user", "password", Some("other")).unwrap());
+
+    chain.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    assert!(!validate_scoped_vk(&chain, &auth, "user", &key, Some("dashboard")).unwrap());
+}
+
+#[test]
+fn revoke_sub_key() {
+    let (mut chain, auth) = init_contract().unwrap();
+
+    let data = query_auth::ExecuteMsg::CreateSubKey {
+        entropy: "blah".to_string(),
+        expiration: None,
+        contracts: None,
+        padding: None,
+    }
+    .test_exec(&auth, &mut chain, Addr::unchecked("user"), &[])
+    .unwrap()
+    .data
+    .unwrap();
+
+    let msg: query_auth::ExecuteAnswer = from_binary(&data).unwrap();
+    let key = match msg {
+        query_auth::ExecuteAnswer::CreateSubKey { key } => key,
+        _ => panic!("Wrong response"),
+    };
+
+    assert!(validate_vk(&chain, &auth, "user", &key).unwrap());
+
+    query_auth::ExecuteMsg::RevokeSubKey {
+        key: key.clone(),
+        padding: None,
+    }
+    .test_exec(&auth, &mut chain, Addr::unchecked("user"), &[])
+    .unwrap();
+
+    assert!(!validate_vk(&chain, &auth, "user", &key).unwrap());
+}
+
 #[test]
 fn block_permit_key() {
     let (mut chain, auth) = init_contract().unwrap();
@@ -272,3 +366,58 @@ fn block_permit_key() {
 
     assert!(validate_permit(&chain, &auth).unwrap().1);
 }
+
+#[test]
+fn block_permit_keys() {
+    let (mut chain, auth) = init_contract().unwrap();
+
+    query_auth::ExecuteMsg::BlockPermitKeys {
+        keys: vec!["other_key".to_string(), "key".to_string()],
+        padding: None,
+    }
+    .test_exec(
+        &auth,
+        &mut chain,
+        Addr::unchecked("secret19rla95xfp22je7hyxv7h0nhm6cwtwahu69zraq"),
+        &[],
+    )
+    .unwrap();
+
+    assert!(validate_permit(&chain, &auth).unwrap().1);
+}
+
+#[test]
+fn block_all_permits() {
+    let (mut chain, auth) = init_contract().unwrap();
+    let signer = "secret19rla95xfp22je7hyxv7h0nhm6cwtwahu69zraq";
+
+    // Another user's epoch doesn't apply
+    query_auth::ExecuteMsg::BlockAllPermits { padding: None }
+        .test_exec(&auth, &mut chain, Addr::unchecked("user"), &[])
+        .unwrap();
+
+    assert_eq!(permit_epoch(&chain, &auth, "user").unwrap(), 1);
+    assert_eq!(permit_epoch(&chain, &auth, signer).unwrap(), 0);
+    assert!(!validate_permit(&chain, &auth).unwrap().1);
+
+    // The test permit's data isn't a permit scope so epochs don't apply to it
+    let data = query_auth::ExecuteMsg::BlockAllPermits { padding: None }
+        .test_exec(&auth, &mut chain, Addr::unchecked(signer), &[])
+        .unwrap()
+        .data
+        .unwrap();
+
+    match from_binary(&data).unwrap() {
+        query_auth::ExecuteAnswer::BlockAllPermits { epoch, .. } => assert_eq!(epoch, 1),
+        _ => panic!("Wrong response"),
+    };
+    assert_eq!(permit_epoch(&chain, &auth, signer).unwrap(), 1);
+    assert!(!validate_permit(&chain, &auth).unwrap().1);
+
+    // Still usable after blocking again
+    query_auth::ExecuteMsg::BlockAllPermits { padding: None }
+        .test_exec(&auth, &mut chain, Addr::unchecked(signer), &[])
+        .unwrap();
+    assert_eq!(permit_epoch(&chain, &auth, signer).unwrap(), 2);
+    assert!(!validate_permit(&chain, &auth).unwrap().1);
+}
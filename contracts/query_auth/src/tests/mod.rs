@@ -51,6 +51,7 @@ pub fn init_contract() -> StdResult<(App, ContractInfo)> {
             admin::RegistryAction::GrantAccess {
                 permissions: vec![AdminPermissions::QueryAuthAdmin.into_string()],
                 user: "admin".to_string(),
+                expiration: None,
             },
         ],
     }
@@ -94,9 +95,20 @@ pub fn get_config(chain: &App, auth: &ContractInfo) -> StdResult<(Contract, Cont
 }
 
 pub fn validate_vk(chain: &App, auth: &ContractInfo, user: &str, key: &str) -> StdResult<bool> {
+    validate_scoped_vk(chain, auth, user, key, None)
+}
+
+pub fn validate_scoped_vk(
+    chain: &App,
+    auth: &ContractInfo,
+    user: &str,
+    key: &str,
+    contract: Option<&str>,
+) -> StdResult<bool> {
     let query: query_auth::QueryAnswer = query_auth::QueryMsg::ValidateViewingKey {
         user: Addr::unchecked(user),
         key: key.to_string(),
+        contract: contract.map(Addr::unchecked),
     }
     .test_query(&auth, &chain)?;
 
@@ -117,3 +129,37 @@ pub fn validate_permit(chain: &App, auth: &ContractInfo) -> StdResult<(Addr, boo
         _ => Err(StdError::generic_err("VK not found")),
     }
 }
+
+pub fn validate_permit_for(
+    chain: &App,
+    auth: &ContractInfo,
+    required_permissions: Vec<&str>,
+    contract: &str,
+) -> StdResult<(Addr, bool)> {
+    let query: query_auth::QueryAnswer = query_auth::QueryMsg::ValidatePermitFor {
+        permit: get_permit(),
+        required_permissions: required_permissions
+            .into_iter()
+            .map(|perm| perm.to_string())
+            .collect(),
+        contract: Addr::unchecked(contract),
+    }
+    .test_query(&auth, &chain)?;
+
+    match query {
+        query_auth::QueryAnswer::ValidatePermitFor { user, is_valid } => Ok((user, is_valid)),
+        _ => Err(StdError::generic_err("Permit not found")),
+    }
+}
+
+pub fn permit_epoch(chain: &App, auth: &ContractInfo, user: &str) -> StdResult<u64> {
+    let query: query_auth::QueryAnswer = query_auth::QueryMsg::PermitEpoch {
+        user: Addr::unchecked(user),
+    }
+    .test_query(&auth, &chain)?;
+
+    match query {
+        query_auth::QueryAnswer::PermitEpoch { epoch } => Ok(epoch),
+        _ => Err(StdError::generic_err("Epoch not found")),
+    }
+}
@@ -1,4 +1,11 @@
-use crate::tests::{get_config, get_permit, init_contract, validate_permit, validate_vk};
+use crate::tests::{
+    get_config,
+    get_permit,
+    init_contract,
+    validate_permit,
+    validate_permit_for,
+    validate_vk,
+};
 use shade_protocol::{
     c_std::{Addr},
     contract_interfaces::{query_auth, query_auth::ContractStatus},
@@ -48,3 +55,17 @@ fn permit_validation() {
         Addr::unchecked("secret19rla95xfp22je7hyxv7h0nhm6cwtwahu69zraq")
     );
 }
+
+#[test]
+fn permit_for_validation() {
+    let (chain, auth) = init_contract().unwrap();
+
+    // The test permit's data isn't a permit scope
+    let (user, is_valid) = validate_permit_for(&chain, &auth, vec![], "contract").unwrap();
+
+    assert!(!is_valid);
+    assert_eq!(
+        user,
+        Addr::unchecked("secret19rla95xfp22je7hyxv7h0nhm6cwtwahu69zraq")
+    );
+}
@@ -50,8 +50,9 @@ use shade_protocol::{
         Permission,
         QueryMsg,
         QueryWithPermit,
+        transaction_history::TxFilter,
     },
-    query_auth::helpers::{authenticate_permit, authenticate_vk, PermitAuthentication},
+    query_auth::helpers::{authenticate_permit, authenticate_scoped_vk, PermitAuthentication},
     snip20::{errors::permit_not_found, manager::QueryAuth, PermitParams},
     utils::{
         asset::validate_vec,
@@ -107,10 +108,16 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
                 recipient,
                 amount,
                 memo,
+                decoys,
+                entropy,
                 ..
             } => {
                 let recipient = deps.api.addr_validate(recipient.as_str())?;
-                try_transfer(deps, env, info, recipient, amount, memo)
+                let decoys = match decoys {
+                    None => None,
+                    Some(decoys) => Some(validate_vec(deps.api, decoys)?),
+                };
+                try_transfer(deps, env, info, recipient, amount, memo, decoys, entropy)
             }
 
             ExecuteMsg::Send {
@@ -119,9 +126,15 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
                 amount,
                 msg,
                 memo,
+                decoys,
+                entropy,
                 ..
             } => {
                 let recipient = deps.api.addr_validate(recipient.as_str())?;
+                let decoys = match decoys {
+                    None => None,
+                    Some(decoys) => Some(validate_vec(deps.api, decoys)?),
+                };
                 try_send(
                     deps,
                     env,
@@ -131,14 +144,18 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
                     amount,
                     memo,
                     msg,
+                    decoys,
+                    entropy,
                 )
             }
 
-            ExecuteMsg::BatchTransfer { actions, .. } => {
-                try_batch_transfer(deps, env, info, actions)
-            }
+            ExecuteMsg::BatchTransfer {
+                actions, entropy, ..
+            } => try_batch_transfer(deps, env, info, actions, entropy),
 
-            ExecuteMsg::BatchSend { actions, .. } => try_batch_send(deps, env, info, actions),
+            ExecuteMsg::BatchSend {
+                actions, entropy, ..
+            } => try_batch_send(deps, env, info, actions, entropy),
 
             ExecuteMsg::Burn { amount, memo, .. } => try_burn(deps, env, info, amount, memo),
 
@@ -263,7 +280,7 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
     )
 }
 
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     pad_query_result(
         to_binary(&match msg {
             QueryMsg::TokenInfo {} => query::token_info(deps)?,
@@ -334,6 +351,40 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
 
                         query::allowance(deps, owner, spender)?
                     }
+                    QueryWithPermit::AllowancesGiven {
+                        owner,
+                        page,
+                        page_size,
+                    } => {
+                        let owner = deps.api.addr_validate(&owner)?;
+
+                        if !params.contains(Permission::Allowance) {
+                            return Err(unauthorized_permit(Permission::Allowance));
+                        }
+
+                        if owner != account {
+                            return Err(unauthorized_permit(Permission::Allowance));
+                        }
+
+                        query::allowances_given(deps, owner, page.unwrap_or(0), page_size)?
+                    }
+                    QueryWithPermit::AllowancesReceived {
+                        spender,
+                        page,
+                        page_size,
+                    } => {
+                        let spender = deps.api.addr_validate(&spender)?;
+
+                        if !params.contains(Permission::Allowance) {
+                            return Err(unauthorized_permit(Permission::Allowance));
+                        }
+
+                        if spender != account {
+                            return Err(unauthorized_permit(Permission::Allowance));
+                        }
+
+                        query::allowances_received(deps, spender, page.unwrap_or(0), page_size)?
+                    }
                     QueryWithPermit::Balance {} => {
                         if !params.contains(Permission::Balance) {
                             return Err(unauthorized_permit(Permission::Balance));
@@ -353,7 +404,11 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
                             page_size,
                         )?
                     }
-                    QueryWithPermit::TransactionHistory { page, page_size } => {
+                    QueryWithPermit::TransactionHistory {
+                        page,
+                        page_size,
+                        filter,
+                    } => {
                         if !params.contains(Permission::History) {
                             return Err(unauthorized_permit(Permission::History));
                         }
@@ -363,6 +418,7 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
                             account.clone(),
                             page.unwrap_or(0),
                             page_size,
+                            validate_filter(&deps, filter)?,
                         )?
                     }
                 }
@@ -376,17 +432,43 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 } => {
                     let owner = deps.api.addr_validate(&owner)?;
                     let spender = deps.api.addr_validate(&spender)?;
-                    if try_authenticate_vk(&deps, owner.clone(), key.clone())?
-                        || try_authenticate_vk(&deps, spender.clone(), key)?
+                    if try_authenticate_vk(&deps, &env, owner.clone(), key.clone())?
+                        || try_authenticate_vk(&deps, &env, spender.clone(), key)?
                     {
                         query::allowance(deps, owner, spender)?
                     } else {
                         return Err(invalid_viewing_key());
                     }
                 }
+                QueryMsg::AllowancesGiven {
+                    owner,
+                    key,
+                    page,
+                    page_size,
+                } => {
+                    let owner = deps.api.addr_validate(&owner)?;
+                    if try_authenticate_vk(&deps, &env, owner.clone(), key)? {
+                        query::allowances_given(deps, owner, page.unwrap_or(0), page_size)?
+                    } else {
+                        return Err(invalid_viewing_key());
+                    }
+                }
+                QueryMsg::AllowancesReceived {
+                    spender,
+                    key,
+                    page,
+                    page_size,
+                } => {
+                    let spender = deps.api.addr_validate(&spender)?;
+                    if try_authenticate_vk(&deps, &env, spender.clone(), key)? {
+                        query::allowances_received(deps, spender, page.unwrap_or(0), page_size)?
+                    } else {
+                        return Err(invalid_viewing_key());
+                    }
+                }
                 QueryMsg::Balance { address, key } => {
                     let address = deps.api.addr_validate(&address)?;
-                    if try_authenticate_vk(&deps, address.clone(), key.clone())? {
+                    if try_authenticate_vk(&deps, &env, address.clone(), key.clone())? {
                         query::balance(deps, address.clone())?
                     } else {
                         return Err(invalid_viewing_key());
@@ -399,7 +481,7 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
                     page_size,
                 } => {
                     let address = deps.api.addr_validate(&address)?;
-                    if try_authenticate_vk(&deps, address.clone(), key.clone())? {
+                    if try_authenticate_vk(&deps, &env, address.clone(), key.clone())? {
                         query::transfer_history(
                             deps,
                             address.clone(),
@@ -415,14 +497,16 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
                     key,
                     page,
                     page_size,
+                    filter,
                 } => {
                     let address = deps.api.addr_validate(&address)?;
-                    if try_authenticate_vk(&deps, address.clone(), key.clone())? {
+                    if try_authenticate_vk(&deps, &env, address.clone(), key.clone())? {
                         query::transaction_history(
                             deps,
                             address.clone(),
                             page.unwrap_or(0),
                             page_size,
+                            validate_filter(&deps, filter)?,
                         )?
                     } else {
                         return Err(invalid_viewing_key());
@@ -435,9 +519,27 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     )
 }
 
-fn try_authenticate_vk(deps: &Deps, address: Addr, key: String) -> StdResult<bool> {
+fn validate_filter(deps: &Deps, filter: Option<TxFilter>) -> StdResult<Option<TxFilter>> {
+    Ok(match filter {
+        None => None,
+        Some(mut filter) => {
+            if let Some(counterparty) = filter.counterparty {
+                filter.counterparty = Some(deps.api.addr_validate(&counterparty)?.into_string());
+            }
+            Some(filter)
+        }
+    })
+}
+
+fn try_authenticate_vk(deps: &Deps, env: &Env, address: Addr, key: String) -> StdResult<bool> {
     match QueryAuth::may_load(deps.storage)? {
         None => Key::verify(deps.storage, address, key),
-        Some(authenticator) => authenticate_vk(address, key, &deps.querier, &authenticator.0),
+        Some(authenticator) => authenticate_scoped_vk(
+            address,
+            key,
+            env.contract.address.clone(),
+            &deps.querier,
+            &authenticator.0,
+        ),
     }
 }
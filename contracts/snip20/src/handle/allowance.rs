@@ -1,17 +1,75 @@
 use crate::handle::transfers::{try_send_impl, try_transfer_impl};
 use shade_protocol::{
-    c_std::{to_binary, Addr, Binary, DepsMut, Env, MessageInfo, Response, StdResult, Uint128},
+    c_std::{
+        to_binary,
+        Addr,
+        Binary,
+        DepsMut,
+        Env,
+        MessageInfo,
+        Response,
+        StdResult,
+        Storage,
+        Uint128,
+    },
     contract_interfaces::snip20::{
         batch,
         manager::{Allowance, CoinInfo},
+        transaction_history::{store_decrease_allowance, store_increase_allowance},
         ExecuteAnswer,
     },
     utils::{
         generic_response::ResponseStatus::Success,
-        storage::plus::{ItemStorage, MapStorage},
+        storage::plus::{ItemStorage, Map, MapStorage},
     },
 };
 
+// Every address an account has given allowances to or received them from,
+// appended the first time an (owner, spender) allowance is created
+pub struct AllowanceIndex {
+    total: Map<'static, Addr, u32>,
+    accounts: Map<'static, (Addr, u32), Addr>,
+}
+
+pub const ALLOWANCES_GIVEN: AllowanceIndex = AllowanceIndex {
+    total: Map::new("allowances-given-total-"),
+    accounts: Map::new("allowances-given-"),
+};
+
+pub const ALLOWANCES_RECEIVED: AllowanceIndex = AllowanceIndex {
+    total: Map::new("allowances-received-total-"),
+    accounts: Map::new("allowances-received-"),
+};
+
+impl AllowanceIndex {
+    pub fn append(&self, storage: &mut dyn Storage, account: &Addr, other: &Addr) -> StdResult<()> {
+        let id = self.total.may_load(storage, account.clone())?.unwrap_or(0);
+        self.total.save(storage, account.clone(), &(id + 1))?;
+        self.accounts.save(storage, (account.clone(), id), other)?;
+
+        Ok(())
+    }
+
+    pub fn get(
+        &self,
+        storage: &dyn Storage,
+        account: &Addr,
+        page: u32,
+        page_size: u32,
+    ) -> StdResult<(Vec<Addr>, u32)> {
+        let total = self.total.may_load(storage, account.clone())?.unwrap_or(0);
+        let start_index = page.saturating_mul(page_size).min(total);
+        let end_index = start_index.saturating_add(page_size).min(total);
+
+        let mut accounts = vec![];
+        for index in start_index..end_index {
+            accounts.push(self.accounts.load(storage, (account.clone(), index))?);
+        }
+
+        Ok((accounts, total))
+    }
+}
+
 pub fn try_increase_allowance(
     deps: DepsMut,
     env: Env,
@@ -21,8 +79,14 @@ pub fn try_increase_allowance(
     expiration: Option<u64>,
 ) -> StdResult<Response> {
     let owner = info.sender;
-    let mut allowance = Allowance::may_load(deps.storage, (owner.clone(), spender.clone()))?
-        .unwrap_or(Allowance::default());
+    let mut allowance = match Allowance::may_load(deps.storage, (owner.clone(), spender.clone()))? {
+        Some(allowance) => allowance,
+        None => {
+            ALLOWANCES_GIVEN.append(deps.storage, &owner, &spender)?;
+            ALLOWANCES_RECEIVED.append(deps.storage, &spender, &owner)?;
+            Allowance::default()
+        }
+    };
 
     // Reset allowance if its expired
     if allowance.is_expired(&env.block) {
@@ -41,6 +105,9 @@ pub fn try_increase_allowance(
 
     allowance.save(deps.storage, (owner.clone(), spender.clone()))?;
 
+    let denom = CoinInfo::load(deps.storage)?.symbol;
+    store_increase_allowance(deps.storage, &owner, &spender, amount, denom, &env.block)?;
+
     Ok(
         Response::new().set_data(to_binary(&ExecuteAnswer::IncreaseAllowance {
             spender,
@@ -78,6 +145,9 @@ pub fn try_decrease_allowance(
 
     allowance.save(deps.storage, (owner.clone(), spender.clone()))?;
 
+    let denom = CoinInfo::load(deps.storage)?.symbol;
+    store_decrease_allowance(deps.storage, &owner, &spender, amount, denom, &env.block)?;
+
     Ok(
         Response::new().set_data(to_binary(&ExecuteAnswer::IncreaseAllowance {
             spender,
@@ -104,6 +174,7 @@ pub fn try_transfer_from(
         &recipient,
         amount,
         memo,
+        None,
         denom,
         &env.block,
     )?;
@@ -127,6 +198,7 @@ pub fn try_batch_transfer_from(
             &deps.api.addr_validate(action.recipient.as_str())?,
             action.amount,
             action.memo,
+            None,
             denom.clone(),
             block,
         )?;
@@ -162,6 +234,7 @@ pub fn try_send_from(
         amount,
         memo,
         msg,
+        None,
         denom,
         &env.block,
     )?;
@@ -192,6 +265,7 @@ pub fn try_batch_send_from(
             action.amount,
             action.memo,
             action.msg,
+            None,
             denom.clone(),
             &env.block,
         )?;
@@ -15,12 +15,14 @@ use shade_protocol::{
     contract_interfaces::snip20::{
         batch,
         errors::transfer_disabled,
-        manager::{Allowance, Balance, CoinInfo, Config, ReceiverHash},
-        transaction_history::store_transfer,
+        manager::{Allowance, Balance, CoinInfo, Config, RandSeed, ReceiverHash},
+        transaction_history::{store_send, store_transfer},
         ExecuteAnswer,
         ReceiverHandleMsg,
     },
     utils::{
+        asset::validate_vec,
+        crypto::Prng,
         generic_response::ResponseStatus::Success,
         storage::plus::{ItemStorage, MapStorage},
         ExecuteCallback,
@@ -28,16 +30,35 @@ use shade_protocol::{
     Contract,
 };
 
-pub fn try_transfer_impl(
+// Shuffles the recipient among any decoys, seeded with the caller's entropy
+pub fn decoy_rng(
+    storage: &dyn Storage,
+    env: &Env,
+    info: &MessageInfo,
+    entropy: Option<Binary>,
+) -> StdResult<Prng> {
+    let seed = RandSeed::load(storage)?.0;
+    let entropy = entropy.unwrap_or_default();
+
+    let mut rng_entropy = Vec::with_capacity(16 + info.sender.as_str().len() + entropy.len());
+    rng_entropy.extend_from_slice(&env.block.height.to_be_bytes());
+    rng_entropy.extend_from_slice(&env.block.time.seconds().to_be_bytes());
+    rng_entropy.extend_from_slice(info.sender.as_bytes());
+    rng_entropy.extend_from_slice(entropy.as_slice());
+
+    Ok(Prng::new(&seed, &rng_entropy))
+}
+
+// Moves the funds, returning whose balance they came from
+fn transfer_balances<'a>(
     storage: &mut dyn Storage,
-    sender: &Addr, //spender when using from
-    owner: Option<&Addr>,
+    sender: &'a Addr, //spender when using from
+    owner: Option<&'a Addr>,
     recipient: &Addr,
     amount: Uint128,
-    memo: Option<String>,
-    denom: String,
+    decoys: Option<(Vec<Addr>, &mut Prng)>,
     block: &shade_protocol::c_std::BlockInfo,
-) -> StdResult<()> {
+) -> StdResult<&'a Addr> {
     if !Config::transfer_enabled(storage)? {
         return Err(transfer_disabled());
     }
@@ -50,7 +71,30 @@ pub fn try_transfer_impl(
         }
     };
 
-    Balance::transfer(storage, amount, some_owner, recipient)?;
+    match decoys {
+        None => Balance::transfer(storage, amount, some_owner, recipient)?,
+        Some((decoys, rng)) => {
+            Balance::sub(storage, amount, some_owner)?;
+            Balance::add_with_decoys(storage, amount, recipient, &decoys, rng)?;
+        }
+    }
+
+    Ok(some_owner)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn try_transfer_impl(
+    storage: &mut dyn Storage,
+    sender: &Addr, //spender when using from
+    owner: Option<&Addr>,
+    recipient: &Addr,
+    amount: Uint128,
+    memo: Option<String>,
+    decoys: Option<(Vec<Addr>, &mut Prng)>,
+    denom: String,
+    block: &shade_protocol::c_std::BlockInfo,
+) -> StdResult<()> {
+    let some_owner = transfer_balances(storage, sender, owner, recipient, amount, decoys, block)?;
 
     store_transfer(
         storage, some_owner, sender, recipient, amount, denom, memo, block,
@@ -58,6 +102,7 @@ pub fn try_transfer_impl(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn try_transfer(
     deps: DepsMut,
     env: Env,
@@ -65,8 +110,14 @@ pub fn try_transfer(
     recipient: Addr,
     amount: Uint128,
     memo: Option<String>,
+    decoys: Option<Vec<Addr>>,
+    entropy: Option<Binary>,
 ) -> StdResult<Response> {
     let denom = CoinInfo::load(deps.storage)?.symbol;
+    let mut rng = match decoys {
+        None => None,
+        Some(_) => Some(decoy_rng(deps.storage, &env, &info, entropy)?),
+    };
     try_transfer_impl(
         deps.storage,
         &info.sender,
@@ -74,6 +125,7 @@ pub fn try_transfer(
         &recipient,
         amount,
         memo,
+        decoys.zip(rng.as_mut()),
         denom,
         &env.block,
     )?;
@@ -86,11 +138,20 @@ pub fn try_batch_transfer(
     env: Env,
     info: MessageInfo,
     actions: Vec<batch::TransferAction>,
+    entropy: Option<Binary>,
 ) -> StdResult<Response> {
+    let mut rng = match actions.iter().any(|action| action.decoys.is_some()) {
+        false => None,
+        true => Some(decoy_rng(deps.storage, &env, &info, entropy)?),
+    };
     let sender = info.sender;
     let block = env.block;
     let denom = CoinInfo::load(deps.storage)?.symbol;
     for action in actions {
+        let decoys = match action.decoys {
+            None => None,
+            Some(decoys) => Some(validate_vec(deps.api, decoys)?),
+        };
         try_transfer_impl(
             deps.storage,
             &sender,
@@ -98,6 +159,7 @@ pub fn try_batch_transfer(
             &deps.api.addr_validate(action.recipient.as_str())?,
             action.amount,
             action.memo,
+            decoys.zip(rng.as_mut()),
             denom.clone(),
             &block,
         )?;
@@ -137,6 +199,7 @@ fn try_add_receiver_api_callback(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn try_send_impl(
     storage: &mut dyn Storage,
     messages: &mut Vec<SubMsg>,
@@ -147,17 +210,20 @@ pub fn try_send_impl(
     amount: Uint128,
     memo: Option<String>,
     msg: Option<Binary>,
+    decoys: Option<(Vec<Addr>, &mut Prng)>,
     denom: String,
     block: &shade_protocol::c_std::BlockInfo,
 ) -> StdResult<()> {
-    try_transfer_impl(
+    let some_owner = transfer_balances(storage, sender, owner, recipient, amount, decoys, block)?;
+
+    store_send(
         storage,
-        &sender,
-        owner,
-        &recipient,
+        some_owner,
+        sender,
+        recipient,
         amount,
-        memo.clone(),
         denom,
+        memo.clone(),
         block,
     )?;
     try_add_receiver_api_callback(
@@ -175,6 +241,7 @@ pub fn try_send_impl(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn try_send(
     deps: DepsMut,
     env: Env,
@@ -184,9 +251,15 @@ pub fn try_send(
     amount: Uint128,
     memo: Option<String>,
     msg: Option<Binary>,
+    decoys: Option<Vec<Addr>>,
+    entropy: Option<Binary>,
 ) -> StdResult<Response> {
     let mut messages = vec![];
     let denom = CoinInfo::load(deps.storage)?.symbol;
+    let mut rng = match decoys {
+        None => None,
+        Some(_) => Some(decoy_rng(deps.storage, &env, &info, entropy)?),
+    };
 
     try_send_impl(
         deps.storage,
@@ -198,6 +271,7 @@ pub fn try_send(
         amount,
         memo,
         msg,
+        decoys.zip(rng.as_mut()),
         denom,
         &env.block,
     )?;
@@ -212,12 +286,21 @@ pub fn try_batch_send(
     env: Env,
     info: MessageInfo,
     actions: Vec<batch::SendAction>,
+    entropy: Option<Binary>,
 ) -> StdResult<Response> {
     let mut messages = vec![];
+    let mut rng = match actions.iter().any(|action| action.decoys.is_some()) {
+        false => None,
+        true => Some(decoy_rng(deps.storage, &env, &info, entropy)?),
+    };
     let sender = info.sender;
     let denom = CoinInfo::load(deps.storage)?.symbol;
 
     for action in actions {
+        let decoys = match action.decoys {
+            None => None,
+            Some(decoys) => Some(validate_vec(deps.api, decoys)?),
+        };
         try_send_impl(
             deps.storage,
             &mut messages,
@@ -228,6 +311,7 @@ pub fn try_batch_send(
             action.amount,
             action.memo,
             action.msg,
+            decoys.zip(rng.as_mut()),
             denom.clone(),
             &env.block,
         )?;
@@ -4,6 +4,8 @@ use shade_protocol::{
     contract_interfaces::snip20::{
         manager::{
             Allowance,
+            AllowanceGiven,
+            AllowanceReceived,
             Balance,
             CoinInfo,
             Config,
@@ -11,11 +13,12 @@ use shade_protocol::{
             Minters,
             TotalSupply,
         },
-        transaction_history::{RichTx, Tx},
+        transaction_history::{RichTx, Tx, TxFilter},
         QueryAnswer,
     },
     utils::storage::plus::{ItemStorage, MapStorage},
 };
+use crate::handle::allowance::{ALLOWANCES_GIVEN, ALLOWANCES_RECEIVED};
 
 pub fn token_info(
     deps: Deps,
@@ -107,6 +110,56 @@ pub fn allowance(
     })
 }
 
+pub fn allowances_given(
+    deps: Deps,
+    owner: Addr,
+    page: u32,
+    page_size: u32,
+) -> StdResult<QueryAnswer> {
+    let (spenders, count) = ALLOWANCES_GIVEN.get(deps.storage, &owner, page, page_size)?;
+
+    let mut allowances = vec![];
+    for spender in spenders {
+        let allowance = Allowance::load(deps.storage, (owner.clone(), spender.clone()))?;
+        allowances.push(AllowanceGiven {
+            spender,
+            allowance: allowance.amount,
+            expiration: allowance.expiration,
+        });
+    }
+
+    Ok(QueryAnswer::AllowancesGiven {
+        owner,
+        allowances,
+        count,
+    })
+}
+
+pub fn allowances_received(
+    deps: Deps,
+    spender: Addr,
+    page: u32,
+    page_size: u32,
+) -> StdResult<QueryAnswer> {
+    let (owners, count) = ALLOWANCES_RECEIVED.get(deps.storage, &spender, page, page_size)?;
+
+    let mut allowances = vec![];
+    for owner in owners {
+        let allowance = Allowance::load(deps.storage, (owner.clone(), spender.clone()))?;
+        allowances.push(AllowanceReceived {
+            owner,
+            allowance: allowance.amount,
+            expiration: allowance.expiration,
+        });
+    }
+
+    Ok(QueryAnswer::AllowancesReceived {
+        spender,
+        allowances,
+        count,
+    })
+}
+
 pub fn balance(
     deps: Deps,
     account: Addr,
@@ -134,8 +187,9 @@ pub fn transaction_history(
     account: Addr,
     page: u32,
     page_size: u32,
+    filter: Option<TxFilter>,
 ) -> StdResult<QueryAnswer> {
-    let transfer = RichTx::get(deps.storage, &account, page, page_size)?;
+    let transfer = RichTx::get(deps.storage, &account, page, page_size, filter.as_ref())?;
     Ok(QueryAnswer::TransactionHistory {
         txs: transfer.0,
         total: Some(transfer.1),
@@ -138,6 +138,8 @@ fn contract_status_stop_all() {
         recipient: "dylan".into(),
         amount: Uint128::new(100),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).is_err());
 
@@ -156,6 +158,8 @@ fn contract_status_stop_all() {
         recipient: "dylan".into(),
         amount: Uint128::new(100),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).is_ok());
 
@@ -200,6 +204,8 @@ fn contract_status_stop_all_but_redeem() {
         recipient: "dylan".into(),
         amount: Uint128::new(100),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).is_err());
 
@@ -218,6 +224,8 @@ fn contract_status_stop_all_but_redeem() {
         recipient: "dylan".into(),
         amount: Uint128::new(100),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).is_ok());
 
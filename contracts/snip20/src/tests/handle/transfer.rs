@@ -1,7 +1,8 @@
-use shade_protocol::c_std::Addr;
+use shade_protocol::c_std::{Addr, Binary};
 use shade_protocol::utils::{ExecuteCallback, Query};
 use shade_protocol::c_std::Uint128;
 use shade_protocol::contract_interfaces::snip20::{ExecuteMsg, InitialBalance, QueryMsg, QueryAnswer};
+use shade_protocol::contract_interfaces::snip20::batch::TransferAction;
 use crate::tests::init_snip20_with_config;
 
 #[test]
@@ -46,6 +47,8 @@ fn transfer() {
         recipient: "dylan".into(),
         amount: Uint128::new(100),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).is_ok());
 
@@ -75,10 +78,71 @@ fn transfer() {
         recipient: "dylan".into(),
         amount: Uint128::new(1000),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).is_err());
 }
 
+#[test]
+fn transfer_with_decoys() {
+    let (mut chain, snip) = init_snip20_with_config(Some(vec![
+        InitialBalance{
+            address: "bob".into(),
+            amount: (Uint128::new(1000))
+        },
+        InitialBalance {
+            address: "dylan".into(),
+            amount: Uint128::new(1000)
+        },
+        InitialBalance {
+            address: "lucas".into(),
+            amount: Uint128::new(1000)
+        },
+    ]), None).unwrap();
+
+    assert!(ExecuteMsg::Transfer {
+        recipient: "lucas".into(),
+        amount: Uint128::new(100),
+        memo: None,
+        decoys: Some(vec!["dylan".into(), "walter".into()]),
+        entropy: Some(Binary::from("entropy".as_bytes())),
+        padding: None
+    }.test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).is_ok());
+
+    assert!(ExecuteMsg::BatchTransfer {
+        actions: vec![
+            TransferAction {
+                recipient: "lucas".into(),
+                amount: Uint128::new(50),
+                memo: None,
+                decoys: Some(vec!["dylan".into()]),
+            },
+            TransferAction {
+                recipient: "dylan".into(),
+                amount: Uint128::new(50),
+                memo: None,
+                decoys: None,
+            },
+        ],
+        entropy: None,
+        padding: None
+    }.test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).is_ok());
+
+    // Decoys are left untouched
+    for (address, expected) in [("bob", 800), ("dylan", 1050), ("lucas", 1150)] {
+        let answer: QueryAnswer = QueryMsg::Balance {
+                address: address.into(),
+                key: "password".into()
+            }.test_query(&snip, &chain).unwrap();
+
+        match answer {
+            QueryAnswer::Balance {amount} => assert_eq!(amount, Uint128::new(expected)),
+            _ => assert!(false)
+        }
+    }
+}
+
 #[test]
 fn send() {
     let (mut chain, snip) = init_snip20_with_config(Some(vec![
@@ -97,6 +161,8 @@ fn send() {
         amount: Uint128::new(100),
         recipient_code_hash: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
         msg: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).is_ok());
@@ -128,6 +194,8 @@ fn send() {
         amount: Uint128::new(1000),
         recipient_code_hash: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
         msg: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).is_err());
@@ -1,6 +1,7 @@
 use shade_protocol::c_std::{Coin, Addr, Uint128};
 use shade_protocol::contract_interfaces::snip20::{ExecuteMsg, InitialBalance, QueryAnswer, QueryMsg};
-use shade_protocol::contract_interfaces::snip20::transaction_history::{RichTx, TxAction};
+use shade_protocol::contract_interfaces::snip20::transaction_history::{RichTx, TxAction, TxActionType, TxFilter};
+use shade_protocol::multi_test::App;
 use shade_protocol::query_auth;
 use shade_protocol::utils::{ExecuteCallback, InstantiateCallback, Query, MultiTestable};
 use crate::tests::{create_vk, init_snip20_with_auth, init_snip20_with_config};
@@ -74,6 +75,97 @@ fn allowance_auth_vk() {
     }
 }
 
+#[test]
+fn allowances_given_received_vk() {
+    let (mut chain, snip) = init_snip20_with_config(None, None).unwrap();
+
+    let saul = Addr::unchecked("saul");
+    let goodman = Addr::unchecked("goodman");
+    let kim = Addr::unchecked("kim");
+
+    create_vk(&mut chain, &snip, "saul", None).unwrap();
+    create_vk(&mut chain, &snip, "goodman", None).unwrap();
+
+    for (owner, spender, amount) in [
+        (&saul, &goodman, 100),
+        (&saul, &kim, 50),
+        (&kim, &goodman, 25),
+        // Increasing again doesn't index twice
+        (&saul, &goodman, 100),
+    ] {
+        ExecuteMsg::IncreaseAllowance {
+            spender: spender.clone().into_string(),
+            amount: Uint128::new(amount),
+            expiration: None,
+            padding: None
+        }.test_exec(&snip, &mut chain, owner.clone(), &[]).unwrap();
+    }
+
+    let answer: QueryAnswer = QueryMsg::AllowancesGiven {
+        owner: saul.clone().into(),
+        key: "password".into(),
+        page: None,
+        page_size: 10,
+    }.test_query(&snip, &chain).unwrap();
+
+    match answer {
+        QueryAnswer::AllowancesGiven { owner, allowances, count } => {
+            assert_eq!(owner, saul);
+            assert_eq!(count, 2);
+            assert_eq!(allowances.len(), 2);
+            assert_eq!(allowances[0].spender, goodman);
+            assert_eq!(allowances[0].allowance, Uint128::new(200));
+            assert_eq!(allowances[1].spender, kim);
+            assert_eq!(allowances[1].allowance, Uint128::new(50));
+        },
+        _ => assert!(false)
+    }
+
+    // Second page
+    let answer: QueryAnswer = QueryMsg::AllowancesGiven {
+        owner: saul.clone().into(),
+        key: "password".into(),
+        page: Some(1),
+        page_size: 1,
+    }.test_query(&snip, &chain).unwrap();
+
+    match answer {
+        QueryAnswer::AllowancesGiven { allowances, count, .. } => {
+            assert_eq!(count, 2);
+            assert_eq!(allowances.len(), 1);
+            assert_eq!(allowances[0].spender, kim);
+        },
+        _ => assert!(false)
+    }
+
+    let answer: QueryAnswer = QueryMsg::AllowancesReceived {
+        spender: goodman.clone().into(),
+        key: "password".into(),
+        page: None,
+        page_size: 10,
+    }.test_query(&snip, &chain).unwrap();
+
+    match answer {
+        QueryAnswer::AllowancesReceived { spender, allowances, count } => {
+            assert_eq!(spender, goodman);
+            assert_eq!(count, 2);
+            assert_eq!(allowances[0].owner, saul);
+            assert_eq!(allowances[0].allowance, Uint128::new(200));
+            assert_eq!(allowances[1].owner, kim);
+            assert_eq!(allowances[1].allowance, Uint128::new(25));
+        },
+        _ => assert!(false)
+    }
+
+    // Only the owner's key works
+    assert!(QueryMsg::AllowancesGiven {
+        owner: saul.clone().into(),
+        key: "wrong".into(),
+        page: None,
+        page_size: 10,
+    }.test_query::<QueryAnswer>(&snip, &chain).is_err());
+}
+
 #[test]
 fn balance_vk() {
     let (mut chain, snip) = init_snip20_with_config(Some(vec![InitialBalance {
@@ -111,6 +203,8 @@ fn transaction_history() {
         recipient: stratos.clone().into_string(),
         amount: Uint128::new(200),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("setsuna"), &[]).unwrap();
 
@@ -120,6 +214,8 @@ fn transaction_history() {
         amount: Uint128::new(140),
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("setsuna"), &[]).unwrap();
 
@@ -127,6 +223,8 @@ fn transaction_history() {
         recipient: felt.clone().into_string(),
         amount: Uint128::new(300),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("setsuna"), &[]).unwrap();
 
@@ -134,6 +232,8 @@ fn transaction_history() {
         recipient: tieria.clone().into_string(),
         amount: Uint128::new(540),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("setsuna"), &[]).unwrap();
 
@@ -141,12 +241,14 @@ fn transaction_history() {
         address: setsuna.clone().into(),
         key: "password".into(),
         page: None,
-        page_size: 10
+        page_size: 10,
+        filter: None,
     }.test_query(&snip, &chain).unwrap();
 
     match answer {
-        QueryAnswer::TransactionHistory { txs, .. } => {
+        QueryAnswer::TransactionHistory { txs, total } => {
             assert_eq!(txs.len(), 5);
+            assert_eq!(total, Some(5));
 
             assert_eq!(txs[0].id, 1);
             assert_eq!(txs[0].action, TxAction::Mint {
@@ -170,7 +272,7 @@ fn transaction_history() {
             });
 
             assert_eq!(txs[2].id, 3);
-            assert_eq!(txs[2].action, TxAction::Transfer {
+            assert_eq!(txs[2].action, TxAction::Send {
                 from: setsuna.clone(),
                 sender: setsuna.clone(),
                 recipient: smirnoff.clone()
@@ -205,4 +307,118 @@ fn transaction_history() {
         },
         _ => assert!(false)
     }
-}
\ No newline at end of file
+}
+#[test]
+fn transaction_history_filter() {
+    let setsuna = Addr::unchecked("setsuna");
+    let stratos = Addr::unchecked("stratos");
+    let felt = Addr::unchecked("felt");
+
+    let (mut chain, snip) = init_snip20_with_config(Some(vec![InitialBalance {
+        address: setsuna.clone().into_string(),
+        amount: Uint128::new(1500)
+    }]), None).unwrap();
+
+    ExecuteMsg::Transfer {
+        recipient: stratos.clone().into_string(),
+        amount: Uint128::new(200),
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None
+    }.test_exec(&snip, &mut chain, setsuna.clone(), &[]).unwrap();
+
+    ExecuteMsg::Send {
+        recipient: felt.clone().into_string(),
+        recipient_code_hash: None,
+        amount: Uint128::new(140),
+        msg: None,
+        memo: Some("rent".into()),
+        decoys: None,
+        entropy: None,
+        padding: None
+    }.test_exec(&snip, &mut chain, setsuna.clone(), &[]).unwrap();
+
+    ExecuteMsg::IncreaseAllowance {
+        spender: felt.clone().into_string(),
+        amount: Uint128::new(100),
+        expiration: None,
+        padding: None
+    }.test_exec(&snip, &mut chain, setsuna.clone(), &[]).unwrap();
+
+    ExecuteMsg::DecreaseAllowance {
+        spender: felt.clone().into_string(),
+        amount: Uint128::new(40),
+        expiration: None,
+        padding: None
+    }.test_exec(&snip, &mut chain, setsuna.clone(), &[]).unwrap();
+
+    let history = |chain: &App, filter: TxFilter, page_size: u32| -> (Vec<RichTx>, Option<u64>) {
+        match (QueryMsg::TransactionHistory {
+            address: setsuna.clone().into(),
+            key: "password".into(),
+            page: None,
+            page_size,
+            filter: Some(filter),
+        }).test_query(&snip, chain).unwrap() {
+            QueryAnswer::TransactionHistory { txs, total } => (txs, total),
+            _ => panic!("Query failed"),
+        }
+    };
+
+    // Action type
+    let (txs, total) = history(&chain, TxFilter {
+        actions: Some(vec![TxActionType::Send]),
+        ..Default::default()
+    }, 10);
+    assert_eq!(total, Some(1));
+    assert_eq!(txs[0].action, TxAction::Send {
+        from: setsuna.clone(),
+        sender: setsuna.clone(),
+        recipient: felt.clone(),
+    });
+    assert_eq!(txs[0].memo, Some("rent".into()));
+
+    // Counterparty, the total counts every match past the page
+    let (txs, total) = history(&chain, TxFilter {
+        counterparty: Some(felt.clone().into_string()),
+        ..Default::default()
+    }, 2);
+    assert_eq!(total, Some(3));
+    assert_eq!(txs.len(), 2);
+    assert_eq!(txs[1].action, TxAction::IncreaseAllowance {
+        owner: setsuna.clone(),
+        spender: felt.clone(),
+    });
+    assert_eq!(txs[1].coins.amount, Uint128::new(100));
+
+    // Date range
+    let now = chain.block_info().time.seconds();
+    let (_, total) = history(&chain, TxFilter {
+        start_time: Some(now + 1),
+        ..Default::default()
+    }, 10);
+    assert_eq!(total, Some(0));
+
+    let (_, total) = history(&chain, TxFilter {
+        start_time: Some(now),
+        end_time: Some(now + 1),
+        ..Default::default()
+    }, 10);
+    assert_eq!(total, Some(5));
+
+    // Short page, the total still counts every match
+    let (txs, total) = history(&chain, TxFilter {
+        start_time: Some(now),
+        ..Default::default()
+    }, 2);
+    assert_eq!(txs.len(), 2);
+    assert_eq!(total, Some(5));
+
+    let (txs, total) = history(&chain, TxFilter {
+        start_time: Some(now),
+        ..Default::default()
+    }, 0);
+    assert!(txs.is_empty());
+    assert_eq!(total, Some(5));
+}
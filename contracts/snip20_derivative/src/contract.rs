@@ -2,9 +2,9 @@ use std::ops::Add;
 
 use crate::{
     msg::{
-        status_level_to_u8, Config, ContractStatusLevel, ExecuteAnswer, ExecuteMsg,
+        status_level_to_u8, Config, ContractInfo, ContractStatusLevel, ExecuteAnswer, ExecuteMsg,
         InProcessUnbonding, InstantiateMsg, PanicUnbond, QueryAnswer, QueryMsg, QueryWithPermit,
-        ReceiverMsg, ResponseStatus::Success,
+        ReceiverMsg, ResponseStatus::Success, StakingBackend, UnbondingSource,
     },
     staking_interface::{transfer_staked_msg, Reward, Rewards, Token},
     state::{ContractsVksStore, REWARDED_TOKENS_LIST},
@@ -18,9 +18,9 @@ use crate::staking_interface::{
 };
 
 use crate::state::{
-    UnbondingIdsStore, UnbondingStore, CONFIG, CONTRACT_STATUS, PANIC_UNBONDS,
-    PANIC_UNBOND_REPLY_ID, PANIC_WITHDRAW_REPLY_ID, PENDING_UNBONDING, RESPONSE_BLOCK_SIZE,
-    UNBOND_REPLY_ID,
+    UnbondingIdsStore, UnbondingSourcesStore, UnbondingStore, CONFIG, CONTRACT_STATUS,
+    LIQUIDITY_BUFFER, PANIC_UNBONDS, PANIC_UNBOND_REPLY_ID, PANIC_WITHDRAW_REPLY_ID,
+    PENDING_UNBONDING, RESPONSE_BLOCK_SIZE, UNBONDING_COUNT, UNBOND_REPLY_ID,
 };
 /// This contract implements SNIP-20 standard:
 /// https://github.com/SecretFoundation/SNIPs/blob/master/SNIP-20.md
@@ -82,9 +82,10 @@ pub fn instantiate(
             "Derivative and token contracts should have the same amount of decimals",
         ));
     }
-    // Generate viewing key for staking contract
-    let entropy: String = msg
-        .staking
+    validate_staking_backends(&msg.staking, &[])?;
+    // Generate viewing key for staking contracts
+    let entropy: String = msg.staking[0]
+        .contract
         .entropy
         .clone()
         .unwrap_or_else(|| msg.prng_seed.to_string());
@@ -100,6 +101,12 @@ pub fn instantiate(
     let (token_contract_vk, _new_seed) =
         new_viewing_key(&info.sender, &env, &new_seed, entropy.as_ref());
 
+    let liquidity_buffer = msg.liquidity_buffer.unwrap_or(Fee {
+        rate: 0,
+        decimal_places: 0,
+    });
+    validate_liquidity_buffer(&liquidity_buffer)?;
+
     CONFIG.save(
         deps.storage,
         &Config {
@@ -111,6 +118,7 @@ pub fn instantiate(
             derivative: msg.derivative.clone(),
             staking: msg.staking,
             fees: msg.fees,
+            liquidity_buffer,
             contract_address: env.contract.address.clone(),
             admin: msg.admin,
         },
@@ -142,7 +150,7 @@ pub fn instantiate(
             msg.token.code_hash,
             msg.token.address.to_string(),
         )?,
-        // Set viewing key for staking contract
+        // Set viewing key for staking contracts
         set_viewing_key_msg(
             staking_contract_vk,
             msg.query_auth.entropy,
@@ -173,15 +181,23 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
         ExecuteMsg::UpdateFees {
             staking,
             unbonding,
+            instant_unbonding,
             collector,
         } => update_fees(
             deps,
             info,
             staking,
             unbonding,
+            instant_unbonding,
             collector,
             ContractStatusLevel::Panicked,
         ),
+        ExecuteMsg::UpdateLiquidityBuffer { share } => {
+            update_liquidity_buffer(deps, info, share, ContractStatusLevel::Panicked)
+        }
+        ExecuteMsg::UpdateStaking { staking } => {
+            update_staking(deps, info, staking, ContractStatusLevel::Panicked)
+        }
 
         // Messages available when status is normal
         ExecuteMsg::Receive {
@@ -218,28 +234,23 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
         (UNBOND_REPLY_ID, SubMsgResult::Ok(s)) => match s.data {
             Some(x) => {
                 let result: UnbondResponse = from_binary(&x)?;
-                // Unbonding stored in try_unbond function
-                // Because of here you can't access the sender of the TX this was stored previously
-                let pending_unbonding = PENDING_UNBONDING.may_load(deps.storage)?;
-
-                if let Some(unbonding_processing) = pending_unbonding {
-                    // Set properly id for this unbonding
-                    let unbond = Unbonding {
+                // Unbond msgs sent in try_unbond, replies arrive in the same order
+                let mut pending_unbondings = PENDING_UNBONDING
+                    .may_load(deps.storage)?
+                    .unwrap_or_default();
+
+                if !pending_unbondings.is_empty() {
+                    let unbonding_processing = pending_unbondings.remove(0);
+                    PENDING_UNBONDING.save(deps.storage, &pending_unbondings)?;
+
+                    // Link the staking contract unbonding to the derivative one
+                    let id = unbonding_processing.id.u128();
+                    let mut sources = UnbondingSourcesStore::load(deps.storage, id);
+                    sources.push(UnbondingSource {
+                        staking: unbonding_processing.staking,
                         id: result.unbond.id,
-                        amount: unbonding_processing.amount,
-                        complete: unbonding_processing.complete,
-                    };
-                    UnbondingStore::save(deps.storage, result.unbond.id.clone().u128(), &unbond)?;
-
-                    // Add unbonding id to user's unbondings IDs
-                    let mut users_unbondings_ids =
-                        UnbondingIdsStore::load(deps.storage, &unbonding_processing.owner);
-                    users_unbondings_ids.push(result.unbond.id.u128());
-                    UnbondingIdsStore::save(
-                        deps.storage,
-                        &unbonding_processing.owner,
-                        users_unbondings_ids,
-                    )?;
+                    });
+                    UnbondingSourcesStore::save(deps.storage, id, &sources)?;
 
                     Ok(Response::default())
                 } else {
@@ -276,14 +287,14 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
             Some(x) => {
                 let result: UnbondResponse = from_binary(&x)?;
                 let mut panic_unbonds = PANIC_UNBONDS.may_load(deps.storage)?.unwrap_or_default();
-                let last = panic_unbonds.pop();
+                // Panic unbonds are stored with id 0 until the staking contract replies,
+                // replies arrive in the same order the unbond msgs were sent
+                let pending = panic_unbonds.iter_mut().find(|u| u.id.is_zero());
 
                 // Validate there is at least 1 element is storage to update
                 // should never happen but you never know
-                if let Some(mut last_unbond) = last {
-                    // Update latest panic unbond id
-                    last_unbond.id = result.unbond.id;
-                    panic_unbonds.push(last_unbond);
+                if let Some(unbond) = pending {
+                    unbond.id = result.unbond.id;
                     //Save list of panic unbonds in storage
                     PANIC_UNBONDS.save(deps.storage, &panic_unbonds)?;
                 }
@@ -321,6 +332,7 @@ fn try_claim(
     let user_unbondings_ids = UnbondingIdsStore::load(deps.storage, &sender);
     let config = CONFIG.load(deps.storage)?;
     let mut to_claim_ids: Vec<u128> = vec![];
+    let mut to_withdraw: Vec<(Addr, Vec<Uint128>)> = vec![];
     let mut amount_claimed = Uint128::zero();
 
     for id in user_unbondings_ids.iter() {
@@ -331,8 +343,17 @@ fn try_claim(
                 to_claim_ids.push(unbonding.id.u128());
                 amount_claimed += unbonding.amount;
 
+                // Group staking contracts unbondings by staking contract
+                for source in UnbondingSourcesStore::load(deps.storage, *id).into_iter() {
+                    match to_withdraw.iter_mut().find(|(s, _)| *s == source.staking) {
+                        Some((_, ids)) => ids.push(source.id),
+                        None => to_withdraw.push((source.staking, vec![source.id])),
+                    }
+                }
+
                 // Remove unbonding from storage
                 UnbondingStore::remove(deps.storage, *id)?;
+                UnbondingSourcesStore::remove(deps.storage, *id)?;
             }
         }
     }
@@ -347,15 +368,16 @@ fn try_claim(
         .collect();
     UnbondingIdsStore::save(deps.storage, &sender, users_new_pending_unbondings)?;
 
-    let to_claim_ids_uint128: Vec<Uint128> = to_claim_ids.into_iter().map(Uint128::from).collect();
-
-    let config: Config = CONFIG.load(deps.storage)?;
-    let messages: Vec<CosmosMsg> = vec![
-        withdraw_msg(
-            config.staking.code_hash,
-            config.staking.address.to_string(),
-            Some(to_claim_ids_uint128),
-        )?,
+    let mut messages: Vec<CosmosMsg> = vec![];
+    for (staking, ids) in to_withdraw.into_iter() {
+        let backend = get_staking_backend(&config, &staking)?;
+        messages.push(withdraw_msg(
+            backend.contract.code_hash.clone(),
+            backend.contract.address.to_string(),
+            Some(ids),
+        )?);
+    }
+    messages.extend(vec![
         send_msg(
             config.fees.collector.to_string(),
             fee,
@@ -376,7 +398,7 @@ fn try_claim(
             config.token.code_hash,
             config.token.address.to_string(),
         )?,
-    ];
+    ]);
 
     Ok(Response::default()
         .add_messages(messages)
@@ -398,15 +420,17 @@ fn try_claim(
 fn try_compound_rewards(deps: DepsMut, priority: ContractStatusLevel) -> StdResult<Response> {
     check_status(deps.storage, priority)?;
     let config = CONFIG.load(deps.storage)?;
-    let staked = get_staked_shd(deps.querier, &config.contract_address, &config)?;
+    let staked = get_staked_balances(deps.querier, &config.contract_address, &config)?;
     let rewards = query_rewards(deps.querier, &config.contract_address, &config)?;
 
     let mut messages: Vec<CosmosMsg> = vec![];
-    if staked > 0 {
-        messages.push(compound_msg(
-            config.staking.code_hash,
-            config.staking.address.to_string(),
-        )?);
+    for (backend, staked) in config.staking.iter().zip(staked.into_iter()) {
+        if staked > 0 {
+            messages.push(compound_msg(
+                backend.contract.code_hash.clone(),
+                backend.contract.address.to_string(),
+            )?);
+        }
     }
     let response = Response::default();
     let rewarded_tokens_list = REWARDED_TOKENS_LIST
@@ -484,26 +508,38 @@ fn try_panic_unbond(
         info.sender.to_string(),
         &config.admin,
     )?;
-    // Store panic unbond
-    let staking_config = get_staking_contract_config(deps.querier, &config)?;
-    let complete: Uint128 =
-        Uint128::from(env.block.time.seconds()).checked_add(staking_config.unbond_period)?;
+    let staked = get_staked_balances(deps.querier, &env.contract.address, &config)?;
+    let parts = split_withdrawal(amount.u128(), &staked, &staking_weights(&config));
+
     let mut panic_unbonds: Vec<PanicUnbond> =
         PANIC_UNBONDS.may_load(deps.storage)?.unwrap_or_default();
-    panic_unbonds.push(PanicUnbond {
-        id: Uint128::zero(),
-        amount,
-        complete,
-    });
+    let mut response = Response::default();
+    for (backend, part) in config.staking.iter().zip(parts.into_iter()) {
+        if part == 0 {
+            continue;
+        }
+        // Store panic unbond
+        let staking_config = get_staking_contract_config(deps.querier, &backend.contract)?;
+        let complete: Uint128 =
+            Uint128::from(env.block.time.seconds()).checked_add(staking_config.unbond_period)?;
+        panic_unbonds.push(PanicUnbond {
+            id: Uint128::zero(),
+            staking: backend.contract.address.clone(),
+            amount: Uint128::from(part),
+            complete,
+        });
+
+        let msg = unbond_msg(
+            Uint128::from(part),
+            backend.contract.code_hash.clone(),
+            backend.contract.address.to_string(),
+            Some(false),
+        )?;
+        response = response.add_submessage(SubMsg::reply_always(msg, PANIC_UNBOND_REPLY_ID));
+    }
     PANIC_UNBONDS.save(deps.storage, &panic_unbonds)?;
 
-    let msg = unbond_msg(
-        amount,
-        config.staking.code_hash,
-        config.staking.address.to_string(),
-        Some(false),
-    )?;
-    Ok(Response::default().add_submessage(SubMsg::reply_always(msg, PANIC_UNBOND_REPLY_ID)))
+    Ok(response)
 }
 
 /// It sends a message to the staking contract to claim rewards, then sends a message to the staking contract
@@ -537,46 +573,57 @@ fn try_panic_withdraw(
     let rewards = get_rewards(deps.querier, &env.contract.address, &config)?;
     let balance = get_available_shd(deps.querier, &env.contract.address, &config)?;
     let amount = Uint128::from(rewards + balance);
-    let mut response = Response::default().add_messages(vec![
-        claim_rewards_msg(
-            config.staking.code_hash.clone(),
-            config.staking.address.to_string(),
-        )?,
-        send_msg(
-            addr.to_string(),
-            amount,
-            None,
-            Some("Panic withdraw {} tokens".to_string()),
-            config.token.entropy,
-            RESPONSE_BLOCK_SIZE,
-            config.token.code_hash,
-            config.token.address.to_string(),
-        )?,
-    ]);
+    // The whole SHD balance is sent, liquidity buffer included
+    LIQUIDITY_BUFFER.save(deps.storage, &0)?;
+    let mut messages: Vec<CosmosMsg> = vec![];
+    for backend in config.staking.iter() {
+        messages.push(claim_rewards_msg(
+            backend.contract.code_hash.clone(),
+            backend.contract.address.to_string(),
+        )?);
+    }
+    messages.push(send_msg(
+        addr.to_string(),
+        amount,
+        None,
+        Some("Panic withdraw {} tokens".to_string()),
+        config.token.entropy.clone(),
+        RESPONSE_BLOCK_SIZE,
+        config.token.code_hash.clone(),
+        config.token.address.to_string(),
+    )?);
+    let mut response = Response::default().add_messages(messages);
     let panic_unbonds = PANIC_UNBONDS.may_load(deps.storage)?;
     if let Some(unbonds) = panic_unbonds {
         let time = Uint128::from(env.block.time.seconds());
-        let mut to_withdraw: Vec<Uint128> = vec![];
         let mut pending_unbonds: Vec<PanicUnbond> = vec![];
 
-        for u in unbonds.into_iter() {
-            if time >= u.complete {
-                to_withdraw.push(u.id);
-            } else {
-                pending_unbonds.push(u);
+        for backend in config.staking.iter() {
+            let mut to_withdraw: Vec<Uint128> = vec![];
+            for u in unbonds.iter() {
+                if u.staking != backend.contract.address {
+                    continue;
+                }
+                if time >= u.complete {
+                    to_withdraw.push(u.id);
+                } else {
+                    pending_unbonds.push(u.clone());
+                }
+            }
+
+            if !to_withdraw.is_empty() {
+                response = response.add_submessage(SubMsg::reply_on_success(
+                    withdraw_msg(
+                        backend.contract.code_hash.clone(),
+                        backend.contract.address.to_string(),
+                        Some(to_withdraw),
+                    )?,
+                    PANIC_WITHDRAW_REPLY_ID,
+                ));
             }
         }
 
         PANIC_UNBONDS.save(deps.storage, &pending_unbonds)?;
-
-        response = response.add_submessage(SubMsg::reply_on_success(
-            withdraw_msg(
-                config.staking.code_hash,
-                config.staking.address.to_string(),
-                Some(to_withdraw.clone()),
-            )?,
-            PANIC_WITHDRAW_REPLY_ID,
-        ));
     }
 
     Ok(response)
@@ -590,6 +637,7 @@ fn try_panic_withdraw(
 /// * `info`: MessageInfo - this is the information about the message that was sent to the contract.
 /// * `staking`: The fee for staking.
 /// * `unbonding`: Option<Fee>
+/// * `instant_unbonding`: The extra fee for unbonding from the liquidity buffer.
 ///
 /// Returns:
 ///
@@ -599,6 +647,7 @@ fn update_fees(
     info: MessageInfo,
     staking: Option<Fee>,
     unbonding: Option<Fee>,
+    instant_unbonding: Option<Fee>,
     collector: Option<Addr>,
     priority: ContractStatusLevel,
 ) -> StdResult<Response> {
@@ -613,6 +662,7 @@ fn update_fees(
     let fees: FeeInfo = FeeInfo {
         staking: staking.unwrap_or(config.fees.staking),
         unbonding: unbonding.unwrap_or(config.fees.unbonding),
+        instant_unbonding: instant_unbonding.unwrap_or(config.fees.instant_unbonding),
         collector: collector.unwrap_or(config.fees.collector),
     };
     config.fees = fees.clone();
@@ -626,6 +676,102 @@ fn update_fees(
     )
 }
 
+/// `update_liquidity_buffer` updates the share of the pool kept unstaked for instant unbondings
+///
+/// Arguments:
+///
+/// * `deps`: DepsMut - This is the dependency object that contains the storage, querier, and logger.
+/// * `info`: MessageInfo - this is the information about the message that was sent to the contract.
+/// * `share`: The new share, uses the same format as fee rates.
+///
+/// Returns:
+///
+/// StdResult<Response>.
+fn update_liquidity_buffer(
+    deps: DepsMut,
+    info: MessageInfo,
+    share: Fee,
+    priority: ContractStatusLevel,
+) -> StdResult<Response> {
+    check_status(deps.storage, priority)?;
+    let mut config = CONFIG.load(deps.storage)?;
+    check_if_admin(
+        &deps.querier,
+        AdminPermissions::DerivativeAdmin,
+        info.sender.to_string(),
+        &config.admin,
+    )?;
+    validate_liquidity_buffer(&share)?;
+    config.liquidity_buffer = share.clone();
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(
+        Response::default().set_data(to_binary(&ExecuteAnswer::UpdateLiquidityBuffer {
+            status: Success,
+            share,
+        })?),
+    )
+}
+
+/// `validate_liquidity_buffer` checks the share is at most 100% and its precision fits in a u32
+///
+/// Arguments:
+///
+/// * `share`: The liquidity buffer share.
+///
+/// Returns:
+///
+/// StdResult<()>.
+fn validate_liquidity_buffer(share: &Fee) -> StdResult<()> {
+    if share.decimal_places > 9 {
+        return Err(StdError::generic_err(
+            "Liquidity buffer can't have more than 9 decimal places",
+        ));
+    }
+    if share.rate > 10_u32.pow(share.decimal_places as u32) {
+        return Err(StdError::generic_err(
+            "Liquidity buffer can't be more than 100%",
+        ));
+    }
+    Ok(())
+}
+
+/// `update_staking` replaces the staking contracts and their weights
+///
+/// Arguments:
+///
+/// * `deps`: DepsMut - This is the dependency object that contains the storage, querier, and logger.
+/// * `info`: MessageInfo - this is the information about the message that was sent to the contract.
+/// * `staking`: The new list of staking contracts, it must keep every current staking contract.
+///
+/// Returns:
+///
+/// StdResult<Response>.
+fn update_staking(
+    deps: DepsMut,
+    info: MessageInfo,
+    staking: Vec<StakingBackend>,
+    priority: ContractStatusLevel,
+) -> StdResult<Response> {
+    check_status(deps.storage, priority)?;
+    let mut config = CONFIG.load(deps.storage)?;
+    check_if_admin(
+        &deps.querier,
+        AdminPermissions::DerivativeAdmin,
+        info.sender.to_string(),
+        &config.admin,
+    )?;
+    validate_staking_backends(&staking, &config.staking)?;
+    config.staking = staking;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(
+        Response::default().set_data(to_binary(&ExecuteAnswer::UpdateStaking {
+            status: Success,
+        })?),
+    )
+}
+
 /// If the message is a `Stake` message, call `try_stake`, if it's an `Unbond` message, call
 /// `try_unbond`, otherwise return an error
 ///
@@ -666,6 +812,14 @@ fn receive(
                 amount,
                 ContractStatusLevel::NormalRun,
             ),
+            ReceiverMsg::InstantUnbond {} => try_instant_unbond(
+                deps,
+                env,
+                info,
+                from,
+                amount,
+                ContractStatusLevel::NormalRun,
+            ),
             ReceiverMsg::TransferStaked { receiver } => try_transfer_staked(
                 deps,
                 env,
@@ -677,9 +831,10 @@ fn receive(
             ),
             #[allow(unreachable_patterns)]
             _ => Err(StdError::generic_err(format!(
-                "Invalid msg provided, expected {} , {} or {}",
+                "Invalid msg provided, expected {} , {} , {} or {}",
                 to_binary(&ReceiverMsg::Stake {})?,
                 to_binary(&ReceiverMsg::Unbond {})?,
+                to_binary(&ReceiverMsg::InstantUnbond {})?,
                 to_binary(&ReceiverMsg::TransferStaked { receiver: None })?
             ))),
         }
@@ -745,7 +900,8 @@ fn try_stake(
     let claiming = available + rewards_amount;
 
     // get staked SHD
-    let bonded = get_staked_shd(deps.querier, &env.contract.address, &config)?;
+    let staked = get_staked_balances(deps.querier, &env.contract.address, &config)?;
+    let bonded: u128 = staked.iter().sum();
     let starting_pool = (claiming + bonded).saturating_sub(deposit.u128() + fee.u128());
 
     let token_info = get_token_info(
@@ -770,6 +926,19 @@ fn try_stake(
     if mint == Uint128::zero() {
         return Err(StdError::generic_err("The amount of SHD deposited is not enough to receive any of the derivative token at the current price"));
     }
+    // Keep part of the deposit unstaked until the liquidity buffer reaches its share of the pool
+    let buffer = LIQUIDITY_BUFFER.may_load(deps.storage)?.unwrap_or_default();
+    let (buffer_target, _) = get_fee(
+        Uint128::from(bonded + buffer).checked_add(deposit)?,
+        &config.liquidity_buffer,
+    )?;
+    let buffered = buffer_target
+        .u128()
+        .saturating_sub(buffer)
+        .min(deposit.u128());
+    LIQUIDITY_BUFFER.save(deps.storage, &(buffer + buffered))?;
+    let to_stake = deposit.u128() - buffered;
+
     // Sync rewarded tokens
     let mut messages = sync_rewarded_tokens(&env, deps, info, &non_shd_rewards, &config)?;
 
@@ -802,9 +971,19 @@ fn try_stake(
         config.token.address.to_string(),
     )?);
 
-    // Stake available SHD
-    if deposit > Uint128::zero() {
-        messages.push(generate_stake_msg(deposit, Some(true), &config)?);
+    // Stake available SHD, spread across staking contracts by weight
+    if to_stake > 0 {
+        let parts = split_deposit(to_stake, &staked, &staking_weights(&config));
+        for (backend, part) in config.staking.iter().zip(parts.into_iter()) {
+            if part > 0 {
+                messages.push(generate_stake_msg(
+                    Uint128::from(part),
+                    Some(true),
+                    &config,
+                    &backend.contract,
+                )?);
+            }
+        }
     }
 
     Ok(Response::new()
@@ -848,7 +1027,8 @@ fn try_transfer_staked(
     }
 
     let (_, rewards, delegatable) = get_delegatable(deps.querier, &env.contract.address, &config)?;
-    let staked = get_staked_shd(deps.querier, &env.contract.address, &config)?;
+    let staked_balances = get_staked_balances(deps.querier, &env.contract.address, &config)?;
+    let staked: u128 = staked_balances.iter().sum();
     let pool = delegatable + staked;
     // unwrap is ok because multiplying 2 u128 ints can not overflow a u256
     let number = Uint256::from(amount)
@@ -871,19 +1051,38 @@ fn try_transfer_staked(
         )));
     }
     let recipient: String = receiver.unwrap_or(from).to_string();
-    Ok(Response::default()
-        .add_messages([
-            // Claim rewards
-            claim_rewards_msg(
-                config.staking.code_hash.clone(),
-                config.staking.address.to_string(),
-            )?,
-            // Re-stake rewards
-            generate_stake_msg(
-                Uint128::from(rewards).saturating_sub(fee),
+    let weights = staking_weights(&config);
+    let mut messages: Vec<CosmosMsg> = vec![];
+    // Claim rewards
+    for backend in config.staking.iter() {
+        messages.push(claim_rewards_msg(
+            backend.contract.code_hash.clone(),
+            backend.contract.address.to_string(),
+        )?);
+    }
+    // Re-stake rewards
+    let restake = Uint128::from(rewards).saturating_sub(fee);
+    let restake_parts = split_deposit(restake.u128(), &staked_balances, &weights);
+    for (backend, part) in config.staking.iter().zip(restake_parts.iter()) {
+        if *part > 0 {
+            messages.push(generate_stake_msg(
+                Uint128::from(*part),
                 Some(true),
                 &config,
-            )?,
+                &backend.contract,
+            )?);
+        }
+    }
+    // Staked balances after re-staking, the transfer is taken from them
+    let restaked_balances: Vec<u128> = staked_balances
+        .iter()
+        .zip(restake_parts.iter())
+        .map(|(s, p)| s + p)
+        .collect();
+    let transfer_parts = split_withdrawal(deposit.u128(), &restaked_balances, &weights);
+    Ok(Response::default()
+        .add_messages(messages)
+        .add_messages([
             // Burn derivatives sent
             burn_msg(
                 amount,
@@ -910,15 +1109,25 @@ fn try_transfer_staked(
                 config.token.code_hash.clone(),
                 config.token.address.to_string(),
             )?,
-            // Transfer staked
-            transfer_staked_msg(
-                config.staking.code_hash,
-                config.staking.address.to_string(),
-                deposit,
-                recipient,
-                Some(true),
-            )?,
         ])
+        // Transfer staked
+        .add_messages(
+            config
+                .staking
+                .iter()
+                .zip(transfer_parts.into_iter())
+                .filter(|(_, part)| *part > 0)
+                .map(|(backend, part)| {
+                    transfer_staked_msg(
+                        backend.contract.code_hash.clone(),
+                        backend.contract.address.to_string(),
+                        Uint128::from(part),
+                        recipient.clone(),
+                        Some(true),
+                    )
+                })
+                .collect::<StdResult<Vec<CosmosMsg>>>()?,
+        )
         .set_data(to_binary(&ExecuteAnswer::TransferStaked {
             tokens_returned: deposit,
             amount_sent: amount,
@@ -958,7 +1167,6 @@ fn try_unbond(
         config.derivative.address.to_string(),
         true,
     )?;
-    let staking_info = get_staking_contract_config(deps.querier, &config)?;
     let amount = Uint128::try_from(amt)?;
     if info.sender != config.derivative.address {
         return Err(StdError::generic_err(
@@ -972,7 +1180,8 @@ fn try_unbond(
 
     let (_, _, delegatable) = get_delegatable(deps.querier, &env.contract.address, &config)?;
 
-    let staked = get_staked_shd(deps.querier, &env.contract.address, &config)?;
+    let staked_balances = get_staked_balances(deps.querier, &env.contract.address, &config)?;
+    let staked: u128 = staked_balances.iter().sum();
     let pool = delegatable + staked;
     // unwrap is ok because multiplying 2 u128 ints can not overflow a u256
     let number = Uint256::from(amount)
@@ -995,29 +1204,57 @@ fn try_unbond(
         )));
     }
 
-    // Store unbonding temporarily
-    // This unbonding is used in unbond sub-message reply handler
-    // Due to that in reply handler you can't access sender information
-    // and this is required to store user's unbondings
-    let unbonding = InProcessUnbonding {
-        id: Uint128::zero(),
-        amount: shd_to_be_received,
-        owner: from,
-        complete: Uint128::from(env.block.time.seconds())
-            .checked_add(staking_info.unbond_period)?,
-    };
-    PENDING_UNBONDING.save(deps.storage, &unbonding)?;
-    CONFIG.save(deps.storage, &config)?;
+    let id = UNBONDING_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    UNBONDING_COUNT.save(deps.storage, &id)?;
+
+    // Source the unbonding from the staking contracts furthest above their weight
+    let parts = split_withdrawal(
+        shd_to_be_received.u128(),
+        &staked_balances,
+        &staking_weights(&config),
+    );
+    let mut unbond_period = Uint128::zero();
+    // Store unbond msgs sent temporarily
+    // These are used in unbond sub-message reply handler
+    // to link the staking contracts unbondings to this one
+    let mut pending_unbondings: Vec<InProcessUnbonding> = vec![];
+    for (backend, part) in config.staking.iter().zip(parts.into_iter()) {
+        if part == 0 {
+            continue;
+        }
+        let staking_info = get_staking_contract_config(deps.querier, &backend.contract)?;
+        unbond_period = unbond_period.max(staking_info.unbond_period);
+        pending_unbondings.push(InProcessUnbonding {
+            id: Uint128::from(id),
+            staking: backend.contract.address.clone(),
+        });
+
+        response = response.add_submessage(SubMsg::reply_always(
+            unbond_msg(
+                Uint128::from(part),
+                backend.contract.code_hash.clone(),
+                backend.contract.address.to_string(),
+                Some(true),
+            )?,
+            UNBOND_REPLY_ID,
+        ));
+    }
+    PENDING_UNBONDING.save(deps.storage, &pending_unbondings)?;
 
-    response = response.add_submessage(SubMsg::reply_always(
-        unbond_msg(
-            shd_to_be_received,
-            config.staking.code_hash.clone(),
-            config.staking.address.to_string(),
-            Some(true),
-        )?,
-        UNBOND_REPLY_ID,
-    ));
+    // The unbonding matures once every staking contract unbonding has
+    let complete = Uint128::from(env.block.time.seconds()).checked_add(unbond_period)?;
+    UnbondingStore::save(
+        deps.storage,
+        id,
+        &Unbonding {
+            id: Uint128::from(id),
+            amount: shd_to_be_received,
+            complete,
+        },
+    )?;
+    let mut users_unbondings_ids = UnbondingIdsStore::load(deps.storage, &from);
+    users_unbondings_ids.push(id);
+    UnbondingIdsStore::save(deps.storage, &from, users_unbondings_ids)?;
 
     Ok(response
         .add_attribute("unbonded_amount", shd_to_be_received)
@@ -1035,8 +1272,127 @@ fn try_unbond(
         .set_data(to_binary(&ExecuteAnswer::Unbond {
             shd_to_be_received,
             tokens_redeemed: amount,
-            estimated_time_of_maturity: Uint128::from(env.block.time.seconds())
-                .checked_add(staking_info.unbond_period)?,
+            estimated_time_of_maturity: complete,
+        })?))
+}
+
+/// `try_instant_unbond` is called when a user sends a derivative token to the contract to redeem it
+/// right away. The SHD is taken from the liquidity buffer for an extra fee, if the buffer can't cover
+/// it the derivative tokens are unbonded as in `try_unbond`
+///
+/// Arguments:
+///
+/// * `deps`: DepsMut,
+/// * `env`: The environment in which the contract is running.
+/// * `info`: MessageInfo - this is the information about the message that was sent to the contract.
+/// * `from`: The address of the user who is unbonding
+/// * `amt`: The amount of derivative tokens to be redeemed.
+///
+/// Returns:
+///
+/// The amount of SHD sent to the user.
+fn try_instant_unbond(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    from: Addr,
+    amt: Uint256,
+    priority: ContractStatusLevel,
+) -> StdResult<Response> {
+    check_status(deps.storage, priority.clone())?;
+    let config = CONFIG.load(deps.storage)?;
+    let derivative_token_info = get_token_info(
+        deps.querier,
+        RESPONSE_BLOCK_SIZE,
+        config.derivative.code_hash.clone(),
+        config.derivative.address.to_string(),
+        true,
+    )?;
+    let amount = Uint128::try_from(amt)?;
+    if info.sender != config.derivative.address {
+        return Err(StdError::generic_err(
+            "Sender is not derivative (SNIP20) contract",
+        ));
+    }
+
+    if amount == Uint128::zero() {
+        return Err(StdError::generic_err("0 amount sent to unbond"));
+    }
+
+    let (available, _, delegatable) =
+        get_delegatable(deps.querier, &env.contract.address, &config)?;
+    let staked = get_staked_shd(deps.querier, &env.contract.address, &config)?;
+    let pool = delegatable + staked;
+    // unwrap is ok because multiplying 2 u128 ints can not overflow a u256
+    let number = Uint256::from(amount)
+        .checked_mul(Uint256::from(pool))
+        .unwrap();
+    // unwrap is ok because derivative token supply could not have been 0 if we were able
+    // to burn
+    let unbond_amount = Uint128::try_from(
+        number
+            .checked_div(Uint256::from(derivative_token_info.total_supply.unwrap()))
+            .unwrap(),
+    )?;
+    // Same amount as the one try_unbond would unbond
+    let (_, withdrawn) = get_fee(unbond_amount, &config.fees.unbonding)?;
+
+    let buffer = LIQUIDITY_BUFFER.may_load(deps.storage)?.unwrap_or_default();
+    if withdrawn.u128() > buffer.min(available) {
+        // Not enough liquidity, go through the unbonding queue instead
+        return try_unbond(deps, env, info, from, amt, priority);
+    }
+
+    // Fees charged when claiming an unbonding plus the instant unbonding fee
+    let (fee, claimed) = get_fee(withdrawn, &config.fees.unbonding)?;
+    let (instant_fee, shd_received) = get_fee(claimed, &config.fees.instant_unbonding)?;
+
+    if shd_received.is_zero() {
+        return Err(StdError::generic_err(format!(
+            "Redeeming {} derivative tokens would be worth less than 1 SHD",
+            amount
+        )));
+    }
+    LIQUIDITY_BUFFER.save(deps.storage, &(buffer - withdrawn.u128()))?;
+
+    Ok(Response::default()
+        .add_attribute("instant_unbonded_amount", shd_received)
+        .add_messages(vec![
+            burn_msg(
+                amount,
+                Some(format!(
+                    "Burn {} derivatives to receive {} SHD",
+                    amount, shd_received
+                )),
+                config.derivative.entropy,
+                RESPONSE_BLOCK_SIZE,
+                config.derivative.code_hash.clone(),
+                config.derivative.address.to_string(),
+            )?,
+            send_msg(
+                config.fees.collector.to_string(),
+                fee.checked_add(instant_fee)?,
+                None,
+                Some(base64::encode(&"Payment of fee for instant unbonding SHD")),
+                config.token.entropy.clone(),
+                RESPONSE_BLOCK_SIZE,
+                config.token.code_hash.clone(),
+                config.token.address.to_string(),
+            )?,
+            send_msg(
+                from.to_string(),
+                shd_received,
+                None,
+                Some(format!("Instant unbonding {} SHD tokens", shd_received)),
+                config.token.entropy,
+                RESPONSE_BLOCK_SIZE,
+                config.token.code_hash,
+                config.token.address.to_string(),
+            )?,
+        ])
+        .set_data(to_binary(&ExecuteAnswer::InstantUnbond {
+            tokens_redeemed: amount,
+            shd_received,
         })?))
 }
 
@@ -1080,7 +1436,7 @@ fn get_available_shd<C: CustomQuery>(
     Ok(100000000_u128)
 }
 
-/// It queries the staking contract to get the amount of staked SHD for the given contract address
+/// It queries every staking contract to get the amount of staked SHD for the given contract address
 ///
 /// Arguments:
 ///
@@ -1090,26 +1446,55 @@ fn get_available_shd<C: CustomQuery>(
 ///
 /// Returns:
 ///
-/// The balance of the staking contract.
+/// The balance in each staking contract, in the same order as `config.staking`.
 #[cfg(not(test))]
-fn get_staked_shd<C: CustomQuery>(
+fn get_staked_balances<C: CustomQuery>(
     querier: QuerierWrapper<C>,
     contract_addr: &Addr,
     config: &Config,
-) -> StdResult<u128> {
-    let balance = staking_balance_query(
-        contract_addr.to_string(),
-        config.staking_contract_vk.clone(),
-        querier,
-        config.staking.code_hash.to_string(),
-        config.staking.address.to_string(),
-    )?;
+) -> StdResult<Vec<u128>> {
+    let mut balances = vec![];
+    for backend in config.staking.iter() {
+        let balance = staking_balance_query(
+            contract_addr.to_string(),
+            config.staking_contract_vk.clone(),
+            querier,
+            backend.contract.code_hash.to_string(),
+            backend.contract.address.to_string(),
+        )?;
+        balances.push(balance.amount.u128());
+    }
 
-    Ok(balance.amount.u128())
+    Ok(balances)
 }
 #[cfg(test)]
-fn get_staked_shd<C: CustomQuery>(_: QuerierWrapper<C>, _: &Addr, _: &Config) -> StdResult<u128> {
-    Ok(300000000)
+fn get_staked_balances<C: CustomQuery>(
+    _: QuerierWrapper<C>,
+    _: &Addr,
+    config: &Config,
+) -> StdResult<Vec<u128>> {
+    Ok(vec![300000000; config.staking.len()])
+}
+
+/// It sums the staked SHD of the given contract address across every staking contract
+///
+/// Arguments:
+///
+/// * `querier`: The querier object that will be used to query the blockchain.
+/// * `contract_addr`: The address of the contract that is being queried.
+/// * `config`: The configuration of the contract.
+///
+/// Returns:
+///
+/// The total staked balance.
+fn get_staked_shd<C: CustomQuery>(
+    querier: QuerierWrapper<C>,
+    contract_addr: &Addr,
+    config: &Config,
+) -> StdResult<u128> {
+    Ok(get_staked_balances(querier, contract_addr, config)?
+        .into_iter()
+        .sum())
 }
 
 /// It queries the rewards generated for this contract address.
@@ -1157,13 +1542,28 @@ fn query_rewards<C: CustomQuery>(
     contract_addr: &Addr,
     config: &Config,
 ) -> StdResult<Rewards> {
-    rewards_query(
-        contract_addr.to_string(),
-        config.staking_contract_vk.clone(),
-        querier,
-        config.staking.code_hash.to_string(),
-        config.staking.address.to_string(),
-    )
+    // Merge the rewards of every staking contract by token
+    let mut rewards: Vec<Reward> = vec![];
+    for backend in config.staking.iter() {
+        let backend_rewards = rewards_query(
+            contract_addr.to_string(),
+            config.staking_contract_vk.clone(),
+            querier,
+            backend.contract.code_hash.to_string(),
+            backend.contract.address.to_string(),
+        )?;
+        for r in backend_rewards.rewards.into_iter() {
+            match rewards
+                .iter_mut()
+                .find(|x| x.token.address == r.token.address)
+            {
+                Some(reward) => reward.amount += r.amount,
+                None => rewards.push(r),
+            }
+        }
+    }
+
+    Ok(Rewards { rewards })
 }
 
 #[cfg(test)]
@@ -1188,7 +1588,7 @@ fn query_rewards<C: CustomQuery>(_: QuerierWrapper<C>, _: &Addr, _: &Config) ->
 // Allow warn code because mock queries make warnings to show up
 fn get_staking_contract_config<C: CustomQuery>(
     _: QuerierWrapper<C>,
-    _: &Config,
+    _: &ContractInfo,
 ) -> StdResult<StakingConfig> {
     Ok(StakingConfig {
         admin_auth: RawContract {
@@ -1210,7 +1610,7 @@ fn get_staking_contract_config<C: CustomQuery>(
 /// Arguments:
 ///
 /// * `querier`: The querier object that will be used to query the contract.
-/// * `staking_info`: The staking contract information.
+/// * `staking`: The staking contract information.
 ///
 /// Returns:
 ///
@@ -1218,12 +1618,12 @@ fn get_staking_contract_config<C: CustomQuery>(
 #[cfg(not(test))]
 fn get_staking_contract_config<C: CustomQuery>(
     querier: QuerierWrapper<C>,
-    staking_info: &Config,
+    staking: &ContractInfo,
 ) -> StdResult<StakingConfig> {
     config_query(
         querier,
-        staking_info.staking.code_hash.clone(),
-        staking_info.staking.address.to_string(),
+        staking.code_hash.clone(),
+        staking.address.to_string(),
     )
 }
 
@@ -1402,8 +1802,8 @@ fn check_if_admin(
 ///
 /// * `amount`: The amount of SHD to stake
 /// * `compound`: Whether to compound the interest or not.
-/// * `config`: The configuration file that contains the staking contract address, token address, token
-/// code hash, and entropy.
+/// * `config`: The configuration file that contains the token address, token code hash, and entropy.
+/// * `staking`: The staking contract the SHD is sent to.
 ///
 /// Returns:
 ///
@@ -1412,12 +1812,13 @@ fn generate_stake_msg(
     amount: Uint128,
     compound: Option<bool>,
     config: &Config,
+    staking: &ContractInfo,
 ) -> StdResult<CosmosMsg> {
     let memo =
         Some(to_binary(&format!("Staking {} SHD into staking contract", amount))?.to_base64());
     let msg = Some(to_binary(&Action::Stake { compound })?);
     send_msg(
-        config.staking.address.to_string(),
+        staking.address.to_string(),
         amount,
         msg,
         memo,
@@ -1428,6 +1829,196 @@ fn generate_stake_msg(
     )
 }
 
+/// It checks that a list of staking contracts can be used, it must not be empty, have some weight
+/// and keep every staking contract of `current` so their unbondings can still be withdrawn
+///
+/// Arguments:
+///
+/// * `staking`: The new list of staking contracts.
+/// * `current`: The staking contracts currently in use.
+///
+/// Returns:
+///
+/// Ok if the list is valid.
+fn validate_staking_backends(
+    staking: &[StakingBackend],
+    current: &[StakingBackend],
+) -> StdResult<()> {
+    if staking.is_empty() {
+        return Err(StdError::generic_err(
+            "At least one staking contract is required",
+        ));
+    }
+    if staking.iter().all(|b| b.weight == 0) {
+        return Err(StdError::generic_err(
+            "At least one staking contract must have a weight",
+        ));
+    }
+    for (i, backend) in staking.iter().enumerate() {
+        if staking[..i]
+            .iter()
+            .any(|b| b.contract.address == backend.contract.address)
+        {
+            return Err(StdError::generic_err(format!(
+                "Staking contract {} is duplicated",
+                backend.contract.address
+            )));
+        }
+    }
+    for backend in current.iter() {
+        if !staking
+            .iter()
+            .any(|b| b.contract.address == backend.contract.address)
+        {
+            return Err(StdError::generic_err(format!(
+                "Staking contract {} can't be removed, set its weight to 0 instead",
+                backend.contract.address
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// It finds a staking contract of the config by its address
+///
+/// Arguments:
+///
+/// * `config`: The configuration of the contract.
+/// * `address`: The staking contract address.
+///
+/// Returns:
+///
+/// The staking contract information.
+fn get_staking_backend<'a>(config: &'a Config, address: &Addr) -> StdResult<&'a StakingBackend> {
+    config
+        .staking
+        .iter()
+        .find(|b| b.contract.address == *address)
+        .ok_or_else(|| StdError::generic_err(format!("Unknown staking contract {}", address)))
+}
+
+fn staking_weights(config: &Config) -> Vec<u32> {
+    config.staking.iter().map(|b| b.weight).collect()
+}
+
+/// It splits a deposit between the staking contracts, filling the ones furthest below their
+/// weighted share of the total staked SHD first
+///
+/// Arguments:
+///
+/// * `amount`: The amount of SHD to stake.
+/// * `staked`: The SHD currently staked in each staking contract.
+/// * `weights`: The weight of each staking contract.
+///
+/// Returns:
+///
+/// The amount to stake in each staking contract, in the same order as `staked`.
+pub fn split_deposit(amount: u128, staked: &[u128], weights: &[u32]) -> Vec<u128> {
+    let total = staked.iter().sum::<u128>() + amount;
+    let targets = weighted_targets(total, weights);
+    let deficits: Vec<u128> = targets
+        .iter()
+        .zip(staked.iter())
+        .map(|(t, s)| t.saturating_sub(*s))
+        .collect();
+
+    let mut parts = split_proportionally(amount, &deficits);
+    // Rounding leftovers go to the largest part, or to the heaviest staking contract if every
+    // part is empty
+    let remainder = amount - parts.iter().sum::<u128>();
+    if remainder > 0 {
+        let index = if parts.iter().any(|p| *p > 0) {
+            max_index(&parts)
+        } else {
+            max_index(&weights.iter().map(|w| *w as u128).collect::<Vec<u128>>())
+        };
+        parts[index] += remainder;
+    }
+
+    parts
+}
+
+/// It splits a withdrawal between the staking contracts, taking from the ones furthest above their
+/// weighted share of the remaining staked SHD first
+///
+/// Arguments:
+///
+/// * `amount`: The amount of SHD to unbond.
+/// * `staked`: The SHD currently staked in each staking contract.
+/// * `weights`: The weight of each staking contract.
+///
+/// Returns:
+///
+/// The amount to unbond from each staking contract, in the same order as `staked`.
+pub fn split_withdrawal(amount: u128, staked: &[u128], weights: &[u32]) -> Vec<u128> {
+    let total: u128 = staked.iter().sum();
+    let withdrawable = amount.min(total);
+    let targets = weighted_targets(total - withdrawable, weights);
+    let excesses: Vec<u128> = staked
+        .iter()
+        .zip(targets.iter())
+        .map(|(s, t)| s.saturating_sub(*t))
+        .collect();
+
+    // Excesses add up to at least `withdrawable` so no part is bigger than its staked balance
+    let mut parts = split_proportionally(withdrawable, &excesses);
+    // Rounding leftovers are taken from the first staking contracts with SHD left
+    let mut remainder = withdrawable - parts.iter().sum::<u128>();
+    for (part, staked) in parts.iter_mut().zip(staked.iter()) {
+        let taken = remainder.min(staked - *part);
+        *part += taken;
+        remainder -= taken;
+    }
+    // Anything above the staked SHD is left to the biggest staking contract to reject
+    if amount > withdrawable {
+        let index = max_index(staked);
+        parts[index] += amount - withdrawable;
+    }
+
+    parts
+}
+
+// Share of total each staking contract should hold according to its weight
+fn weighted_targets(total: u128, weights: &[u32]) -> Vec<u128> {
+    let shares: Vec<u128> = weights.iter().map(|w| *w as u128).collect();
+    split_proportionally(total, &shares)
+}
+
+// Splits amount proportionally to shares, rounding down
+fn split_proportionally(amount: u128, shares: &[u128]) -> Vec<u128> {
+    let total = Uint256::from(shares.iter().sum::<u128>());
+    shares
+        .iter()
+        .map(|share| {
+            if total.is_zero() {
+                return 0;
+            }
+            // unwrap is ok because multiplying 2 u128 ints can not overflow a u256,
+            // total is not zero and the result is never bigger than amount
+            Uint128::try_from(
+                Uint256::from(amount)
+                    .checked_mul(Uint256::from(*share))
+                    .unwrap()
+                    .checked_div(total)
+                    .unwrap(),
+            )
+            .unwrap()
+            .u128()
+        })
+        .collect()
+}
+
+fn max_index(values: &[u128]) -> usize {
+    let mut index = 0;
+    for (i, v) in values.iter().enumerate() {
+        if *v > values[index] {
+            index = i;
+        }
+    }
+    index
+}
+
 /// It checks if the sender is an admin, and if so, it sets the contract status to the value passed in
 ///
 /// Arguments:
@@ -1510,7 +2101,12 @@ pub fn sync_rewarded_tokens(
                 &info.sender,
                 &env,
                 &config.prng_seed,
-                config.staking.entropy.clone().unwrap_or_default().as_ref(),
+                config.staking[0]
+                    .contract
+                    .entropy
+                    .clone()
+                    .unwrap_or_default()
+                    .as_ref(),
             );
 
             let token: Token = Token {
@@ -1755,6 +2351,10 @@ fn query_staking_info(deps: &Deps, env: &Env) -> StdResult<Binary> {
     let bonded = get_staked_shd(deps.querier, &env.contract.address, &config)?;
     let rewards = get_rewards(deps.querier, &env.contract.address, &config)?;
     let available = get_available_shd(deps.querier, &env.contract.address, &config)?;
+    let liquidity_buffer = LIQUIDITY_BUFFER
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .min(available);
 
     let total_supply = derivative_info.total_supply.unwrap_or(Uint128::zero());
 
@@ -1770,12 +2370,18 @@ fn query_staking_info(deps: &Deps, env: &Env) -> StdResult<Binary> {
         Uint128::try_from(number.checked_div(Uint256::from(total_supply)).unwrap())?
     };
 
-    let staking_contract_config = get_staking_contract_config(deps.querier, &config)?;
+    // Unbondings can be sourced from any staking contract, report the longest period
+    let mut unbonding_time = Uint128::zero();
+    for backend in config.staking.iter() {
+        let staking_contract_config = get_staking_contract_config(deps.querier, &backend.contract)?;
+        unbonding_time = unbonding_time.max(staking_contract_config.unbond_period);
+    }
 
     to_binary(&QueryAnswer::StakingInfo {
-        unbonding_time: staking_contract_config.unbond_period,
+        unbonding_time,
         bonded_shd: Uint128::from(bonded),
         available_shd: Uint128::from(available),
+        liquidity_buffer: Uint128::from(liquidity_buffer),
         rewards: Uint128::from(rewards),
         total_derivative_token_supply: total_supply,
         price,
@@ -1797,6 +2403,7 @@ fn query_fee_info(deps: &Deps) -> StdResult<Binary> {
     to_binary(&QueryAnswer::FeeInfo {
         staking: config.fees.staking,
         unbonding: config.fees.unbonding,
+        instant_unbonding: config.fees.instant_unbonding,
         collector: config.fees.collector,
     })
 }
@@ -1828,11 +2435,14 @@ mod tests {
                 code_hash: String::from("derivative_snip20_info_codehash"),
                 entropy: Some(String::from("4359o74nd8dnkjerjrh")),
             },
-            staking: CustomContractInfo {
-                address: Addr::unchecked("staking_contract_info_address"),
-                code_hash: String::from("staking_contract_info_code_hash"),
-                entropy: Some(String::from("4359o74nd8dnkjerjrh")),
-            },
+            staking: vec![StakingBackend {
+                contract: CustomContractInfo {
+                    address: Addr::unchecked("staking_contract_info_address"),
+                    code_hash: String::from("staking_contract_info_code_hash"),
+                    entropy: Some(String::from("4359o74nd8dnkjerjrh")),
+                },
+                weight: 1,
+            }],
             query_auth: CustomContractInfo {
                 address: Addr::unchecked("authentication_contract_info_address"),
                 code_hash: String::from("authentication_contract_info_code_hash"),
@@ -1856,8 +2466,13 @@ mod tests {
                     rate: 5,
                     decimal_places: 2_u8,
                 },
+                instant_unbonding: Fee {
+                    rate: 1,
+                    decimal_places: 2_u8,
+                },
                 collector: Addr::unchecked("collector_address"),
             },
+            liquidity_buffer: None,
         };
 
         (instantiate(deps.as_mut(), env, info, init_msg), deps)
@@ -2254,14 +2869,24 @@ mod tests {
 
         assert_eq!(
             unbonding_processing,
-            InProcessUnbonding {
-                id: Uint128::zero(),
-                owner: Addr::unchecked("bob"),
+            vec![InProcessUnbonding {
+                id: Uint128::from(1_u128),
+                staking: Addr::unchecked("staking_contract_info_address"),
+            }]
+        );
+        assert_eq!(
+            UnbondingIdsStore::load(&deps.storage, &Addr::unchecked("bob")),
+            vec![1_u128]
+        );
+        assert_eq!(
+            UnbondingStore::may_load(&deps.storage, 1),
+            Some(Unbonding {
+                id: Uint128::from(1_u128),
                 amount: Uint128::from(21375000000000_u128),
                 complete: Uint128::from(env.block.time.seconds())
                     .checked_add(Uint128::from(300_u32))
                     .unwrap(),
-            }
+            })
         )
     }
 
@@ -2277,6 +2902,7 @@ mod tests {
         let handle_msg = ExecuteMsg::UpdateFees {
             staking: None,
             unbonding: None,
+            instant_unbonding: None,
             collector: None,
         };
         let info = mock_info("not_admin", &[]);
@@ -2304,6 +2930,7 @@ mod tests {
         let handle_msg = ExecuteMsg::UpdateFees {
             staking: None,
             unbonding: None,
+            instant_unbonding: None,
             collector: None,
         };
         let info = mock_info("admin", &[]);
@@ -2336,6 +2963,7 @@ mod tests {
             }),
             collector: Some(Addr::unchecked("new_collector")),
             unbonding: None,
+            instant_unbonding: None,
         };
         let info = mock_info("admin", &[]);
 
@@ -2410,6 +3038,7 @@ mod tests {
             unbonding_time,
             bonded_shd,
             available_shd,
+            liquidity_buffer,
             rewards,
             total_derivative_token_supply,
             price,
@@ -2418,6 +3047,7 @@ mod tests {
                 unbonding_time,
                 bonded_shd,
                 available_shd,
+                liquidity_buffer,
                 rewards,
                 total_derivative_token_supply,
                 price,
@@ -2425,6 +3055,7 @@ mod tests {
                 unbonding_time,
                 bonded_shd,
                 available_shd,
+                liquidity_buffer,
                 rewards,
                 total_derivative_token_supply,
                 price,
@@ -2435,6 +3066,7 @@ mod tests {
         assert_eq!(unbonding_time, Uint128::from(300_u32));
         assert_eq!(bonded_shd, Uint128::from(300000000_u128));
         assert_eq!(available_shd, Uint128::from(100000000_u128));
+        assert_eq!(liquidity_buffer, Uint128::zero());
         assert_eq!(rewards, Uint128::from(100000000_u128));
         assert_eq!(total_derivative_token_supply, Uint128::from(2000_u128));
         assert_eq!(price, Uint128::from(250000000000_u128));
@@ -2450,14 +3082,16 @@ mod tests {
         );
         let query_msg = QueryMsg::FeeInfo {};
         let query_result = query(deps.as_ref(), mock_env(), query_msg);
-        let (staking, unbonding, collector) = match from_binary(&query_result.unwrap()).unwrap() {
-            QueryAnswer::FeeInfo {
-                staking,
-                unbonding,
-                collector,
-            } => (staking, unbonding, collector),
-            other => panic!("Unexpected: {:?}", other),
-        };
+        let (staking, unbonding, instant_unbonding, collector) =
+            match from_binary(&query_result.unwrap()).unwrap() {
+                QueryAnswer::FeeInfo {
+                    staking,
+                    unbonding,
+                    instant_unbonding,
+                    collector,
+                } => (staking, unbonding, instant_unbonding, collector),
+                other => panic!("Unexpected: {:?}", other),
+            };
 
         assert_eq!(
             staking,
@@ -2473,6 +3107,13 @@ mod tests {
                 decimal_places: 2_u8,
             }
         );
+        assert_eq!(
+            instant_unbonding,
+            Fee {
+                rate: 1,
+                decimal_places: 2_u8,
+            }
+        );
 
         assert_eq!(collector, Addr::unchecked("collector_address"));
     }
@@ -2516,9 +3157,11 @@ mod tests {
 
         let config = CONFIG.load(&deps.storage).unwrap();
 
-        let msgs = vec![
-            compound_msg(config.staking.code_hash, config.staking.address.to_string()).unwrap(),
-        ];
+        let msgs = vec![compound_msg(
+            config.staking[0].contract.code_hash.clone(),
+            config.staking[0].contract.address.to_string(),
+        )
+        .unwrap()];
 
         assert_eq!(
             handle_result.unwrap(),
@@ -2576,8 +3219,8 @@ mod tests {
         let msg = SubMsg::reply_always(
             unbond_msg(
                 Uint128::from(100000000_u128),
-                config.staking.code_hash,
-                config.staking.address.to_string(),
+                config.staking[0].contract.code_hash.clone(),
+                config.staking[0].contract.address.to_string(),
                 Some(false),
             )
             .unwrap(),
@@ -2639,8 +3282,8 @@ mod tests {
             handle_result.unwrap(),
             Response::default().add_messages(vec![
                 claim_rewards_msg(
-                    config.staking.code_hash.clone(),
-                    config.staking.address.to_string(),
+                    config.staking[0].contract.code_hash.clone(),
+                    config.staking[0].contract.address.to_string(),
                 )
                 .unwrap(),
                 send_msg(
@@ -2657,4 +3300,379 @@ mod tests {
             ])
         );
     }
+
+    #[test]
+    fn test_split_deposit() {
+        // Fills the staking contracts below their weight first
+        assert_eq!(split_deposit(100, &[0, 0], &[1, 1]), vec![50, 50]);
+        assert_eq!(split_deposit(100, &[100, 0], &[1, 1]), vec![0, 100]);
+        assert_eq!(split_deposit(100, &[300, 0], &[1, 1]), vec![0, 100]);
+        assert_eq!(split_deposit(400, &[100, 100], &[1, 3]), vec![50, 350]);
+        // Weight 0 staking contracts don't receive deposits
+        assert_eq!(split_deposit(100, &[0, 50], &[0, 1]), vec![0, 100]);
+        // Rounding leftovers go to the largest part
+        assert_eq!(split_deposit(10, &[0, 0, 0], &[1, 1, 1]), vec![4, 3, 3]);
+        assert_eq!(split_deposit(1, &[5, 5], &[1, 2]), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_split_withdrawal() {
+        // Takes from the staking contracts above their weight first
+        assert_eq!(split_withdrawal(100, &[100, 100], &[1, 1]), vec![50, 50]);
+        assert_eq!(split_withdrawal(100, &[200, 100], &[1, 1]), vec![100, 0]);
+        assert_eq!(split_withdrawal(200, &[300, 300], &[1, 3]), vec![200, 0]);
+        // Weight 0 staking contracts are drained first
+        assert_eq!(split_withdrawal(100, &[50, 200], &[0, 1]), vec![50, 50]);
+        // Rounding leftovers are taken from the first staking contracts
+        assert_eq!(
+            split_withdrawal(10, &[10, 10, 10], &[1, 1, 1]),
+            vec![4, 3, 3]
+        );
+        // Amounts above the staked SHD go to the biggest staking contract
+        assert_eq!(split_withdrawal(100, &[20, 30], &[1, 1]), vec![20, 80]);
+    }
+
+    #[test]
+    fn test_update_staking() {
+        let (init_result, mut deps) = init_helper();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let config = CONFIG.load(&deps.storage).unwrap();
+        let second = StakingBackend {
+            contract: CustomContractInfo {
+                address: Addr::unchecked("second_staking_contract_address"),
+                code_hash: String::from("second_staking_contract_code_hash"),
+                entropy: None,
+            },
+            weight: 3,
+        };
+
+        let handle_msg = ExecuteMsg::UpdateStaking {
+            staking: vec![second.clone()],
+        };
+        let handle_result = execute(deps.as_mut(), mock_env(), mock_info("bob", &[]), handle_msg);
+        assert_eq!(
+            extract_error_msg(handle_result),
+            "This is an admin command. Admin commands can only be run from admin address"
+        );
+
+        // Current staking contracts can't be removed
+        let handle_msg = ExecuteMsg::UpdateStaking {
+            staking: vec![second.clone()],
+        };
+        let handle_result = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            handle_msg,
+        );
+        assert_eq!(
+            extract_error_msg(handle_result),
+            "Staking contract staking_contract_info_address can't be removed, set its weight to 0 instead"
+        );
+
+        let handle_msg = ExecuteMsg::UpdateStaking {
+            staking: vec![
+                StakingBackend {
+                    weight: 0,
+                    ..config.staking[0].clone()
+                },
+                StakingBackend {
+                    weight: 0,
+                    ..second.clone()
+                },
+            ],
+        };
+        let handle_result = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            handle_msg,
+        );
+        assert_eq!(
+            extract_error_msg(handle_result),
+            "At least one staking contract must have a weight"
+        );
+
+        let staking = vec![config.staking[0].clone(), second.clone()];
+        let handle_msg = ExecuteMsg::UpdateStaking {
+            staking: staking.clone(),
+        };
+        let handle_result = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            handle_msg,
+        );
+        assert!(
+            handle_result.is_ok(),
+            "handle() failed: {}",
+            handle_result.err().unwrap()
+        );
+        let config = CONFIG.load(&deps.storage).unwrap();
+        assert_eq!(config.staking, staking);
+
+        // Both staking contracts hold 300000000, the deposit goes to the one below its weight
+        let handle_msg = ExecuteMsg::Receive {
+            sender: Addr::unchecked(""),
+            from: Addr::unchecked("bob"),
+            amount: Uint256::from(300000000 as u32),
+            msg: Some(to_binary(&ReceiverMsg::Stake {}).unwrap()),
+        };
+        let info = mock_info("shade_contract_info_address", &[]);
+        let handle_result = execute(deps.as_mut(), mock_env(), info, handle_msg);
+        assert!(
+            handle_result.is_ok(),
+            "handle() failed: {}",
+            handle_result.err().unwrap()
+        );
+        let messages: Vec<CosmosMsg> = handle_result
+            .unwrap()
+            .messages
+            .into_iter()
+            .map(|m| m.msg)
+            .collect();
+        assert!(messages.contains(
+            &generate_stake_msg(
+                Uint128::from(285000000_u128),
+                Some(true),
+                &config,
+                &second.contract,
+            )
+            .unwrap()
+        ));
+
+        // Unbondings are taken from the one above its weight
+        let handle_msg = ExecuteMsg::Receive {
+            sender: Addr::unchecked(""),
+            from: Addr::unchecked("bob"),
+            amount: Uint256::from(1 as u32),
+            msg: Some(to_binary(&ReceiverMsg::Unbond {}).unwrap()),
+        };
+        let info = mock_info("derivative_snip20_info_address", &[]);
+        let handle_result = execute(deps.as_mut(), mock_env(), info, handle_msg);
+        assert!(
+            handle_result.is_ok(),
+            "handle() failed: {}",
+            handle_result.err().unwrap()
+        );
+        assert_eq!(
+            PENDING_UNBONDING.load(&deps.storage).unwrap(),
+            vec![InProcessUnbonding {
+                id: Uint128::from(1_u128),
+                staking: Addr::unchecked("staking_contract_info_address"),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_liquidity_buffer_staking() {
+        let (init_result, mut deps) = init_helper();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let share = Fee {
+            rate: 10,
+            decimal_places: 2_u8,
+        };
+        let handle_msg = ExecuteMsg::UpdateLiquidityBuffer {
+            share: share.clone(),
+        };
+        let handle_result = execute(deps.as_mut(), mock_env(), mock_info("bob", &[]), handle_msg);
+        assert_eq!(
+            extract_error_msg(handle_result),
+            "This is an admin command. Admin commands can only be run from admin address"
+        );
+
+        let handle_msg = ExecuteMsg::UpdateLiquidityBuffer {
+            share: Fee {
+                rate: 101,
+                decimal_places: 2_u8,
+            },
+        };
+        let handle_result = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            handle_msg,
+        );
+        assert_eq!(
+            extract_error_msg(handle_result),
+            "Liquidity buffer can't be more than 100%"
+        );
+
+        let handle_msg = ExecuteMsg::UpdateLiquidityBuffer {
+            share: Fee {
+                rate: 1,
+                decimal_places: 10_u8,
+            },
+        };
+        let handle_result = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            handle_msg,
+        );
+        assert_eq!(
+            extract_error_msg(handle_result),
+            "Liquidity buffer can't have more than 9 decimal places"
+        );
+
+        let handle_msg = ExecuteMsg::UpdateLiquidityBuffer {
+            share: share.clone(),
+        };
+        let handle_result = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            handle_msg,
+        );
+        assert!(
+            handle_result.is_ok(),
+            "handle() failed: {}",
+            handle_result.err().unwrap()
+        );
+        let config = CONFIG.load(&deps.storage).unwrap();
+        assert_eq!(config.liquidity_buffer, share);
+
+        let handle_msg = ExecuteMsg::Receive {
+            sender: Addr::unchecked(""),
+            from: Addr::unchecked("bob"),
+            amount: Uint256::from(300000000 as u32),
+            msg: Some(to_binary(&ReceiverMsg::Stake {}).unwrap()),
+        };
+        let info = mock_info("shade_contract_info_address", &[]);
+        let handle_result = execute(deps.as_mut(), mock_env(), info.clone(), handle_msg.clone());
+        assert!(
+            handle_result.is_ok(),
+            "handle() failed: {}",
+            handle_result.err().unwrap()
+        );
+        // 10% of the 300000000 staked plus the 285000000 deposited is kept unstaked
+        assert_eq!(LIQUIDITY_BUFFER.load(&deps.storage).unwrap(), 58500000);
+        let response = handle_result.unwrap();
+        assert!(response.messages.contains(&SubMsg::new(
+            generate_stake_msg(
+                Uint128::from(226500000_u128),
+                Some(true),
+                &config,
+                &config.staking[0].contract,
+            )
+            .unwrap()
+        )));
+
+        // Only the missing part is kept once the buffer is filled
+        let handle_result = execute(deps.as_mut(), mock_env(), info, handle_msg);
+        assert!(
+            handle_result.is_ok(),
+            "handle() failed: {}",
+            handle_result.err().unwrap()
+        );
+        assert_eq!(LIQUIDITY_BUFFER.load(&deps.storage).unwrap(), 64350000);
+
+        let query_result = query(deps.as_ref(), mock_env(), QueryMsg::StakingInfo {});
+        match from_binary(&query_result.unwrap()).unwrap() {
+            QueryAnswer::StakingInfo {
+                liquidity_buffer, ..
+            } => assert_eq!(liquidity_buffer, Uint128::from(64350000_u128)),
+            other => panic!("Unexpected: {:?}", other),
+        };
+    }
+
+    #[test]
+    fn test_instant_unbond() {
+        let (init_result, mut deps) = init_helper();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        LIQUIDITY_BUFFER.save(&mut deps.storage, &1000000).unwrap();
+
+        let handle_msg = ExecuteMsg::Receive {
+            sender: Addr::unchecked(""),
+            from: Addr::unchecked("bob"),
+            amount: Uint256::from(1 as u32),
+            msg: Some(to_binary(&ReceiverMsg::InstantUnbond {}).unwrap()),
+        };
+        let info = mock_info("derivative_snip20_info_address", &[]);
+        let handle_result = execute(deps.as_mut(), mock_env(), info, handle_msg);
+        assert!(
+            handle_result.is_ok(),
+            "handle() failed: {}",
+            handle_result.err().unwrap()
+        );
+        let response = handle_result.unwrap();
+        let (tokens_redeemed, shd_received) = match from_binary(&response.data.unwrap()).unwrap() {
+            ExecuteAnswer::InstantUnbond {
+                tokens_redeemed,
+                shd_received,
+            } => (tokens_redeemed, shd_received),
+            other => panic!("Unexpected: {:?}", other),
+        };
+        // 225000 SHD redeemed minus the unbonding fee twice and the instant unbonding fee
+        assert_eq!(tokens_redeemed, Uint128::from(1_u128));
+        assert_eq!(shd_received, Uint128::from(201033_u128));
+        assert_eq!(
+            LIQUIDITY_BUFFER.load(&deps.storage).unwrap(),
+            1000000 - 213750
+        );
+
+        let config = CONFIG.load(&deps.storage).unwrap();
+        assert!(response.messages.contains(&SubMsg::new(
+            send_msg(
+                "collector_address".to_string(),
+                Uint128::from(10687_u128 + 2030_u128),
+                None,
+                Some(base64::encode(&"Payment of fee for instant unbonding SHD")),
+                config.token.entropy.clone(),
+                RESPONSE_BLOCK_SIZE,
+                config.token.code_hash.clone(),
+                config.token.address.to_string(),
+            )
+            .unwrap()
+        )));
+        assert!(PENDING_UNBONDING.may_load(&deps.storage).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_instant_unbond_empty_buffer() {
+        let (init_result, mut deps) = init_helper();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+
+        let handle_msg = ExecuteMsg::Receive {
+            sender: Addr::unchecked(""),
+            from: Addr::unchecked("bob"),
+            amount: Uint256::from(1 as u32),
+            msg: Some(to_binary(&ReceiverMsg::InstantUnbond {}).unwrap()),
+        };
+        let info = mock_info("derivative_snip20_info_address", &[]);
+        let handle_result = execute(deps.as_mut(), mock_env(), info, handle_msg);
+        assert!(
+            handle_result.is_ok(),
+            "handle() failed: {}",
+            handle_result.err().unwrap()
+        );
+
+        // Goes through the unbonding queue
+        match from_binary(&handle_result.unwrap().data.unwrap()).unwrap() {
+            ExecuteAnswer::Unbond {
+                shd_to_be_received, ..
+            } => assert_eq!(shd_to_be_received, Uint128::from(213750_u128)),
+            other => panic!("Unexpected: {:?}", other),
+        };
+        assert_eq!(
+            UnbondingIdsStore::load(&deps.storage, &Addr::unchecked("bob")),
+            vec![1_u128]
+        );
+    }
 }
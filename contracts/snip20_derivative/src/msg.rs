@@ -11,8 +11,8 @@ use crate::staking_interface::Unbonding;
 #[cfg_attr(test, derive(Eq, PartialEq))]
 pub struct Config {
     pub prng_seed:Binary,
-    // Staking contract (SHADE-CUSTOM) information
-    pub staking: ContractInfo,
+    // Staking contracts (SHADE-CUSTOM) information, all authenticated through query_auth
+    pub staking: Vec<StakingBackend>,
     pub staking_contract_vk: String,
     // Staking authentication contract (SHADE-CUSTOM) information
     pub query_auth: ContractInfo,
@@ -23,6 +23,8 @@ pub struct Config {
     pub derivative: ContractInfo,
     // Fee collector and rate information
     pub fees: FeeInfo,
+    // Share of the pool kept unstaked for instant unbondings, same format as fee rates
+    pub liquidity_buffer: Fee,
     pub contract_address: Addr,
     pub admin: Contract,
 }
@@ -39,6 +41,8 @@ pub struct Fee {
 pub struct FeeInfo {
     pub staking: Fee,
     pub unbonding: Fee,
+    // Charged on top of the unbonding fee when unbonding from the liquidity buffer
+    pub instant_unbonding: Fee,
     pub collector: Addr,
 }
 
@@ -52,15 +56,25 @@ pub struct ContractInfo {
     pub entropy: Option<String>,
 }
 
+#[cfg_attr(test, derive(Eq, PartialEq))]
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct StakingBackend {
+    pub contract: ContractInfo,
+    // Relative share of the staked SHD, out of the sum of all weights
+    pub weight: u32,
+}
+
 #[derive(Serialize, Deserialize, JsonSchema)]
 pub struct InstantiateMsg {
     pub prng_seed: Binary,
-    pub staking: ContractInfo,
+    pub staking: Vec<StakingBackend>,
     pub query_auth: ContractInfo,
     pub derivative: ContractInfo,
     pub token: ContractInfo,
     pub admin: Contract,
     pub fees: FeeInfo,
+    // Defaults to no liquidity buffer
+    pub liquidity_buffer: Option<Fee>,
 }
 
 #[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
@@ -71,12 +85,20 @@ pub enum ExecuteMsg {
     UpdateFees {
         staking: Option<Fee>,
         unbonding: Option<Fee>,
+        instant_unbonding: Option<Fee>,
         collector: Option<Addr>,
     },
+    UpdateLiquidityBuffer {
+        share: Fee,
+    },
     PanicUnbond {
         amount: Uint128,
     },
     PanicWithdraw {},
+    // Existing staking contracts can't be removed, only weighted to 0
+    UpdateStaking {
+        staking: Vec<StakingBackend>,
+    },
     //Receiver interface
     Receive {
         sender: Addr,
@@ -119,6 +141,13 @@ pub enum ExecuteAnswer {
         /// estimated time of maturity
         estimated_time_of_maturity: Uint128,
     },
+    /// redeem derivative tokens for SHD from the liquidity buffer
+    InstantUnbond {
+        /// amount of derivative tokens redeemed
+        tokens_redeemed: Uint128,
+        /// amount of shd sent
+        shd_received: Uint128,
+    },
     CreateViewingKey {
         key: String,
     },
@@ -135,6 +164,13 @@ pub enum ExecuteAnswer {
         status: ResponseStatus,
         fee: FeeInfo,
     },
+    UpdateStaking {
+        status: ResponseStatus,
+    },
+    UpdateLiquidityBuffer {
+        status: ResponseStatus,
+        share: Fee,
+    },
     // Permit
     RevokePermit {
         status: ResponseStatus,
@@ -146,6 +182,8 @@ pub enum ExecuteAnswer {
 pub enum ReceiverMsg {
     Stake {},
     Unbond {},
+    // Falls back to Unbond if the liquidity buffer can't cover the redemption
+    InstantUnbond {},
     TransferStaked { receiver: Option<Addr> },
 }
 
@@ -208,6 +246,8 @@ pub enum QueryAnswer {
         bonded_shd: Uint128,
         /// amount of available SHD not reserved for mature unbondings
         available_shd: Uint128,
+        /// amount of available SHD kept for instant unbondings
+        liquidity_buffer: Uint128,
         /// unclaimed staking rewards
         rewards: Uint128,
         /// total supply of derivative token
@@ -218,6 +258,7 @@ pub enum QueryAnswer {
     FeeInfo {
         staking: Fee,
         unbonding: Fee,
+        instant_unbonding: Fee,
         collector: Addr,
     },
     ContractStatus {
@@ -230,15 +271,23 @@ pub enum QueryAnswer {
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct InProcessUnbonding {
+    // Derivative unbonding id
+    pub id: Uint128,
+    // Staking contract the unbond msg was sent to
+    pub staking: Addr,
+}
+
+// Staking contract unbonding backing part of a derivative unbonding
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct UnbondingSource {
+    pub staking: Addr,
     pub id: Uint128,
-    pub owner: Addr,
-    pub amount: Uint128,
-    pub complete: Uint128,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct PanicUnbond {
     pub id: Uint128,
+    pub staking: Addr,
     pub amount: Uint128,
     pub complete: Uint128,
 }
@@ -2,7 +2,7 @@ use cosmwasm_std::{Addr, StdResult, Storage};
 use secret_toolkit::storage::Item;
 use secret_toolkit::{serialization::Json, storage::Keymap};
 
-use crate::msg::{Config, ContractStatusLevel, InProcessUnbonding, PanicUnbond};
+use crate::msg::{Config, ContractStatusLevel, InProcessUnbonding, PanicUnbond, UnbondingSource};
 use crate::staking_interface::{Unbonding, Token};
 
 pub const KEY_CONFIG: &[u8] = b"config";
@@ -10,8 +10,11 @@ pub const KEY_STAKING_INFO: &[u8] = b"staking_info";
 pub const KEY_PANIC_UNBONDS: &[u8] = b"panic_unbonds_ids";
 pub const KEY_PENDING_UNBONDING: &[u8] = b"last_unbonding_id";
 pub const KEY_CONTRACT_STATUS: &[u8] = b"contract_status";
+pub const KEY_LIQUIDITY_BUFFER: &[u8] = b"liquidity_buffer";
 pub const PREFIX_UNBONDINGS_IDS: &[u8] = b"unbondings_ids";
 pub const PREFIX_UNBONDINGS: &[u8] = b"unbondings";
+pub const PREFIX_UNBONDING_SOURCES: &[u8] = b"unbonding_sources";
+pub const KEY_UNBONDING_COUNT: &[u8] = b"unbonding_count";
 pub const RESPONSE_BLOCK_SIZE: usize = 256;
 pub const UNBOND_REPLY_ID: u64 = 1_u64;
 pub const PANIC_WITHDRAW_REPLY_ID: u64 = 2_u64;
@@ -25,7 +28,13 @@ pub static CONTRACTS_VKS: Keymap<Addr, Token> = Keymap::new(PREFIX_CONTRACTS_VKS
 pub static CONTRACT_STATUS: Item<ContractStatusLevel, Json> = Item::new(KEY_CONTRACT_STATUS);
 pub static CONFIG: Item<Config> = Item::new(KEY_CONFIG);
 pub static PANIC_UNBONDS: Item<Vec<PanicUnbond>> = Item::new(KEY_PANIC_UNBONDS);
-pub static PENDING_UNBONDING: Item<InProcessUnbonding> = Item::new(KEY_PENDING_UNBONDING);
+// SHD kept unstaked for instant unbondings
+pub static LIQUIDITY_BUFFER: Item<u128> = Item::new(KEY_LIQUIDITY_BUFFER);
+// Unbond msgs waiting for their reply, in the order they were sent
+pub static PENDING_UNBONDING: Item<Vec<InProcessUnbonding>> = Item::new(KEY_PENDING_UNBONDING);
+pub static UNBONDING_COUNT: Item<u128> = Item::new(KEY_UNBONDING_COUNT);
+pub static UNBONDING_SOURCES: Keymap<u128, Vec<UnbondingSource>> =
+    Keymap::new(PREFIX_UNBONDING_SOURCES);
 pub static UNBONDINGS_IDS: Item<Vec<u128>> = Item::new(PREFIX_UNBONDINGS_IDS);
 pub static UNBONDING: Keymap<u128, Unbonding> = Keymap::new(PREFIX_UNBONDINGS);
 
@@ -67,3 +76,22 @@ impl UnbondingStore {
         UNBONDING.remove(store, &id)
     }
 }
+
+pub struct UnbondingSourcesStore {}
+impl UnbondingSourcesStore {
+    pub fn load(store: &dyn Storage, id: u128) -> Vec<UnbondingSource> {
+        UNBONDING_SOURCES.get(store, &id).unwrap_or_default()
+    }
+
+    pub fn save(
+        store: &mut dyn Storage,
+        id: u128,
+        sources: &Vec<UnbondingSource>,
+    ) -> StdResult<()> {
+        UNBONDING_SOURCES.insert(store, &id, sources)
+    }
+
+    pub fn remove(store: &mut dyn Storage, id: u128) -> StdResult<()> {
+        UNBONDING_SOURCES.remove(store, &id)
+    }
+}
@@ -177,6 +177,8 @@ pub fn migration_test() {
         amount: Uint128::new(1000000),
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token0, &mut chain, admin.clone().into(), &[])
@@ -217,6 +219,8 @@ pub fn migration_test() {
         amount: Uint128::new(1_000_000_000_000_000),
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token0, &mut chain, admin.clone().into(), &[])
@@ -65,6 +65,8 @@ pub fn init(
             owner: treasury_manager.address.into(),
             sscrt: sscrt.into(),
             validator_bounds,
+            weighted_validators: None,
+            unbonding_period: 21 * 24 * 60 * 60,
             viewing_key: "viewing_key".into(),
         }
         .test_init(
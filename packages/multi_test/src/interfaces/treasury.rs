@@ -29,6 +29,7 @@ pub fn init(chain: &mut App, sender: &str, contracts: &mut DeployedContracts) ->
     let treasury = Contract::from(
         match (treasury::InstantiateMsg {
             multisig: admin.address.clone().to_string(),
+            timelock: Uint128::zero(),
             admin_auth: admin.clone().into(),
             viewing_key: "viewing_key".to_string(),
         }
@@ -160,6 +161,27 @@ pub fn reserves_query(
     }
 }
 
+pub fn portfolio_query(
+    chain: &App,
+    contracts: &DeployedContracts,
+) -> StdResult<(Vec<treasury::AssetValuation>, Uint128)> {
+    let res = treasury::QueryMsg::Portfolio {}.test_query(
+        &contracts
+            .get(&SupportedContracts::Treasury)
+            .unwrap()
+            .clone()
+            .into(),
+        chain,
+    )?;
+    match res {
+        treasury::QueryAnswer::Portfolio {
+            assets,
+            total_value,
+        } => Ok((assets, total_value)),
+        _ => Err(StdError::generic_err("query failed")),
+    }
+}
+
 pub fn balance_query(
     chain: &App,
     contracts: &DeployedContracts,
@@ -231,6 +253,70 @@ pub fn metrics_query(
     }
 }
 
+pub fn metrics_page_query(
+    chain: &App,
+    contracts: &DeployedContracts,
+    date: Option<String>,
+    period: Period,
+    filter: Option<treasury::MetricFilter>,
+    start_after: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<(Vec<treasury::Metric>, Option<u32>)> {
+    let res = treasury::QueryMsg::MetricsPage {
+        date,
+        epoch: None,
+        period,
+        filter,
+        start_after,
+        limit,
+    }
+    .test_query(
+        &contracts
+            .get(&SupportedContracts::Treasury)
+            .unwrap()
+            .clone()
+            .into(),
+        chain,
+    )?;
+    match res {
+        treasury::QueryAnswer::MetricsPage { metrics, next } => Ok((metrics, next)),
+        _ => Err(StdError::generic_err("query failed")),
+    }
+}
+
+pub fn metrics_summary_query(
+    chain: &App,
+    contracts: &DeployedContracts,
+    date: Option<String>,
+    period: Period,
+    snip20_symbol: Option<&str>,
+) -> StdResult<Vec<treasury::NetFlow>> {
+    let res = treasury::QueryMsg::MetricsSummary {
+        date,
+        epoch: None,
+        period,
+        token: snip20_symbol.map(|symbol| {
+            contracts
+                .get(&SupportedContracts::Snip20(symbol.to_string()))
+                .unwrap()
+                .address
+                .to_string()
+        }),
+    }
+    .test_query(
+        &contracts
+            .get(&SupportedContracts::Treasury)
+            .unwrap()
+            .clone()
+            .into(),
+        chain,
+    )?;
+    match res {
+        treasury::QueryAnswer::MetricsSummary { flows } => Ok(flows),
+        _ => Err(StdError::generic_err("query failed")),
+    }
+}
+
 pub fn batch_balance_query(
     chain: &App,
     contracts: &DeployedContracts,
@@ -422,6 +508,9 @@ pub fn set_config(
     match (treasury::ExecuteMsg::UpdateConfig {
         admin_auth,
         multisig,
+        timelock: None,
+        oracle: None,
+        clear_oracle: None,
     }
     .test_exec(
         &contracts
@@ -545,6 +545,43 @@ pub fn update_exec(
     }
 }
 
+pub fn emergency_unwind_exec(
+    chain: &mut App,
+    sender: &str,
+    contracts: &DeployedContracts,
+    snip20_symbol: &str,
+    treasury_manager_contract: SupportedContracts,
+    adapters: Option<Vec<SupportedContracts>>,
+) -> StdResult<()> {
+    match (treasury_manager::ExecuteMsg::EmergencyUnwind {
+        asset: contracts
+            .get(&SupportedContracts::Snip20(snip20_symbol.to_string()))
+            .unwrap()
+            .clone()
+            .address
+            .to_string(),
+        adapters: adapters.map(|adapters| {
+            adapters
+                .iter()
+                .map(|a| contracts.get(a).unwrap().address.to_string())
+                .collect()
+        }),
+    }
+    .test_exec(
+        &contracts
+            .get(&treasury_manager_contract)
+            .unwrap()
+            .clone()
+            .into(),
+        chain,
+        Addr::unchecked(sender),
+        &[],
+    )) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(StdError::generic_err(e.to_string())),
+    }
+}
+
 pub fn register_holder_exec(
     chain: &mut App,
     sender: &str,
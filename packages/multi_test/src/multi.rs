@@ -32,6 +32,12 @@ pub mod stkd_scrt {
     multi_derive::implement_multi!(StkdScrt, stkd_scrt);
 }
 
+#[cfg(feature = "basket")]
+pub mod basket {
+    use basket;
+    multi_derive::implement_multi!(Basket, basket);
+}
+
 // #[cfg(feature = "mint")]
 // pub mod mint {
 //     use mint;
@@ -132,6 +138,12 @@ pub mod mock_sienna {
     multi_derive::implement_multi!(MockSienna, mock_sienna);
 }
 
+#[cfg(feature = "mock_oracle")]
+pub mod mock_oracle {
+    pub use mock_oracle;
+    multi_derive::implement_multi!(MockOracle, mock_oracle);
+}
+
 #[cfg(feature = "snip20_migration")]
 pub mod snip20_migration {
     use snip20_migration;
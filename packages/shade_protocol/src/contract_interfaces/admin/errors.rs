@@ -15,6 +15,8 @@ pub enum Error {
     IsShutdown,
     IsUnderMaintenance,
     InvalidPermissionFormat,
+    NoPendingSuper,
+    PermissionUnderMaintenance,
 }
 
 impl_into_u8!(Error);
@@ -36,6 +38,10 @@ impl CodeType for Error {
                 Error::InvalidPermissionFormat => {
                     "{} must be > 10 characters and only contains 0-9, A-Z, and underscores"
                 }
+                Error::NoPendingSuper => "{} has not been proposed as the super admin",
+                Error::PermissionUnderMaintenance => {
+                    "{} is under maintenance. Its validation is disabled."
+                }
             },
             context,
         )
@@ -72,3 +78,12 @@ pub fn invalid_permission_format(permission: &str) -> StdError {
     ])
     .to_error()
 }
+pub fn no_pending_super(address: &str) -> StdError {
+    DetailedError::from_code(ADMIN_TARGET, Error::NoPendingSuper, vec![address]).to_error()
+}
+pub fn permission_under_maintenance(permission: &str) -> StdError {
+    DetailedError::from_code(ADMIN_TARGET, Error::PermissionUnderMaintenance, vec![
+        permission,
+    ])
+    .to_error()
+}
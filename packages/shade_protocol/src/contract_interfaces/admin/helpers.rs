@@ -53,6 +53,7 @@ pub enum AdminPermissions {
     StakingAdmin,
     DerivativeAdmin,
     Snip20MigrationAdmin,
+    BasketAdmin,
 }
 
 // NOTE: SHADE_{CONTRACT_NAME}_{CONTRACT_ROLE}_{POTENTIAL IDs}
@@ -74,6 +75,7 @@ impl AdminPermissions {
             AdminPermissions::StakingAdmin => "SHADE_STAKING_ADMIN",
             AdminPermissions::DerivativeAdmin => "SHADE_DERIVATIVE_ADMIN",
             AdminPermissions::Snip20MigrationAdmin => "SNIP20_MIGRATION_ADMIN",
+            AdminPermissions::BasketAdmin => "SHADE_BASKET_ADMIN",
         }
         .to_string()
     }
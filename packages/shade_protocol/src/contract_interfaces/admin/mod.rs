@@ -46,9 +46,18 @@ impl InstantiateCallback for InstantiateMsg {
 pub enum ExecuteMsg {
     UpdateRegistry { action: RegistryAction },
     UpdateRegistryBulk { actions: Vec<RegistryAction> },
+    /// Proposes a new super admin, who has to accept before the transfer happens
     TransferSuper { new_super: String },
+    /// Called by the proposed super admin to complete the transfer
+    AcceptSuper {},
+    CancelTransferSuper {},
     SelfDestruct {},
     ToggleStatus { new_status: AdminAuthStatus },
+    /// Pauses or resumes validation of individual permissions
+    TogglePermissionStatus {
+        permissions: Vec<String>,
+        paused: bool,
+    },
 }
 
 #[cw_serde]
@@ -59,6 +68,8 @@ pub enum RegistryAction {
     GrantAccess {
         permissions: Vec<String>,
         user: String,
+        /// Time in seconds when the permissions lapse, they never do when None
+        expiration: Option<u64>,
     },
     RevokeAccess {
         permissions: Vec<String>,
@@ -69,6 +80,30 @@ pub enum RegistryAction {
     },
 }
 
+/// Changes recorded in the audit log, bulk registry updates get one entry per action.
+#[cw_serde]
+pub enum AuditAction {
+    Registry { action: RegistryAction },
+    TransferSuper { new_super: String },
+    AcceptSuper {},
+    CancelTransferSuper {},
+    SelfDestruct {},
+    ToggleStatus { new_status: AdminAuthStatus },
+    TogglePermissionStatus {
+        permissions: Vec<String>,
+        paused: bool,
+    },
+}
+
+#[cw_serde]
+pub struct AuditLogEntry {
+    pub id: u64,
+    /// Block time in seconds
+    pub time: u64,
+    pub sender: Addr,
+    pub action: AuditAction,
+}
+
 impl ExecuteCallback for ExecuteMsg {
     const BLOCK_SIZE: usize = 256;
 }
@@ -78,10 +113,23 @@ impl ExecuteCallback for ExecuteMsg {
 pub enum QueryMsg {
     #[returns(ConfigResponse)]
     GetConfig {},
+    /// Returns every admin when page_size is None
     #[returns(AdminsResponse)]
-    GetAdmins {},
+    GetAdmins {
+        page: Option<u32>,
+        page_size: Option<u32>,
+    },
     #[returns(PermissionsResponse)]
     GetPermissions { user: String },
+    /// Admins holding an unexpired permission, the super admin implicitly holds all of them
+    #[returns(PermissionHoldersResponse)]
+    GetPermissionHolders { permission: String },
+    /// Entries with ids in [start, end), at most 100 per query
+    #[returns(AuditLogResponse)]
+    GetAuditLog { start: u64, end: u64 },
+    /// Permissions that are individually under maintenance
+    #[returns(PausedPermissionsResponse)]
+    GetPausedPermissions {},
     #[returns(ValidateAdminPermissionResponse)]
     ValidateAdminPermission { permission: String, user: String },
 }
@@ -93,6 +141,7 @@ impl Query for QueryMsg {
 #[cw_serde]
 pub struct ConfigResponse {
     pub super_admin: Addr,
+    pub pending_super_admin: Option<Addr>,
     pub status: AdminAuthStatus,
 }
 
@@ -104,6 +153,23 @@ pub struct PermissionsResponse {
 #[cw_serde]
 pub struct AdminsResponse {
     pub admins: Vec<Addr>,
+    pub total: u32,
+}
+
+#[cw_serde]
+pub struct PermissionHoldersResponse {
+    pub holders: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct PausedPermissionsResponse {
+    pub permissions: Vec<String>,
+}
+
+#[cw_serde]
+pub struct AuditLogResponse {
+    pub entries: Vec<AuditLogEntry>,
+    pub total: u64,
 }
 
 #[cw_serde]
@@ -21,6 +21,34 @@ pub struct Config {
     pub unbond_period: Uint128,
     // Number of non-admin pools allowed
     pub max_user_pools: Uint128,
+    // Lock durations users can choose from when staking
+    pub lock_tiers: Vec<LockTier>,
+    // Unbonding that skips 'unbond_period' for a penalty, disabled if None
+    pub instant_unbond: Option<InstantUnbond>,
+}
+
+#[cw_serde]
+pub struct InstantUnbond {
+    // Portion of the unbonded amount kept as a penalty
+    pub penalty: Decimal,
+    pub destination: PenaltyDestination,
+}
+
+#[cw_serde]
+pub enum PenaltyDestination {
+    // Emitted to remaining stakers through a single penalty pool, restarted over 'duration'
+    // seconds with each penalty
+    Stakers { duration: Uint128 },
+    // Validated on UpdateConfig
+    Treasury { address: String },
+}
+
+#[cw_serde]
+pub struct LockTier {
+    // Seconds the stake is locked for
+    pub duration: Uint128,
+    // Reward weight applied to the locked amount, must be >= 1
+    pub multiplier: Decimal,
 }
 
 #[cw_serde]
@@ -38,6 +66,8 @@ pub enum Action {
     Stake {
         compound: Option<bool>,
         airdrop_task: Option<bool>,
+        // Lock duration, must match a configured lock tier
+        lock: Option<Uint128>,
     },
     Rewards {
         start: Uint128,
@@ -52,6 +82,32 @@ pub struct Unbonding {
     pub complete: Uint128,
 }
 
+// Stake locked until 'end', earning rewards at 'multiplier' weight
+// The boost is removed the next time the user's stake is updated after 'end'
+#[cw_serde]
+pub struct Lock {
+    pub id: Uint128,
+    pub amount: Uint128,
+    pub multiplier: Decimal,
+    pub start: Uint128,
+    pub end: Uint128,
+}
+
+// Actions an operator may trigger for a staker, rewards always go to the staker
+#[cw_serde]
+pub enum OperatorPermission {
+    Claim,
+    Compound,
+}
+
+#[cw_serde]
+pub struct Operator {
+    pub address: Addr,
+    pub permissions: Vec<OperatorPermission>,
+    // Timestamp after which the operator can no longer act
+    pub expiration: Option<Uint128>,
+}
+
 #[cw_serde]
 pub struct Reward {
     pub token: Contract,
@@ -74,6 +130,13 @@ pub struct RewardPoolInternal {
     pub official: bool,
 }
 
+// Internal storage, staked amount from 'time' until the next checkpoint
+#[cw_serde]
+pub struct Checkpoint {
+    pub time: Uint128,
+    pub amount: Uint128,
+}
+
 // Query returned data
 #[cw_serde]
 pub struct RewardPool {
@@ -86,6 +149,31 @@ pub struct RewardPool {
     pub official: bool,
 }
 
+// Query returned data, includes ended pools
+#[cw_serde]
+pub struct RewardPoolRecord {
+    pub id: Uint128,
+    pub amount: Uint128,
+    pub start: Uint128,
+    pub end: Uint128,
+    pub token: Contract,
+    pub rate: Uint128,
+    pub official: bool,
+    pub creator: Addr,
+    // Rewards claimed by stakers
+    pub claimed: Uint128,
+    // Rewards pulled out by 'EndRewardPool'
+    pub extracted: Uint128,
+    pub ended: bool,
+}
+
+#[cw_serde]
+pub struct PoolClaimed {
+    pub pool_id: Uint128,
+    pub token: Contract,
+    pub amount: Uint128,
+}
+
 #[cw_serde]
 pub struct InstantiateMsg {
     pub admin_auth: RawContract,
@@ -109,6 +197,9 @@ pub enum ExecuteMsg {
         airdrop: Option<RawContract>,
         unbond_period: Option<Uint128>,
         max_user_pools: Option<Uint128>,
+        lock_tiers: Option<Vec<LockTier>>,
+        instant_unbond: Option<InstantUnbond>,
+        disable_instant_unbond: Option<bool>,
         padding: Option<String>,
     },
     RegisterRewards {
@@ -125,6 +216,8 @@ pub enum ExecuteMsg {
     Unbond {
         amount: Uint128,
         compound: Option<bool>,
+        // Skip the unbond period for the configured penalty
+        instant: Option<bool>,
         padding: Option<String>,
     },
     Withdraw {
@@ -156,6 +249,32 @@ pub enum ExecuteMsg {
         compound: Option<bool>,
         padding: Option<String>,
     },
+    // Replaces any existing authorization for 'operator'
+    SetOperator {
+        operator: String,
+        permissions: Vec<OperatorPermission>,
+        expiration: Option<Uint128>,
+        padding: Option<String>,
+    },
+    RevokeOperator {
+        operator: String,
+        padding: Option<String>,
+    },
+    // Operator claims on behalf of 'user', rewards are sent to 'user'
+    ClaimFor {
+        user: String,
+        padding: Option<String>,
+    },
+    // Operator compounds on behalf of 'user'
+    CompoundFor {
+        user: String,
+        padding: Option<String>,
+    },
+    // Anyone can remove the boost of 'user's expired locks, rewards are sent to 'user'
+    ExpireLocks {
+        user: String,
+        padding: Option<String>,
+    },
 }
 
 impl ExecuteCallback for ExecuteMsg {
@@ -174,6 +293,7 @@ pub enum ExecuteAnswer {
     // Receive Response
     Stake {
         staked: Uint128,
+        locked: Option<Lock>,
         status: ResponseStatus,
     },
     // Receive Response
@@ -186,8 +306,10 @@ pub enum ExecuteAnswer {
         status: ResponseStatus,
     },
     Unbond {
+        // Zero when unbonded instantly
         id: Uint128,
         unbonded: Uint128,
+        penalty: Uint128,
         status: ResponseStatus,
     },
     Withdraw {
@@ -216,6 +338,16 @@ pub enum ExecuteAnswer {
         transferred: Uint128,
         status: ResponseStatus,
     },
+    SetOperator {
+        status: ResponseStatus,
+    },
+    RevokeOperator {
+        status: ResponseStatus,
+    },
+    ExpireLocks {
+        expired: Vec<Lock>,
+        status: ResponseStatus,
+    },
 }
 
 #[cw_serde]
@@ -234,9 +366,21 @@ pub enum QueryMsg {
     StakeToken {},
     StakingInfo {},
     TotalStaked {},
-    RewardTokens {},
+    // Historical total staked, for voting power snapshots
+    TotalStakedAt {
+        time: Uint128,
+    },
+    RewardTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     // All reward pools in progress
     RewardPools {},
+    // All reward pools by id, including ended ones
+    AllRewardPools {
+        start_after: Option<Uint128>,
+        limit: Option<u32>,
+    },
 
     Balance {
         auth: Auth,
@@ -245,6 +389,11 @@ pub enum QueryMsg {
     Staked {
         auth: Auth,
     },
+    // Historical user staked, for voting power snapshots
+    StakedAt {
+        auth: Auth,
+        time: Uint128,
+    },
     Rewards {
         auth: Auth,
     },
@@ -252,7 +401,24 @@ pub enum QueryMsg {
         auth: Auth,
         ids: Option<Vec<Uint128>>,
     },
+    // Lifetime rewards claimed per pool
+    Claimed {
+        auth: Auth,
+        start_after: Option<Uint128>,
+        limit: Option<u32>,
+    },
     TransferWhitelist {},
+    Operators {
+        auth: Auth,
+    },
+    Locks {
+        auth: Auth,
+    },
+    // Stake plus lock boosts, used for reward distribution
+    Weight {
+        auth: Auth,
+    },
+    TotalWeight {},
 }
 
 impl Query for QueryMsg {
@@ -273,12 +439,18 @@ pub enum QueryAnswer {
     TotalStaked {
         amount: Uint128,
     },
+    TotalStakedAt {
+        amount: Uint128,
+    },
     RewardTokens {
         tokens: Vec<Addr>,
     },
     RewardPools {
         rewards: Vec<RewardPool>,
     },
+    AllRewardPools {
+        pools: Vec<RewardPoolRecord>,
+    },
     Balance {
         staked: Uint128,
         rewards: Vec<Reward>,
@@ -287,13 +459,33 @@ pub enum QueryAnswer {
     Staked {
         amount: Uint128,
     },
+    StakedAt {
+        amount: Uint128,
+    },
     Rewards {
         rewards: Vec<Reward>,
     },
     Unbonding {
         unbondings: Vec<Unbonding>,
     },
+    Claimed {
+        claimed: Vec<PoolClaimed>,
+    },
     TransferWhitelist {
         whitelist: Vec<Addr>,
     },
+    Operators {
+        operators: Vec<Operator>,
+    },
+    Locks {
+        locks: Vec<Lock>,
+    },
+    Weight {
+        staked: Uint128,
+        locked: Uint128,
+        weight: Uint128,
+    },
+    TotalWeight {
+        amount: Uint128,
+    },
 }
@@ -0,0 +1,146 @@
+use crate::{
+    c_std::{Addr, Api, Binary, StdResult, Uint128},
+    contract_interfaces::{dao::adapter, dex::dex::Dex},
+    cosmwasm_schema::cw_serde,
+    utils::{
+        asset::{Contract, RawContract},
+        generic_response::ResponseStatus,
+        ExecuteCallback,
+        InstantiateCallback,
+        Query,
+    },
+};
+
+#[cw_serde]
+pub struct BasketToken {
+    pub token: Contract,
+    // Pair trading this token against the deposit asset
+    pub pair: Contract,
+    pub dex: Dex,
+    // Oracle symbol the token is priced by
+    pub symbol: String,
+    // Relative share of the basket, out of the sum of all weights
+    pub weight: Uint128,
+}
+
+#[cw_serde]
+pub struct RawBasketToken {
+    pub token: RawContract,
+    pub pair: RawContract,
+    pub dex: Dex,
+    pub symbol: String,
+    pub weight: Uint128,
+}
+
+impl RawBasketToken {
+    pub fn into_valid(self, api: &dyn Api) -> StdResult<BasketToken> {
+        Ok(BasketToken {
+            token: self.token.into_valid(api)?,
+            pair: self.pair.into_valid(api)?,
+            dex: self.dex,
+            symbol: self.symbol,
+            weight: self.weight,
+        })
+    }
+}
+
+#[cw_serde]
+pub struct Config {
+    pub admin_auth: Contract,
+    // This is the contract that will "unbond" funds
+    pub owner: Addr,
+    // Deposited, unbonded and traded against for every basket token
+    pub asset: Contract,
+    // Oracle symbol the deposit asset is priced by
+    pub asset_symbol: String,
+    // Prices holdings and swaps, so a pool can't be moved to value the basket
+    pub oracle: Contract,
+    pub basket: Vec<BasketToken>,
+    // Portion (10^18 = 100%) of the basket value a token can drift before being rebalanced
+    pub tolerance: Uint128,
+    // Portion (10^18 = 100%) below the oracle price a swap can return
+    pub max_slippage: Uint128,
+}
+
+#[cw_serde]
+pub struct Holding {
+    pub token: Addr,
+    pub amount: Uint128,
+    // Oracle value in the deposit asset
+    pub value: Uint128,
+    pub target_value: Uint128,
+}
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub admin_auth: RawContract,
+    pub owner: String,
+    pub asset: RawContract,
+    pub asset_symbol: String,
+    pub oracle: RawContract,
+    pub basket: Vec<RawBasketToken>,
+    pub tolerance: Uint128,
+    pub max_slippage: Uint128,
+    pub viewing_key: String,
+}
+
+impl InstantiateCallback for InstantiateMsg {
+    const BLOCK_SIZE: usize = 256;
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    Receive {
+        sender: String,
+        from: String,
+        amount: Uint128,
+        memo: Option<Binary>,
+        msg: Option<Binary>,
+    },
+    UpdateConfig {
+        config: Config,
+    },
+    Adapter(adapter::SubExecuteMsg),
+}
+
+impl ExecuteCallback for ExecuteMsg {
+    const BLOCK_SIZE: usize = 256;
+}
+
+#[cw_serde]
+pub enum ExecuteAnswer {
+    Init {
+        status: ResponseStatus,
+        address: String,
+    },
+    UpdateConfig {
+        status: ResponseStatus,
+    },
+    Receive {
+        status: ResponseStatus,
+    },
+}
+
+#[cw_serde]
+pub enum QueryMsg {
+    Config {},
+    Holdings {},
+    Adapter(adapter::SubQueryMsg),
+}
+
+impl Query for QueryMsg {
+    const BLOCK_SIZE: usize = 256;
+}
+
+#[cw_serde]
+pub enum QueryAnswer {
+    Config {
+        config: Config,
+    },
+    Holdings {
+        // Deposit asset held, including what is reserved for unbondings
+        cash: Uint128,
+        unbonding: Uint128,
+        holdings: Vec<Holding>,
+    },
+}
@@ -0,0 +1,183 @@
+use crate::{
+    c_std::{Addr, Env, StdError, StdResult, Storage, Uint128},
+    cosmwasm_schema::cw_serde,
+    serde::{de::DeserializeOwned, Serialize},
+    utils::{
+        cycle::parse_utc_datetime,
+        storage::plus::period_storage::{Period, PeriodStorage},
+    },
+};
+
+pub const DEFAULT_METRICS_LIMIT: u32 = 50;
+pub const MAX_METRICS_LIMIT: u32 = 100;
+
+/// Fields shared by the treasury & treasury_manager metrics
+pub trait MetricEntry {
+    type Action: PartialEq;
+    type Context: PartialEq;
+
+    fn action(&self) -> &Self::Action;
+    fn context(&self) -> &Self::Context;
+    fn token(&self) -> &Addr;
+    fn user(&self) -> &Addr;
+    /// (inflow, outflow) for metrics that moved funds
+    fn flow(&self) -> Option<(Uint128, Uint128)>;
+}
+
+/// All set fields must match, addresses are compared as given
+#[cw_serde]
+pub struct MetricFilter<A, C> {
+    pub action: Option<A>,
+    pub context: Option<C>,
+    pub token: Option<String>,
+    pub user: Option<String>,
+}
+
+impl<A, C> Default for MetricFilter<A, C> {
+    fn default() -> Self {
+        MetricFilter {
+            action: None,
+            context: None,
+            token: None,
+            user: None,
+        }
+    }
+}
+
+impl<A: PartialEq, C: PartialEq> MetricFilter<A, C> {
+    pub fn matches<M: MetricEntry<Action = A, Context = C>>(&self, metric: &M) -> bool {
+        self.action.as_ref().map_or(true, |a| a == metric.action())
+            && self.context.as_ref().map_or(true, |c| c == metric.context())
+            && self
+                .token
+                .as_ref()
+                .map_or(true, |t| t.as_str() == metric.token().as_str())
+            && self
+                .user
+                .as_ref()
+                .map_or(true, |u| u.as_str() == metric.user().as_str())
+    }
+}
+
+/// Funds moved in & out for a token over a period,
+/// only one of `net_inflow`/`net_outflow` is non-zero
+#[cw_serde]
+pub struct NetFlow {
+    pub token: Addr,
+    pub inflow: Uint128,
+    pub outflow: Uint128,
+    pub net_inflow: Uint128,
+    pub net_outflow: Uint128,
+}
+
+pub fn period_key(env: &Env, date: Option<String>, epoch: Option<Uint128>) -> StdResult<u64> {
+    if date.is_some() && epoch.is_some() {
+        return Err(StdError::generic_err("cannot pass both epoch and date"));
+    }
+    if let Some(d) = date {
+        Ok(parse_utc_datetime(&d)?.timestamp() as u64)
+    } else if let Some(e) = epoch {
+        Ok(e.u128() as u64)
+    } else {
+        Ok(env.block.time.seconds())
+    }
+}
+
+/* Returns the matching metrics & the cursor to continue from,
+ * the cursor is the metric's index within the period, which is append only
+ */
+pub fn metrics_page<M>(
+    storage: &dyn Storage,
+    metrics: &PeriodStorage<M>,
+    key: u64,
+    period: Period,
+    filter: Option<MetricFilter<M::Action, M::Context>>,
+    start_after: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<(Vec<M>, Option<u32>)>
+where
+    M: MetricEntry + Serialize + DeserializeOwned + Clone,
+{
+    let filter = filter.unwrap_or_default();
+    let limit = limit
+        .unwrap_or(DEFAULT_METRICS_LIMIT)
+        .min(MAX_METRICS_LIMIT) as usize;
+    let start = start_after.map_or(0, |i| i as usize + 1);
+
+    let mut page = vec![];
+    let mut last = None;
+    let mut next = None;
+
+    for (i, metric) in metrics
+        .load_period(storage, key, period)?
+        .into_iter()
+        .enumerate()
+        .skip(start)
+    {
+        if !filter.matches(&metric) {
+            continue;
+        }
+        if page.len() == limit {
+            next = last;
+            break;
+        }
+        page.push(metric);
+        last = Some(i as u32);
+    }
+
+    Ok((page, next))
+}
+
+pub fn metrics_summary<M>(
+    storage: &dyn Storage,
+    metrics: &PeriodStorage<M>,
+    key: u64,
+    period: Period,
+    token: Option<String>,
+) -> StdResult<Vec<NetFlow>>
+where
+    M: MetricEntry + Serialize + DeserializeOwned + Clone,
+{
+    let filter = MetricFilter::<M::Action, M::Context> {
+        token,
+        ..Default::default()
+    };
+
+    let mut flows: Vec<NetFlow> = vec![];
+
+    for metric in metrics.load_period(storage, key, period)? {
+        if !filter.matches(&metric) {
+            continue;
+        }
+
+        // allowance & accounting changes don't move funds
+        let (inflow, outflow) = match metric.flow() {
+            Some(flow) => flow,
+            None => continue,
+        };
+
+        match flows.iter_mut().find(|f| f.token == *metric.token()) {
+            Some(flow) => {
+                flow.inflow += inflow;
+                flow.outflow += outflow;
+            }
+            None => flows.push(NetFlow {
+                token: metric.token().clone(),
+                inflow,
+                outflow,
+                net_inflow: Uint128::zero(),
+                net_outflow: Uint128::zero(),
+            }),
+        }
+    }
+
+    for flow in flows.iter_mut() {
+        if flow.inflow > flow.outflow {
+            flow.net_inflow = flow.inflow - flow.outflow;
+        } else {
+            flow.net_outflow = flow.outflow - flow.inflow;
+        }
+    }
+
+    Ok(flows)
+}
@@ -13,6 +13,9 @@ pub mod rewards_emission;
 #[cfg(feature = "treasury")]
 pub mod treasury;
 
+#[cfg(any(feature = "treasury", feature = "treasury_manager"))]
+pub mod metrics;
+
 #[cfg(feature = "scrt_staking")]
 pub mod scrt_staking;
 
@@ -21,3 +24,6 @@ pub mod lp_shdswap;
 
 #[cfg(feature = "stkd_scrt")]
 pub mod stkd_scrt;
+
+#[cfg(feature = "basket")]
+pub mod basket;
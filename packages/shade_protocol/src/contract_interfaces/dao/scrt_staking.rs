@@ -15,6 +15,10 @@ pub struct Config {
     pub owner: Addr,
     pub sscrt: Contract,
     pub validator_bounds: Option<ValidatorBounds>,
+    // When set, delegations are split across these validators instead of a single random one
+    pub weighted_validators: Option<WeightedValidators>,
+    // Chain unbonding period in seconds, used for unbonding maturities & redelegation limits
+    pub unbonding_period: u64,
 }
 
 #[cw_serde]
@@ -25,12 +29,61 @@ pub struct ValidatorBounds {
     pub bottom_position: Uint128,
 }
 
+#[cw_serde]
+pub struct ValidatorWeight {
+    pub validator: String,
+    pub weight: Uint128,
+}
+
+#[cw_serde]
+pub struct WeightedValidators {
+    pub validators: Vec<ValidatorWeight>,
+    // Portion (10^18 = 100%) of total delegations a validator can drift before being rebalanced
+    pub tolerance: Uint128,
+}
+
+#[cw_serde]
+pub struct ValidatorDistribution {
+    pub validator: String,
+    // Portion (10^18 = 100%) of total delegations
+    pub target_portion: Uint128,
+    pub target: Uint128,
+    pub actual: Uint128,
+}
+
+#[cw_serde]
+pub enum RedelegationReason {
+    Rebalance,
+    OutOfBounds,
+}
+
+#[cw_serde]
+pub struct Redelegation {
+    pub src_validator: String,
+    pub dst_validator: String,
+    pub amount: Uint128,
+    pub reason: RedelegationReason,
+    pub timestamp: u64,
+}
+
+#[cw_serde]
+pub struct PendingUnbonding {
+    pub id: Uint128,
+    pub validator: String,
+    // Amount not yet claimed
+    pub amount: Uint128,
+    // Expected time (seconds) the funds are returned
+    pub maturity: u64,
+}
+
 #[cw_serde]
 pub struct InstantiateMsg {
     pub admin_auth: RawContract,
     pub owner: String,
     pub sscrt: RawContract,
     pub validator_bounds: Option<ValidatorBounds>,
+    pub weighted_validators: Option<WeightedValidators>,
+    pub unbonding_period: u64,
     pub viewing_key: String,
 }
 
@@ -68,7 +121,9 @@ pub enum ExecuteAnswer {
     },
     Receive {
         status: ResponseStatus,
+        // First validator delegated to, kept for existing clients
         validator: Validator,
+        validators: Vec<Validator>,
     },
     /*
     Claim {
@@ -86,6 +141,9 @@ pub enum QueryMsg {
     Config {},
     Delegations {},
     Rewards {},
+    ValidatorDistribution {},
+    Redelegations {},
+    PendingUnbondings {},
     Adapter(adapter::SubQueryMsg),
 }
 
@@ -95,6 +153,17 @@ impl Query for QueryMsg {
 
 #[cw_serde]
 pub enum QueryAnswer {
-    Config { config: Config },
+    Config {
+        config: Config,
+    },
+    ValidatorDistribution {
+        validators: Vec<ValidatorDistribution>,
+    },
+    Redelegations {
+        redelegations: Vec<Redelegation>,
+    },
+    PendingUnbondings {
+        unbondings: Vec<PendingUnbonding>,
+    },
     //Balance { amount: Uint128 },
 }
@@ -1,5 +1,5 @@
 use crate::{
-    c_std::{Addr, Binary, Uint128},
+    c_std::{Addr, Api, Binary, StdResult, Uint128},
     cosmwasm_schema::cw_serde,
     utils::{
         asset::{Contract, RawContract},
@@ -18,7 +18,29 @@ pub struct Config {
     // This is the contract that will "unbond" funds
     pub owner: Addr,
     pub sscrt: Contract,
-    pub staking_derivatives: Contract,
+    pub staking_derivatives: Vec<Derivative>,
+}
+
+#[cw_serde]
+pub struct Derivative {
+    pub contract: Contract,
+    // Relative share of deposits, out of the sum of all weights
+    pub weight: Uint128,
+}
+
+#[cw_serde]
+pub struct RawDerivative {
+    pub contract: RawContract,
+    pub weight: Uint128,
+}
+
+impl RawDerivative {
+    pub fn into_valid(self, api: &dyn Api) -> StdResult<Derivative> {
+        Ok(Derivative {
+            contract: self.contract.into_valid(api)?,
+            weight: self.weight,
+        })
+    }
 }
 
 #[cw_serde]
@@ -27,7 +49,7 @@ pub struct InstantiateMsg {
     pub owner: String,
     pub sscrt: RawContract,
     pub viewing_key: String,
-    pub staking_derivatives: RawContract,
+    pub staking_derivatives: Vec<RawDerivative>,
 }
 
 impl InstantiateCallback for InstantiateMsg {
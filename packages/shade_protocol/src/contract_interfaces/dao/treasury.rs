@@ -11,6 +11,9 @@ use cosmwasm_schema::cw_serde;
 
 use crate::utils::storage::plus::period_storage::Period;
 
+use crate::contract_interfaces::dao::metrics;
+pub use crate::contract_interfaces::dao::metrics::NetFlow;
+
 /// The permission referenced in the Admin Auth contract to give a user
 /// admin permissions for the Shade Treasury
 //pub const SHADE_TREASURY_ADMIN: &str = "SHADE_TREASURY_ADMIN";
@@ -19,6 +22,12 @@ use crate::utils::storage::plus::period_storage::Period;
 pub struct Config {
     pub admin_auth: Contract,
     pub multisig: Addr,
+    /// Seconds an allowance/manager change waits in the queue before it can be applied,
+    /// zero applies changes immediately.
+    /// Lowering it and changing the multisig are queued behind the current value
+    pub timelock: Uint128,
+    /// Price source for portfolio valuation
+    pub oracle: Option<Contract>,
 }
 
 #[cw_serde]
@@ -46,6 +55,8 @@ pub enum Action {
     FundsReceived,
     SendFunds,
     Wrap,
+    // Allowance increase refused by the asset outflow limit
+    OutflowCapped,
 }
 
 #[cw_serde]
@@ -58,6 +69,33 @@ pub struct Metric {
     pub user: Addr,
 }
 
+pub type MetricFilter = metrics::MetricFilter<Action, Context>;
+
+impl metrics::MetricEntry for Metric {
+    type Action = Action;
+    type Context = Context;
+
+    fn action(&self) -> &Action {
+        &self.action
+    }
+    fn context(&self) -> &Context {
+        &self.context
+    }
+    fn token(&self) -> &Addr {
+        &self.token
+    }
+    fn user(&self) -> &Addr {
+        &self.user
+    }
+    fn flow(&self) -> Option<(Uint128, Uint128)> {
+        match self.action {
+            Action::FundsReceived | Action::Wrap => Some((self.amount, Uint128::zero())),
+            Action::SendFunds => Some((Uint128::zero(), self.amount)),
+            _ => None,
+        }
+    }
+}
+
 #[cw_serde]
 pub enum AllowanceType {
     Amount,
@@ -104,10 +142,91 @@ pub struct AllowanceMeta {
     pub last_refresh: String,
 }
 
+/// Hard cap on the funds pulled for an asset across all spenders within a rolling window of
+/// `cycle`, allowance still outstanding counts against the cap
+#[cw_serde]
+pub struct OutflowLimit {
+    pub amount: Uint128,
+    pub cycle: Cycle,
+}
+
+/// Funds pulled through allowances, `pulls` are (timestamp, amount) as observed on update and
+/// `granted` is the allowance each spender was left with by the last update
+#[cw_serde]
+pub struct Outflow {
+    pub pulls: Vec<(u64, Uint128)>,
+    pub granted: Vec<(Addr, Uint128)>,
+}
+
+impl Outflow {
+    pub fn pulled(&self) -> Uint128 {
+        self.pulls.iter().map(|(_, amount)| *amount).sum()
+    }
+}
+
+/// Funds of an asset held by the treasury or one of its managers,
+/// `value` is in USD normalized to 10^18
+#[cw_serde]
+pub struct Position {
+    pub holder: Addr,
+    pub balance: Uint128,
+    pub unbonding: Uint128,
+    pub claimable: Uint128,
+    pub value: Uint128,
+    /// A manager's funds in each of its adapters across all of its holders,
+    /// not included in the totals
+    pub adapters: Vec<Position>,
+}
+
+#[cw_serde]
+pub struct AssetValuation {
+    pub asset: Addr,
+    pub symbol: String,
+    // USD per token, normalized to 10^18
+    pub price: Uint128,
+    pub amount: Uint128,
+    pub value: Uint128,
+    pub positions: Vec<Position>,
+}
+
+/// A change queued behind the timelock
+#[cw_serde]
+pub enum QueuedAction {
+    Allowance {
+        asset: Addr,
+        allowance: Allowance,
+        refresh_now: bool,
+    },
+    RegisterManager {
+        contract: Contract,
+    },
+    SetTimelock {
+        timelock: Uint128,
+    },
+    SetMultisig {
+        multisig: Addr,
+    },
+    SetOutflowLimit {
+        asset: Addr,
+        limit: Option<OutflowLimit>,
+    },
+}
+
+#[cw_serde]
+pub struct PendingChange {
+    pub id: Uint128,
+    pub action: QueuedAction,
+    pub proposer: Addr,
+    pub queued_at: Uint128,
+    // Earliest time the change can be applied
+    pub execute_after: Uint128,
+}
+
 #[cw_serde]
 pub struct InstantiateMsg {
     pub admin_auth: RawContract,
     pub multisig: String,
+    pub timelock: Uint128,
     pub viewing_key: String,
 }
 
@@ -127,6 +246,10 @@ pub enum ExecuteMsg {
     UpdateConfig {
         admin_auth: Option<RawContract>,
         multisig: Option<String>,
+        timelock: Option<Uint128>,
+        oracle: Option<RawContract>,
+        /// Removes the oracle, disabling portfolio valuation
+        clear_oracle: Option<bool>,
     },
     RegisterAsset {
         contract: RawContract,
@@ -151,6 +274,19 @@ pub enum ExecuteMsg {
     SetRunLevel {
         run_level: RunLevel,
     },
+    // None removes the limit
+    SetOutflowLimit {
+        asset: String,
+        limit: Option<OutflowLimit>,
+    },
+    // Apply a queued change once its timelock has passed, callable by anyone
+    ApplyChange {
+        id: Uint128,
+    },
+    // Drop a queued change, multisig only
+    CancelChange {
+        id: Uint128,
+    },
 }
 
 impl ExecuteCallback for ExecuteMsg {
@@ -201,6 +337,20 @@ pub enum ExecuteAnswer {
         success: Vec<Coin>,
         failed: Vec<Coin>,
     },
+    SetOutflowLimit {
+        status: ResponseStatus,
+    },
+    QueueChange {
+        id: Uint128,
+        execute_after: Uint128,
+        status: ResponseStatus,
+    },
+    ApplyChange {
+        status: ResponseStatus,
+    },
+    CancelChange {
+        status: ResponseStatus,
+    },
 }
 
 #[cw_serde]
@@ -222,6 +372,21 @@ pub enum QueryMsg {
         epoch: Option<Uint128>,
         period: Period,
     },
+    // Filtered metrics for a period, `start_after` is the `next` cursor of the previous page
+    MetricsPage {
+        date: Option<String>,
+        epoch: Option<Uint128>,
+        period: Period,
+        filter: Option<MetricFilter>,
+        start_after: Option<u32>,
+        limit: Option<u32>,
+    },
+    MetricsSummary {
+        date: Option<String>,
+        epoch: Option<Uint128>,
+        period: Period,
+        token: Option<String>,
+    },
     Balance {
         asset: String,
     },
@@ -231,6 +396,14 @@ pub enum QueryMsg {
     Reserves {
         asset: String,
     },
+    PendingChanges {},
+    PendingChange {
+        id: Uint128,
+    },
+    OutflowLimit {
+        asset: String,
+    },
+    Portfolio {},
 }
 
 impl Query for QueryMsg {
@@ -245,6 +418,23 @@ pub enum QueryAnswer {
     Allowance { amount: Uint128 },
     RunLevel { run_level: RunLevel },
     Metrics { metrics: Vec<Metric> },
+    MetricsPage {
+        metrics: Vec<Metric>,
+        next: Option<u32>,
+    },
+    MetricsSummary {
+        flows: Vec<NetFlow>,
+    },
     Balance { amount: Uint128 },
     Reserves { amount: Uint128 },
+    PendingChanges { changes: Vec<PendingChange> },
+    PendingChange { change: PendingChange },
+    OutflowLimit {
+        limit: Option<OutflowLimit>,
+        outflow: Uint128,
+    },
+    Portfolio {
+        assets: Vec<AssetValuation>,
+        total_value: Uint128,
+    },
 }
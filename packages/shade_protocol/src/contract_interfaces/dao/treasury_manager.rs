@@ -1,6 +1,6 @@
 use crate::{
     c_std::{Addr, Api, Binary, StdResult, Uint128},
-    contract_interfaces::dao::manager,
+    contract_interfaces::dao::{manager, metrics},
     utils::{
         asset::{Contract, RawContract},
         generic_response::ResponseStatus,
@@ -11,6 +11,8 @@ use crate::{
 use crate::utils::{ExecuteCallback, InstantiateCallback, Query};
 use cosmwasm_schema::cw_serde;
 
+pub use crate::contract_interfaces::dao::metrics::NetFlow;
+
 #[cw_serde]
 pub enum Context {
     Receive,
@@ -18,6 +20,7 @@ pub enum Context {
     Unbond,
     Claim,
     Holders,
+    Unwind,
 }
 
 #[cw_serde]
@@ -44,6 +47,35 @@ pub struct Metric {
     pub user: Addr,
 }
 
+pub type MetricFilter = metrics::MetricFilter<Action, Context>;
+
+impl metrics::MetricEntry for Metric {
+    type Action = Action;
+    type Context = Context;
+
+    fn action(&self) -> &Action {
+        &self.action
+    }
+    fn context(&self) -> &Context {
+        &self.context
+    }
+    fn token(&self) -> &Addr {
+        &self.token
+    }
+    fn user(&self) -> &Addr {
+        &self.user
+    }
+    fn flow(&self) -> Option<(Uint128, Uint128)> {
+        match self.action {
+            Action::FundsReceived | Action::RealizeGains => Some((self.amount, Uint128::zero())),
+            Action::SendFunds | Action::SendFundsFrom | Action::RealizeLosses => {
+                Some((Uint128::zero(), self.amount))
+            }
+            _ => None,
+        }
+    }
+}
+
 #[cw_serde]
 pub struct Config {
     pub admin_auth: Contract,
@@ -116,6 +148,13 @@ pub enum AllocationType {
     Amount,
 }
 
+#[cw_serde]
+pub enum AllocationStatus {
+    Active,
+    // Emergency unwound, claimed funds are swept back to treasury on update
+    Unwinding,
+}
+
 //TODO remove - same as Allocation
 #[cw_serde]
 pub struct AllocationMeta {
@@ -124,6 +163,7 @@ pub struct AllocationMeta {
     pub alloc_type: AllocationType,
     pub amount: Uint128,
     pub tolerance: Uint128,
+    pub status: AllocationStatus,
 }
 
 #[cw_serde]
@@ -174,6 +214,11 @@ pub enum ExecuteMsg {
     RemoveHolder {
         holder: String,
     },
+    // Unbond & zero the allocations of all (or the given) adapters for an asset
+    EmergencyUnwind {
+        asset: String,
+        adapters: Option<Vec<String>>,
+    },
     Manager(manager::SubExecuteMsg),
 }
 
@@ -206,6 +251,10 @@ pub enum ExecuteAnswer {
     RemoveHolder {
         status: ResponseStatus,
     },
+    EmergencyUnwind {
+        status: ResponseStatus,
+        unbonded: Uint128,
+    },
     Manager(manager::ExecuteAnswer),
 }
 
@@ -228,6 +277,21 @@ pub enum QueryMsg {
         epoch: Option<Uint128>,
         period: Period,
     },
+    // Filtered metrics for a period, `start_after` is the `next` cursor of the previous page
+    MetricsPage {
+        date: Option<String>,
+        epoch: Option<Uint128>,
+        period: Period,
+        filter: Option<MetricFilter>,
+        start_after: Option<u32>,
+        limit: Option<u32>,
+    },
+    MetricsSummary {
+        date: Option<String>,
+        epoch: Option<Uint128>,
+        period: Period,
+        token: Option<String>,
+    },
     Manager(manager::SubQueryMsg),
 }
 
@@ -244,4 +308,11 @@ pub enum QueryAnswer {
     Holders { holders: Vec<Addr> },
     Holding { holding: Holding },
     Metrics { metrics: Vec<Metric> },
+    MetricsPage {
+        metrics: Vec<Metric>,
+        next: Option<u32>,
+    },
+    MetricsSummary {
+        flows: Vec<NetFlow>,
+    },
 }
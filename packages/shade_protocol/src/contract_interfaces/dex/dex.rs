@@ -1,6 +1,6 @@
 use crate::{
     contract_interfaces::{
-        dex::{secretswap, sienna},
+        dex::{secretswap, shadeswap, sienna},
         oracles::band,
         snip20::helpers::Snip20Asset,
     },
@@ -9,7 +9,7 @@ use crate::{
         price::{normalize_price, translate_price},
     },
 };
-use crate::c_std::{Deps, StdError, StdResult};
+use crate::c_std::{CosmosMsg, Deps, StdError, StdResult};
 
 use cosmwasm_schema::{cw_serde};
 
@@ -171,3 +171,33 @@ pub fn price(
                                                                              */
     }
 }
+
+pub fn simulate_swap(
+    deps: &Deps,
+    dex: &Dex,
+    pair: &Contract,
+    offer: &Contract,
+    amount: Uint128,
+) -> StdResult<Uint128> {
+    match dex {
+        Dex::SecretSwap => secretswap::simulate_swap(deps, pair, offer, amount),
+        Dex::SiennaSwap => sienna::simulate_swap(deps, pair, offer, amount),
+        Dex::ShadeSwap => shadeswap::simulate_swap(deps, pair, offer, amount),
+        _ => Err(StdError::generic_err(format!("{:?} swaps not implemented", dex))),
+    }
+}
+
+pub fn swap_msg(
+    dex: &Dex,
+    pair: &Contract,
+    offer: &Contract,
+    amount: Uint128,
+    expected_return: Option<Uint128>,
+) -> StdResult<CosmosMsg> {
+    match dex {
+        Dex::SecretSwap => secretswap::swap_msg(pair, offer, amount, expected_return),
+        Dex::SiennaSwap => sienna::swap_msg(pair, offer, amount, expected_return),
+        Dex::ShadeSwap => shadeswap::swap_msg(pair, offer, amount, expected_return),
+        _ => Err(StdError::generic_err(format!("{:?} swaps not implemented", dex))),
+    }
+}
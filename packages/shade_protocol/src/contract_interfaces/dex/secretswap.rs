@@ -1,6 +1,6 @@
 use crate::{
-    c_std::{Addr, Deps, StdResult, Uint128},
-    contract_interfaces::{dex::dex, oracles::band},
+    c_std::{to_binary, Addr, CosmosMsg, Deps, StdResult, Uint128},
+    contract_interfaces::{dex::dex, oracles::band, snip20::helpers::send_msg},
     utils::{
         asset::Contract,
         price::{normalize_price, translate_price},
@@ -134,3 +134,46 @@ pub fn pool_cp(deps: &Deps, pair: dex::TradingPair) -> StdResult<Uint128> {
         pool.assets[0].amount.u128() * pool.assets[1].amount.u128(),
     ))
 }
+
+pub fn simulate_swap(
+    deps: &Deps,
+    pair: &Contract,
+    offer: &Contract,
+    amount: Uint128,
+) -> StdResult<Uint128> {
+    let response: SimulationResponse = PairQuery::Simulation {
+        offer_asset: Asset {
+            amount,
+            info: AssetInfo {
+                token: Token {
+                    contract_addr: offer.address.clone(),
+                    token_code_hash: offer.code_hash.clone(),
+                    viewing_key: "SecretSwap".to_string(),
+                },
+            },
+        },
+    }
+    .query(&deps.querier, pair)?;
+
+    Ok(response.return_amount)
+}
+
+pub fn swap_msg(
+    pair: &Contract,
+    offer: &Contract,
+    amount: Uint128,
+    expected_return: Option<Uint128>,
+) -> StdResult<CosmosMsg> {
+    send_msg(
+        pair.address.clone(),
+        amount,
+        Some(to_binary(&CallbackMsg {
+            swap: CallbackSwap {
+                expected_return: expected_return.unwrap_or_default(),
+            },
+        })?),
+        None,
+        None,
+        offer,
+    )
+}
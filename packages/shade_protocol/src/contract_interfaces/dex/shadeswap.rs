@@ -1,5 +1,6 @@
 use crate::{
-    c_std::{Addr, Binary, Uint128},
+    c_std::{to_binary, Addr, Binary, CosmosMsg, Deps, StdError, StdResult, Uint128},
+    contract_interfaces::snip20::helpers::send_msg,
     utils::{
         asset::Contract,
         Query,
@@ -227,3 +228,50 @@ pub fn pool_cp(
     Ok(Uint128::new(pool.assets[0].amount.u128() * pool.assets[1].amount.u128()))
 }
 */
+
+pub fn simulate_swap(
+    deps: &Deps,
+    pair: &Contract,
+    offer: &Contract,
+    amount: Uint128,
+) -> StdResult<Uint128> {
+    let response: QueryMsgResponse = PairQuery::GetEstimatedPrice {
+        offer: TokenAmount {
+            token: TokenType::CustomToken {
+                contract_addr: offer.address.clone(),
+                token_code_hash: offer.code_hash.clone(),
+            },
+            amount,
+        },
+    }
+    .query(&deps.querier, pair)?;
+
+    match response {
+        QueryMsgResponse::EstimatedPrice { estimated_price } => Ok(estimated_price),
+        _ => Err(StdError::generic_err(format!(
+            "Unexpected estimated price response from {}",
+            pair.address
+        ))),
+    }
+}
+
+pub fn swap_msg(
+    pair: &Contract,
+    offer: &Contract,
+    amount: Uint128,
+    expected_return: Option<Uint128>,
+) -> StdResult<CosmosMsg> {
+    send_msg(
+        pair.address.clone(),
+        amount,
+        Some(to_binary(&SwapTokens {
+            expected_return,
+            to: None,
+            router_link: None,
+            callback_signature: None,
+        })?),
+        None,
+        None,
+        offer,
+    )
+}
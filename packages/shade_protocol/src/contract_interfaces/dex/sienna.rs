@@ -1,6 +1,6 @@
 use crate::{
-    c_std::{Addr, Deps, StdResult, Uint128},
-    contract_interfaces::{dex::dex, oracles::band},
+    c_std::{to_binary, Addr, CosmosMsg, Deps, StdResult, Uint128},
+    contract_interfaces::{dex::dex, oracles::band, snip20::helpers::send_msg},
     utils::{
         asset::Contract,
         price::{normalize_price, translate_price},
@@ -168,3 +168,42 @@ pub fn pool_cp(deps: &Deps, pair: dex::TradingPair) -> StdResult<Uint128> {
         pair_info.pair_info.amount_0.u128() * pair_info.pair_info.amount_1.u128(),
     ))
 }
+
+pub fn simulate_swap(
+    deps: &Deps,
+    pair: &Contract,
+    offer: &Contract,
+    amount: Uint128,
+) -> StdResult<Uint128> {
+    let response: SimulationResponse = PairQuery::SwapSimulation {
+        offer: TokenTypeAmount {
+            amount,
+            token: TokenType::CustomToken {
+                contract_addr: offer.address.clone(),
+                token_code_hash: offer.code_hash.clone(),
+            },
+        },
+    }
+    .query(&deps.querier, pair)?;
+
+    Ok(response.return_amount)
+}
+
+pub fn swap_msg(
+    pair: &Contract,
+    offer: &Contract,
+    amount: Uint128,
+    expected_return: Option<Uint128>,
+) -> StdResult<CosmosMsg> {
+    send_msg(
+        pair.address.clone(),
+        amount,
+        Some(to_binary(&ReceiverCallbackMsg::Swap {
+            expected_return,
+            to: None,
+        })?),
+        None,
+        None,
+        offer,
+    )
+}
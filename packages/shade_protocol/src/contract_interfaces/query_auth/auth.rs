@@ -39,6 +39,25 @@ impl Key {
             Some(hashed) => Key(key).compare(&hashed.0)
         })
     }
+
+    // Accepts the user's main key, or one of their sub keys that hasn't expired
+    // and is scoped to the given contract
+    pub fn verify_with_scope(
+        storage: &dyn Storage,
+        address: Addr,
+        key: String,
+        now: u64,
+        contract: Option<&Addr>,
+    ) -> StdResult<bool> {
+        if Key::verify(storage, address.clone(), key.clone())? {
+            return Ok(true);
+        }
+
+        Ok(match SubKey::may_load(storage, (address, Key(key).hash().to_vec()))? {
+            None => false,
+            Some(sub_key) => sub_key.is_valid(now, contract),
+        })
+    }
 }
 
 impl ToString for Key {
@@ -57,6 +76,36 @@ impl MapStorage<'static, Addr> for HashedKey {
 }
 
 
+#[cw_serde]
+pub struct SubKey {
+    // Block time in seconds after which the key stops working
+    pub expiration: Option<u64>,
+    // Contracts allowed to validate the key, any contract when None
+    pub contracts: Option<Vec<Addr>>,
+}
+
+// (User, hashed key)
+impl MapStorage<'static, (Addr, Vec<u8>)> for SubKey {
+    const MAP: Map<'static, (Addr, Vec<u8>), Self> = Map::new("sub-viewing-key-");
+}
+
+impl SubKey {
+    pub fn is_valid(&self, now: u64, contract: Option<&Addr>) -> bool {
+        if let Some(expiration) = self.expiration {
+            if now >= expiration {
+                return false;
+            }
+        }
+
+        match (&self.contracts, contract) {
+            (None, _) => true,
+            (Some(contracts), Some(contract)) => contracts.contains(contract),
+            // Scoped keys can't be checked without knowing who is asking
+            (Some(_), None) => false,
+        }
+    }
+}
+
 #[cw_serde]
 pub struct PermitKey(pub bool);
 
@@ -75,4 +124,30 @@ impl PermitKey {
             Some(_) => true
         })
     }
+}
+
+// Only permits signed for the user's current epoch are valid, starts at 0
+#[cw_serde]
+pub struct PermitEpoch(pub u64);
+
+impl MapStorage<'static, Addr> for PermitEpoch {
+    const MAP: Map<'static, Addr, Self> = Map::new("permit-epoch-");
+}
+
+impl PermitEpoch {
+    pub fn current(storage: &dyn Storage, user: Addr) -> StdResult<u64> {
+        Ok(PermitEpoch::may_load(storage, user)?.map_or(0, |epoch| epoch.0))
+    }
+
+    // Revokes every permit signed for the current epoch
+    pub fn bump(storage: &mut dyn Storage, user: Addr) -> StdResult<u64> {
+        let epoch = PermitEpoch::current(storage, user.clone())? + 1;
+        PermitEpoch(epoch).save(storage, user)?;
+        Ok(epoch)
+    }
+
+    // Scoped permits without an epoch belong to epoch 0
+    pub fn is_revoked(storage: &dyn Storage, user: Addr, epoch: Option<u64>) -> StdResult<bool> {
+        Ok(epoch.unwrap_or(0) != PermitEpoch::current(storage, user)?)
+    }
 }
\ No newline at end of file
@@ -36,15 +36,60 @@ pub fn authenticate_permit<T: DeserializeOwned>(
     })
 }
 
+/// Returns the signer when the permit is unrevoked, has all the required permissions and can
+/// be used on the given contract
+pub fn authenticate_permit_for(
+    permit: QueryPermit,
+    required_permissions: Vec<String>,
+    contract: Addr,
+    querier: &QuerierWrapper,
+    authenticator: &Contract
+) -> StdResult<Option<Addr>> {
+    let res: query_auth::QueryAnswer = query_auth::QueryMsg::ValidatePermitFor {
+        permit,
+        required_permissions,
+        contract,
+    }.query(querier, authenticator)?;
+
+    match res {
+        query_auth::QueryAnswer::ValidatePermitFor { user, is_valid } => {
+            Ok(if is_valid { Some(user) } else { None })
+        }
+        _ => Err(StdError::generic_err("Wrong query response")),
+    }
+}
+
 pub fn authenticate_vk(
     address: Addr,
     key: String,
     querier: &QuerierWrapper,
     authenticator: &Contract
+) -> StdResult<bool> {
+    validate_vk(address, key, None, querier, authenticator)
+}
+
+/// Also accepts sub keys scoped to the given contract
+pub fn authenticate_scoped_vk(
+    address: Addr,
+    key: String,
+    contract: Addr,
+    querier: &QuerierWrapper,
+    authenticator: &Contract
+) -> StdResult<bool> {
+    validate_vk(address, key, Some(contract), querier, authenticator)
+}
+
+fn validate_vk(
+    address: Addr,
+    key: String,
+    contract: Option<Addr>,
+    querier: &QuerierWrapper,
+    authenticator: &Contract
 ) -> StdResult<bool> {
     let res: query_auth::QueryAnswer = query_auth::QueryMsg::ValidateViewingKey {
         user: address,
         key,
+        contract,
     }.query(querier, authenticator)?;
 
     match res {
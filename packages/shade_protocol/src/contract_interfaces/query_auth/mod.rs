@@ -88,11 +88,30 @@ pub enum ExecuteMsg {
         entropy: String,
         padding: Option<String>,
     },
+    // Additional key that can expire and be limited to some contracts
+    CreateSubKey {
+        entropy: String,
+        expiration: Option<u64>,
+        contracts: Option<Vec<String>>,
+        padding: Option<String>,
+    },
+    RevokeSubKey {
+        key: String,
+        padding: Option<String>,
+    },
 
     BlockPermitKey {
         key: String,
         padding: Option<String>,
     },
+    BlockPermitKeys {
+        keys: Vec<String>,
+        padding: Option<String>,
+    },
+    // Revokes every scoped permit signed so far by bumping the sender's permit epoch
+    BlockAllPermits {
+        padding: Option<String>,
+    },
 }
 
 impl ExecuteCallback for ExecuteMsg {
@@ -105,7 +124,11 @@ pub enum ExecuteAnswer {
     SetRunState { status: ResponseStatus },
     SetViewingKey { status: ResponseStatus },
     CreateViewingKey { key: String },
+    CreateSubKey { key: String },
+    RevokeSubKey { status: ResponseStatus },
     BlockPermitKey { status: ResponseStatus },
+    BlockPermitKeys { status: ResponseStatus },
+    BlockAllPermits { status: ResponseStatus, epoch: u64 },
 }
 
 pub type QueryPermit = Permit<PermitData>;
@@ -117,12 +140,35 @@ pub struct PermitData {
     pub key: String,
 }
 
+/// Permit data layout understood by ValidatePermitFor
+#[cw_serde]
+pub struct PermitScope {
+    pub permissions: Vec<String>,
+    // Contracts the permit can be used on, any contract when None
+    pub contracts: Option<Vec<Addr>>,
+    // Must match the signer's current permit epoch, permits without one belong to epoch 0
+    pub epoch: Option<u64>,
+}
+
 #[cw_serde]
 pub enum QueryMsg {
     Config {},
 
-    ValidateViewingKey { user: Addr, key: String },
+    ValidateViewingKey {
+        user: Addr,
+        key: String,
+        // Contract asking, required for scoped sub keys
+        contract: Option<Addr>,
+    },
     ValidatePermit { permit: QueryPermit },
+    // Epoch new permits must be signed for
+    PermitEpoch { user: Addr },
+    // Permit data must be a PermitScope
+    ValidatePermitFor {
+        permit: QueryPermit,
+        required_permissions: Vec<String>,
+        contract: Addr,
+    },
 }
 
 impl Query for QueryMsg {
@@ -142,4 +188,11 @@ pub enum QueryAnswer {
         user: Addr,
         is_revoked: bool,
     },
+    ValidatePermitFor {
+        user: Addr,
+        is_valid: bool,
+    },
+    PermitEpoch {
+        epoch: u64,
+    },
 }
@@ -37,6 +37,8 @@ pub fn send_msg(
         amount,
         msg,
         memo,
+        decoys: None,
+        entropy: None,
         padding,
     }
     .to_cosmos_msg(contract, vec![])?)
@@ -131,7 +133,12 @@ pub fn batch_send_msg(
     padding: Option<String>,
     contract: &Contract,
 ) -> StdResult<CosmosMsg> {
-    ExecuteMsg::BatchSend { actions, padding }.to_cosmos_msg(contract, vec![])
+    ExecuteMsg::BatchSend {
+        actions,
+        entropy: None,
+        padding,
+    }
+    .to_cosmos_msg(contract, vec![])
 }
 
 pub fn batch_send_from_msg(
@@ -2,7 +2,10 @@ use crate::c_std::{Addr, BlockInfo, StdResult, Storage};
 use cosmwasm_std::Timestamp;
 
 #[cfg(feature = "snip20-impl")]
-use crate::utils::storage::plus::{ItemStorage, MapStorage, NaiveItemStorage};
+use crate::utils::{
+    crypto::Prng,
+    storage::plus::{ItemStorage, MapStorage, NaiveItemStorage},
+};
 use crate::{
     c_std::Uint128,
     contract_interfaces::snip20::errors::{
@@ -262,6 +265,37 @@ impl Balance {
         Self::add(storage, amount, recipient)?;
         Ok(())
     }
+
+    // Credits the recipient somewhere among rewrites of each decoy's unchanged balance,
+    // so the storage access pattern doesn't reveal which account was paid
+    pub fn add_with_decoys(
+        storage: &mut dyn Storage,
+        amount: Uint128,
+        recipient: &Addr,
+        decoys: &[Addr],
+        rng: &mut Prng,
+    ) -> StdResult<Uint128> {
+        let bytes = rng.rand_bytes();
+        let position = u64::from_be_bytes([
+            bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7],
+        ]) as usize
+            % (decoys.len() + 1);
+
+        let mut balance = Uint128::zero();
+        for (i, decoy) in decoys.iter().enumerate() {
+            if i == position {
+                balance = Self::add(storage, amount, recipient)?;
+            }
+            let decoy_balance = Self::may_load(storage, decoy.clone())?
+                .unwrap_or(Self(Uint128::zero()));
+            decoy_balance.save(storage, decoy.clone())?;
+        }
+        if position == decoys.len() {
+            balance = Self::add(storage, amount, recipient)?;
+        }
+
+        Ok(balance)
+    }
 }
 
 #[cw_serde]
@@ -280,6 +314,20 @@ pub struct AllowanceResponse {
     pub expiration: Option<u64>,
 }
 
+#[cw_serde]
+pub struct AllowanceGiven {
+    pub spender: Addr,
+    pub allowance: Uint128,
+    pub expiration: Option<u64>,
+}
+
+#[cw_serde]
+pub struct AllowanceReceived {
+    pub owner: Addr,
+    pub allowance: Uint128,
+    pub expiration: Option<u64>,
+}
+
 #[cw_serde]
 pub struct Allowance {
     pub amount: Uint128,
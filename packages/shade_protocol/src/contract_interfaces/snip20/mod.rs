@@ -22,6 +22,8 @@ use crate::{
             errors::{invalid_decimals, invalid_name_format, invalid_symbol_format},
             manager::{
                 Admin,
+                AllowanceGiven,
+                AllowanceReceived,
                 Balance,
                 CoinInfo,
                 Config,
@@ -30,7 +32,7 @@ use crate::{
                 RandSeed,
                 TotalSupply,
             },
-            transaction_history::{RichTx, Tx},
+            transaction_history::{RichTx, Tx, TxFilter},
         },
     },
     snip20::manager::QueryAuth,
@@ -248,6 +250,9 @@ pub enum ExecuteMsg {
         recipient: String,
         amount: Uint128,
         memo: Option<String>,
+        // Accounts whose balances are touched alongside the recipient's
+        decoys: Option<Vec<String>>,
+        entropy: Option<Binary>,
         padding: Option<String>,
     },
     Send {
@@ -256,14 +261,18 @@ pub enum ExecuteMsg {
         amount: Uint128,
         msg: Option<Binary>,
         memo: Option<String>,
+        decoys: Option<Vec<String>>,
+        entropy: Option<Binary>,
         padding: Option<String>,
     },
     BatchTransfer {
         actions: Vec<batch::TransferAction>,
+        entropy: Option<Binary>,
         padding: Option<String>,
     },
     BatchSend {
         actions: Vec<batch::SendAction>,
+        entropy: Option<Binary>,
         padding: Option<String>,
     },
     Burn {
@@ -562,6 +571,18 @@ pub enum QueryMsg {
         spender: String,
         key: String,
     },
+    AllowancesGiven {
+        owner: String,
+        key: String,
+        page: Option<u32>,
+        page_size: u32,
+    },
+    AllowancesReceived {
+        spender: String,
+        key: String,
+        page: Option<u32>,
+        page_size: u32,
+    },
     Balance {
         address: String,
         key: String,
@@ -577,6 +598,7 @@ pub enum QueryMsg {
         key: String,
         page: Option<u32>,
         page_size: u32,
+        filter: Option<TxFilter>,
     },
     Minters {},
     WithPermit {
@@ -594,9 +616,15 @@ impl Query for QueryMsg {
 #[cw_serde]
 pub enum QueryWithPermit {
     Allowance { owner: String, spender: String },
+    AllowancesGiven { owner: String, page: Option<u32>, page_size: u32 },
+    AllowancesReceived { spender: String, page: Option<u32>, page_size: u32 },
     Balance {},
     TransferHistory { page: Option<u32>, page_size: u32 },
-    TransactionHistory { page: Option<u32>, page_size: u32 },
+    TransactionHistory {
+        page: Option<u32>,
+        page_size: u32,
+        filter: Option<TxFilter>,
+    },
 }
 
 #[cw_serde]
@@ -629,6 +657,16 @@ pub enum QueryAnswer {
         allowance: Uint128,
         expiration: Option<u64>,
     },
+    AllowancesGiven {
+        owner: Addr,
+        allowances: Vec<AllowanceGiven>,
+        count: u32,
+    },
+    AllowancesReceived {
+        spender: Addr,
+        allowances: Vec<AllowanceReceived>,
+        count: u32,
+    },
     Balance {
         amount: Uint128,
     },
@@ -74,6 +74,11 @@ pub enum TxAction {
         sender: Addr,
         recipient: Addr,
     },
+    Send {
+        from: Addr,
+        sender: Addr,
+        recipient: Addr,
+    },
     Mint {
         minter: Addr,
         recipient: Addr,
@@ -84,6 +89,39 @@ pub enum TxAction {
     },
     Deposit {},
     Redeem {},
+    IncreaseAllowance {
+        owner: Addr,
+        spender: Addr,
+    },
+    DecreaseAllowance {
+        owner: Addr,
+        spender: Addr,
+    },
+}
+
+#[cw_serde]
+pub enum TxActionType {
+    Transfer,
+    Send,
+    Mint,
+    Burn,
+    Deposit,
+    Redeem,
+    IncreaseAllowance,
+    DecreaseAllowance,
+}
+
+// Every set field must match for a tx to be returned
+#[cw_serde]
+#[derive(Default)]
+pub struct TxFilter {
+    pub actions: Option<Vec<TxActionType>>,
+    // Any other address involved in the tx
+    pub counterparty: Option<String>,
+    // Block time in seconds, inclusive
+    pub start_time: Option<u64>,
+    // Block time in seconds, exclusive
+    pub end_time: Option<u64>,
 }
 
 // Note that id is a globally incrementing counter.
@@ -108,9 +146,39 @@ impl RichTx {
         for_address: &Addr,
         page: u32,
         page_size: u32,
+        filter: Option<&TxFilter>,
     ) -> StdResult<(Vec<Self>, u64)> {
         let id = UserTXTotal::load(storage, for_address.clone())?.0;
         let start_index = page as u64 * page_size as u64;
+
+        if let Some(filter) = filter {
+            // Txs are stored in block order so the date range bounds the scan
+            let first = match filter.start_time {
+                Some(time) => Self::first_at(storage, for_address, id, time)?,
+                None => 0,
+            };
+            let last = match filter.end_time {
+                Some(time) => Self::first_at(storage, for_address, id, time)?,
+                None => id,
+            };
+
+            // Every tx in the date range is checked so the total is the exact match count
+            let mut total = 0u64;
+            let mut txs = vec![];
+            for index in first..last {
+                let stored_tx = StoredRichTx::load(storage, (for_address.clone(), index))?;
+                if !stored_tx.matches(for_address, filter)? {
+                    continue;
+                }
+                total += 1;
+                if total > start_index && txs.len() < page_size as usize {
+                    txs.push(stored_tx.into_humanized()?);
+                }
+            }
+
+            return Ok((txs, total));
+        }
+
         let size: u64;
         if (start_index + page_size as u64) > id {
             size = id;
@@ -124,8 +192,23 @@ impl RichTx {
             txs.push(stored_tx.into_humanized()?);
         }
 
-        let length = txs.len() as u64;
-        Ok((txs, length))
+        Ok((txs, id))
+    }
+
+    /* First index with a block time at or after `time` */
+    fn first_at(storage: &dyn Storage, for_address: &Addr, len: u64, time: u64) -> StdResult<u64> {
+        let time = Timestamp::from_seconds(time);
+        let (mut low, mut high) = (0u64, len);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if StoredRichTx::load(storage, (for_address.clone(), mid))?.block_time < time {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        Ok(low)
     }
 }
 
@@ -138,6 +221,9 @@ enum TxCode {
     Burn = 2,
     Deposit = 3,
     Redeem = 4,
+    Send = 5,
+    IncreaseAllowance = 6,
+    DecreaseAllowance = 7,
 }
 
 impl TxCode {
@@ -153,9 +239,25 @@ impl TxCode {
             2 => Ok(Burn),
             3 => Ok(Deposit),
             4 => Ok(Redeem),
+            5 => Ok(Send),
+            6 => Ok(IncreaseAllowance),
+            7 => Ok(DecreaseAllowance),
             _ => Err(tx_code_invalid_conversion(n)),
         }
     }
+
+    fn action_type(self) -> TxActionType {
+        match self {
+            TxCode::Transfer => TxActionType::Transfer,
+            TxCode::Mint => TxActionType::Mint,
+            TxCode::Burn => TxActionType::Burn,
+            TxCode::Deposit => TxActionType::Deposit,
+            TxCode::Redeem => TxActionType::Redeem,
+            TxCode::Send => TxActionType::Send,
+            TxCode::IncreaseAllowance => TxActionType::IncreaseAllowance,
+            TxCode::DecreaseAllowance => TxActionType::DecreaseAllowance,
+        }
+    }
 }
 
 #[cw_serde]
@@ -176,6 +278,33 @@ impl StoredTxAction {
         }
     }
 
+    fn send(from: Addr, sender: Addr, recipient: Addr) -> Self {
+        Self {
+            tx_type: TxCode::Send.to_u8(),
+            address1: Some(from),
+            address2: Some(sender),
+            address3: Some(recipient),
+        }
+    }
+
+    fn increase_allowance(owner: Addr, spender: Addr) -> Self {
+        Self {
+            tx_type: TxCode::IncreaseAllowance.to_u8(),
+            address1: Some(owner),
+            address2: Some(spender),
+            address3: None,
+        }
+    }
+
+    fn decrease_allowance(owner: Addr, spender: Addr) -> Self {
+        Self {
+            tx_type: TxCode::DecreaseAllowance.to_u8(),
+            address1: Some(owner),
+            address2: Some(spender),
+            address3: None,
+        }
+    }
+
     fn mint(minter: Addr, recipient: Addr) -> Self {
         Self {
             tx_type: TxCode::Mint.to_u8(),
@@ -224,6 +353,11 @@ impl StoredTxAction {
         let burn_addr_err = || {
             StdError::generic_err("Missing address in stored Burn transaction. Storage is corrupt")
         };
+        let allowance_addr_err = || {
+            StdError::generic_err(
+                "Missing address in stored Allowance transaction. Storage is corrupt",
+            )
+        };
 
         // In all of these, we ignore fields that we don't expect to find populated
         let action = match TxCode::from_u8(self.tx_type)? {
@@ -249,6 +383,26 @@ impl StoredTxAction {
             }
             TxCode::Deposit => TxAction::Deposit {},
             TxCode::Redeem => TxAction::Redeem {},
+            TxCode::Send => {
+                let from = self.address1.ok_or_else(transfer_addr_err)?;
+                let sender = self.address2.ok_or_else(transfer_addr_err)?;
+                let recipient = self.address3.ok_or_else(transfer_addr_err)?;
+                TxAction::Send {
+                    from,
+                    sender,
+                    recipient,
+                }
+            }
+            TxCode::IncreaseAllowance => {
+                let owner = self.address1.ok_or_else(allowance_addr_err)?;
+                let spender = self.address2.ok_or_else(allowance_addr_err)?;
+                TxAction::IncreaseAllowance { owner, spender }
+            }
+            TxCode::DecreaseAllowance => {
+                let owner = self.address1.ok_or_else(allowance_addr_err)?;
+                let spender = self.address2.ok_or_else(allowance_addr_err)?;
+                TxAction::DecreaseAllowance { owner, spender }
+            }
         };
 
         Ok(action)
@@ -294,8 +448,45 @@ impl StoredRichTx {
         })
     }
 
+    fn matches(&self, for_address: &Addr, filter: &TxFilter) -> StdResult<bool> {
+        if let Some(actions) = &filter.actions {
+            let action_type = TxCode::from_u8(self.action.tx_type)?.action_type();
+            if !actions.contains(&action_type) {
+                return Ok(false);
+            }
+        }
+
+        if let Some(counterparty) = &filter.counterparty {
+            if ![&self.action.address1, &self.action.address2, &self.action.address3]
+                .iter()
+                .any(|address| match address {
+                    Some(address) => address != for_address && address.as_str() == counterparty,
+                    None => false,
+                })
+            {
+                return Ok(false);
+            }
+        }
+
+        if let Some(start_time) = filter.start_time {
+            if self.block_time < Timestamp::from_seconds(start_time) {
+                return Ok(false);
+            }
+        }
+
+        if let Some(end_time) = filter.end_time {
+            if self.block_time >= Timestamp::from_seconds(end_time) {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
     fn into_legacy(self) -> StdResult<Tx> {
-        if self.action.tx_type == 0 {
+        if self.action.tx_type == TxCode::Transfer.to_u8()
+            || self.action.tx_type == TxCode::Send.to_u8()
+        {
             Ok(Tx {
                 id: self.id,
                 from: self.action.address1.unwrap(),
@@ -384,19 +575,109 @@ pub fn store_transfer(
         block,
     );
 
+    append_transfer(storage, &tx, owner, sender, receiver)
+}
+
+#[cfg(feature = "snip20-impl")]
+#[allow(clippy::too_many_arguments)]
+pub fn store_send(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    sender: &Addr,
+    receiver: &Addr,
+    amount: Uint128,
+    denom: String,
+    memo: Option<String>,
+    block: &BlockInfo,
+) -> StdResult<()> {
+    let id = increment_tx_count(storage)?;
+    let coins = Coin {
+        denom,
+        amount: amount.into(),
+    };
+    let tx = StoredRichTx::new(
+        id,
+        StoredTxAction::send(owner.clone(), sender.clone(), receiver.clone()),
+        coins,
+        memo,
+        block,
+    );
+
+    append_transfer(storage, &tx, owner, sender, receiver)
+}
+
+#[cfg(feature = "snip20-impl")]
+fn append_transfer(
+    storage: &mut dyn Storage,
+    tx: &StoredRichTx,
+    owner: &Addr,
+    sender: &Addr,
+    receiver: &Addr,
+) -> StdResult<()> {
     // Write to the owners history if it's different from the other two addresses
     if owner != sender && owner != receiver {
         // crate::c_std::debug_print("saving transaction history for owner");
-        UserTXTotal::append(storage, owner, &tx)?;
+        UserTXTotal::append(storage, owner, tx)?;
     }
     // Write to the sender's history if it's different from the receiver
     if sender != receiver {
         // crate::c_std::debug_print("saving transaction history for sender");
-        UserTXTotal::append(storage, sender, &tx)?;
+        UserTXTotal::append(storage, sender, tx)?;
     }
     // Always write to the recipient's history
     // crate::c_std::debug_print("saving transaction history for receiver");
-    UserTXTotal::append(storage, receiver, &tx)?;
+    UserTXTotal::append(storage, receiver, tx)?;
+
+    Ok(())
+}
+
+#[cfg(feature = "snip20-impl")]
+pub fn store_increase_allowance(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    spender: &Addr,
+    amount: Uint128,
+    denom: String,
+    block: &BlockInfo,
+) -> StdResult<()> {
+    let action = StoredTxAction::increase_allowance(owner.clone(), spender.clone());
+    store_allowance_change(storage, action, owner, spender, amount, denom, block)
+}
+
+#[cfg(feature = "snip20-impl")]
+pub fn store_decrease_allowance(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    spender: &Addr,
+    amount: Uint128,
+    denom: String,
+    block: &BlockInfo,
+) -> StdResult<()> {
+    let action = StoredTxAction::decrease_allowance(owner.clone(), spender.clone());
+    store_allowance_change(storage, action, owner, spender, amount, denom, block)
+}
+
+#[cfg(feature = "snip20-impl")]
+fn store_allowance_change(
+    storage: &mut dyn Storage,
+    action: StoredTxAction,
+    owner: &Addr,
+    spender: &Addr,
+    amount: Uint128,
+    denom: String,
+    block: &BlockInfo,
+) -> StdResult<()> {
+    let id = increment_tx_count(storage)?;
+    let coins = Coin {
+        denom,
+        amount: amount.into(),
+    };
+    let tx = StoredRichTx::new(id, action, coins, None, block);
+
+    if owner != spender {
+        UserTXTotal::append(storage, spender, &tx)?;
+    }
+    UserTXTotal::append(storage, owner, &tx)?;
 
     Ok(())
 }
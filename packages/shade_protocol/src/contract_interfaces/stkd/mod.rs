@@ -95,6 +95,13 @@ pub enum QueryMsg {
         address: Addr,
         key: String,
     },
+    /// address' token balance and unbondings valued in SCRT
+    Holdings {
+        address: Addr,
+        key: String,
+        /// time in seconds since 01/01/1970.
+        time: u64,
+    },
 }
 
 impl Query for QueryMsg {
@@ -147,6 +154,16 @@ pub enum QueryAnswer {
     Balance {
         amount: Uint128,
     },
+    Holdings {
+        /// amount of SCRT from matured unbondings
+        claimable_scrt: Uint128,
+        /// amount of SCRT still unbonding
+        unbonding_scrt: Uint128,
+        /// derivative token balance
+        token_balance: Uint128,
+        /// derivative token balance valued in SCRT
+        token_balance_value_in_scrt: Uint128,
+    },
 }
 
 /// validators and their weights
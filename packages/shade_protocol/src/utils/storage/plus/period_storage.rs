@@ -33,7 +33,6 @@ where
     Ser: Serde,
 {
     all: Map<'a, u64, Vec<T>, Ser>,
-    recent: Item<'a, Vec<u64>>,
 
     /* keys are date formatted strings "%Y-%m-%dT%h"
      * right-most data is truncated to categorize by higher order
@@ -47,10 +46,9 @@ where
     T: Serialize + DeserializeOwned + Clone,
     Ser: Serde,
 {
-    pub const fn new(all: &'a str, recent: &'a str, timed: &'a str) -> Self {
+    pub const fn new(all: &'a str, timed: &'a str) -> Self {
         PeriodStorage {
             all: Map::new(all),
-            recent: Item::new(recent),
             timed: Map::new(timed),
         }
     }
@@ -76,16 +74,7 @@ where
     }
 
     pub fn push(&self, storage: &mut dyn Storage, ts: Timestamp, item: T) -> StdResult<()> {
-        let key = ts.seconds();
-        let mut recent = self.recent.may_load(storage)?.unwrap_or(vec![]);
-        if !recent.contains(&key) {
-            recent.push(key);
-            self.recent.save(storage, &recent)?;
-        }
-        let mut all = self.all.may_load(storage, key)?.unwrap_or(vec![]);
-        all.push(item);
-        self.all.save(storage, key, &all)?;
-        self.flush(storage)
+        self.append(storage, ts, &mut vec![item])
     }
 
     pub fn append(
@@ -95,33 +84,25 @@ where
         items: &mut Vec<T>,
     ) -> StdResult<()> {
         let key = ts.seconds();
-        let mut recent = self.recent.may_load(storage)?.unwrap_or(vec![]);
-        if !recent.contains(&key) {
-            recent.push(key);
-            self.recent.save(storage, &recent)?;
-        }
+        let new_items = items.clone();
         let mut all = self.all.may_load(storage, key)?.unwrap_or(vec![]);
         all.append(items);
         self.all.save(storage, key, &all)?;
 
-        self.flush(storage)
+        self.flush(storage, key, &new_items)
     }
 
-    /* This will move all "recents" into the time based storage
-     * This should likely be called at the end of execution that adds items
+    /* This will move the newly added items into the time based storage
+     * Only the new items are moved, so multiple writes in the same block aren't duplicated
      */
-    fn flush(&self, storage: &mut dyn Storage) -> StdResult<()> {
-        for seconds in self.recent.load(storage)? {
-            let mut items = self.all.load(storage, seconds)?;
-
-            for period in Period::iter() {
-                let k = map_key(seconds, period);
-                let mut cur_items = self.timed.may_load(storage, k.clone())?.unwrap_or(vec![]);
-                cur_items.append(&mut items.clone());
-                self.timed.save(storage, k, &cur_items)?;
-            }
+    fn flush(&self, storage: &mut dyn Storage, seconds: u64, items: &Vec<T>) -> StdResult<()> {
+        for period in Period::iter() {
+            let k = map_key(seconds, period);
+            let mut cur_items = self.timed.may_load(storage, k.clone())?.unwrap_or(vec![]);
+            cur_items.append(&mut items.clone());
+            self.timed.save(storage, k, &cur_items)?;
         }
-        self.recent.save(storage, &vec![])
+        Ok(())
     }
 }
 
@@ -131,10 +112,34 @@ mod test {
     use super::*;
     use crate::c_std::{MemoryStorage, Timestamp, Uint128};
 
+    #[test]
+    fn same_block_writes() {
+        let now = parse_utc_datetime(&"1995-11-13T00:00:00.00Z".to_string()).unwrap();
+        let ts = Timestamp::from_seconds(now.timestamp() as u64);
+        let mut storage = MemoryStorage::new();
+        pub const STORAGE: PeriodStorage<u128> = PeriodStorage::new("all", "timed");
+
+        STORAGE.push(&mut storage, ts, 1).unwrap();
+        STORAGE.push(&mut storage, ts, 2).unwrap();
+        STORAGE.append(&mut storage, ts, &mut vec![3, 5]).unwrap();
+
+        // earlier writes in the block aren't flushed again
+        let data = vec![1, 2, 3, 5];
+        assert_eq!(STORAGE.load(&storage, ts).unwrap(), data);
+        for period in Period::iter() {
+            assert_eq!(
+                STORAGE
+                    .load_period(&storage, ts.seconds(), period)
+                    .unwrap(),
+                data
+            );
+        }
+    }
+
     fn test_push(now: String) {
         let now = parse_utc_datetime(&"1995-11-13T00:00:00.00Z".to_string()).unwrap();
         let mut storage = MemoryStorage::new();
-        pub const STORAGE: PeriodStorage<u128> = PeriodStorage::new("all", "recent", "timed");
+        pub const STORAGE: PeriodStorage<u128> = PeriodStorage::new("all", "timed");
 
         let data = vec![1, 2, 3, 5, 10];
 
@@ -170,7 +175,7 @@ mod test {
     fn test_append(now: String) {
         let now = parse_utc_datetime(&"1995-11-13T00:00:00.00Z".to_string()).unwrap();
         let mut storage = MemoryStorage::new();
-        pub const STORAGE: PeriodStorage<u128> = PeriodStorage::new("all", "recent", "timed");
+        pub const STORAGE: PeriodStorage<u128> = PeriodStorage::new("all", "timed");
 
         let mut data = vec![1, 2, 3, 5, 10];
 
@@ -205,7 +210,7 @@ mod test {
         let mut now = parse_utc_datetime(&"1995-11-13T00:00:00.00Z".to_string()).unwrap();
 
         let mut storage = MemoryStorage::new();
-        pub const STORAGE: PeriodStorage<u128> = PeriodStorage::new("all", "recent", "timed");
+        pub const STORAGE: PeriodStorage<u128> = PeriodStorage::new("all", "timed");
 
         let mut data = vec![1, 2, 3, 5, 10];
         let mut added = vec![11, 12, 13, 15, 20];
@@ -260,7 +265,7 @@ mod test {
         let mut now = parse_utc_datetime(&"1995-11-13T00:00:00.00Z".to_string()).unwrap();
 
         let mut storage = MemoryStorage::new();
-        pub const STORAGE: PeriodStorage<u128> = PeriodStorage::new("all", "recent", "timed");
+        pub const STORAGE: PeriodStorage<u128> = PeriodStorage::new("all", "timed");
 
         let mut data = vec![1, 2, 3, 5, 10];
         let mut added = vec![11, 12, 13, 15, 20];
@@ -315,7 +320,7 @@ mod test {
         let mut now = parse_utc_datetime(&"1995-11-13T00:00:00.00Z".to_string()).unwrap();
 
         let mut storage = MemoryStorage::new();
-        pub const STORAGE: PeriodStorage<u128> = PeriodStorage::new("all", "recent", "timed");
+        pub const STORAGE: PeriodStorage<u128> = PeriodStorage::new("all", "timed");
 
         let mut data = vec![1, 2, 3, 5, 10];
         let mut added = vec![11, 12, 13, 15, 20];